@@ -3,9 +3,28 @@ set -euxo pipefail
 main() {
     cargo check --target $TARGET
     cargo check --target $TARGET --features unproven
+    for feature in adc bitbang bus calibration can capture digital-unproven dma erased event i2s i3c pdm power pwm qei qspi retry rng rtc storage timer-unproven trace typestate watchdog; do
+        cargo check --target $TARGET --features $feature
+    done
+    cargo check --target $TARGET --features alloc
+    cargo check --target $TARGET --features alloc,async,unproven
+    cargo check --target $TARGET --features async,unproven
+    cargo check --target $TARGET --features compat,unproven
+    cargo check --target $TARGET --features conformance,unproven
+    cargo check --target $TARGET --features critical-section,unproven
+    cargo check --target $TARGET --features fugit,unproven
+    cargo check --target $TARGET --features mock
+    cargo check --target $TARGET --features mock,unproven
+    cargo check --target $TARGET --features defmt,mock,unproven
+    cargo check --target $TARGET --features property,unproven
+    cargo check --target $TARGET --features serde,unproven
+
+    if [ $TARGET = x86_64-unknown-linux-gnu ]; then
+        cargo check --target $TARGET --features mock,std,unproven
+    fi
 
     if [ $TRAVIS_RUST_VERSION = nightly ]; then
-        cargo test --target $TARGET --features unproven
+        cargo test --target $TARGET --features async,mock,unproven
     fi
 }
 
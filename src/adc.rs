@@ -0,0 +1,229 @@
+//! Analog-digital conversion
+//!
+//! *This module is available if embedded-hal is built with the `"adc"` feature.*
+//!
+//! This module contains the non-blocking [`OneShot`] conversion trait, and what the ADC traits
+//! share: the error type and the mapping of pins to ADC channels.
+//!
+//! [`OneShot`]: trait.OneShot.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// ADC error kind
+///
+/// This represents a common set of ADC operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// A conversion result was overwritten before it was read
+    Overrun,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Overrun => "a conversion result was overwritten before it was read",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// ADC error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// ADC error type
+///
+/// All the ADC traits inherit from this trait so that an ADC defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// An analog pin that can be sampled by the `ADC` peripheral
+///
+/// HALs implement this trait for their pin types, mapping each pin to its channel, so that
+/// sampling a pin with the wrong ADC is a compile time error.
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::adc::Channel;
+///
+/// struct Adc1;
+/// struct Gpio1Pin1;
+///
+/// impl Channel<Adc1> for Gpio1Pin1 {
+///     type ID = u8;
+///
+///     fn channel() -> u8 { 7 }
+/// }
+///
+/// fn main() {
+///     assert_eq!(<Gpio1Pin1 as Channel<Adc1>>::channel(), 7);
+/// }
+/// ```
+pub trait Channel<ADC> {
+    /// Channel ID type
+    ///
+    /// A type used to identify the channel, e.g. `u8` for a single ADC, or a tuple of the ADC
+    /// number and the channel number for devices where pins can be sampled by several ADCs.
+    type ID;
+
+    /// Returns the ID of the channel the pin is connected to
+    fn channel() -> Self::ID;
+}
+
+/// Single conversion on a channel
+///
+/// `ADC` is the ADC peripheral the `Pin` is sampled with, and `Word` the type of the conversion
+/// result, e.g. `u16` for a 12-bit ADC.
+///
+/// `read` starts a conversion on `pin` if none is in progress, and returns `WouldBlock` until it
+/// completes. Once it has returned the result, the next call starts a new conversion.
+///
+/// # Examples
+///
+/// Measuring the voltage of a battery behind a 1:2 voltage divider
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::adc::{Channel, OneShot};
+///
+/// fn battery_mv<A, P>(adc: &mut A, pin: &mut P) -> Result<u32, A::Error>
+/// where
+///     A: OneShot<Adc1, u16, P>,
+///     P: Channel<Adc1>,
+/// {
+///     let sample = block!(adc.read(pin))?;
+///
+///     // 12-bit conversion, 3.3 V reference
+///     Ok(u32::from(sample) * 3300 / 4095 * 2)
+/// }
+///
+/// fn main() {
+///     let (mut adc, mut pin): (Adc1, Gpio1Pin1) = {
+///         // ..
+/// #       (Adc1 { busy: false }, Gpio1Pin1)
+///     };
+///
+///     assert_eq!(battery_mv(&mut adc, &mut pin), Ok(3700));
+/// }
+///
+/// # use hal::adc::{ErrorKind, ErrorType};
+/// # struct Adc1 { busy: bool }
+/// # struct Gpio1Pin1;
+/// # impl Channel<Adc1> for Gpio1Pin1 {
+/// #     type ID = u8;
+/// #     fn channel() -> u8 { 1 }
+/// # }
+/// # impl ErrorType for Adc1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl<P: Channel<Adc1, ID = u8>> OneShot<Adc1, u16, P> for Adc1 {
+/// #     fn read(&mut self, _: &mut P) -> nb::Result<u16, ErrorKind> {
+/// #         if P::channel() != 1 {
+/// #             return Err(nb::Error::Other(ErrorKind::Other));
+/// #         }
+/// #         self.busy = !self.busy;
+/// #         if self.busy { Err(nb::Error::WouldBlock) } else { Ok(2296) }
+/// #     }
+/// # }
+/// ```
+pub trait OneShot<ADC, Word, Pin>: ErrorType
+where
+    Pin: Channel<ADC>,
+{
+    /// Samples `pin` once, returning the conversion result
+    fn read(&mut self, pin: &mut Pin) -> nb::Result<Word, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, ADC, Word, Pin> OneShot<ADC, Word, Pin> for Box<T>
+where
+    T: OneShot<ADC, Word, Pin> + ?Sized,
+    Pin: Channel<ADC>,
+{
+    fn read(&mut self, pin: &mut Pin) -> nb::Result<Word, Self::Error> {
+        (**self).read(pin)
+    }
+}
+
+impl<T, ADC, Word, Pin> OneShot<ADC, Word, Pin> for &mut T
+where
+    T: OneShot<ADC, Word, Pin> + ?Sized,
+    Pin: Channel<ADC>,
+{
+    fn read(&mut self, pin: &mut Pin) -> nb::Result<Word, Self::Error> {
+        (**self).read(pin)
+    }
+}
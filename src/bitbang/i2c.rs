@@ -0,0 +1,306 @@
+//! Software I2C
+
+use core::cmp;
+
+use blocking::delay::DelayUs;
+use blocking::i2c::{Operation, Read, Transactional, Write, WriteRead};
+use digital::{InputPin, OutputPin};
+use i2c::{ErrorKind, ErrorType, NoAcknowledgeSource};
+
+/// Longest time, in microseconds, that [`I2c`](struct.I2c.html) waits for the slaves to release
+/// the clock line: the 25 ms clock low timeout of SMBus
+pub const MAX_STRETCH_US: u32 = 25_000;
+
+/// I2C master driven by two open-drain pins
+///
+/// Driving a pin high releases the line, which is pulled up by a resistor, and reading it returns
+/// the level of the line. `I2c` supports 7-bit addresses, clock stretching, i.e. it waits for
+/// the slaves that hold the clock line low, and the arbitration with other masters: the
+/// transaction fails with `ErrorKind::ArbitrationLoss` as soon as another master drives the data
+/// line low while `I2c` released it. A slave that holds the clock line low for more than
+/// [`MAX_STRETCH_US`] fails the transaction with `ErrorKind::Bus`. A stop condition ends the
+/// transactions that fail for other reasons.
+///
+/// Empty reads are skipped: no address is sent for them, so a transaction that only contains
+/// empty reads leaves the bus untouched.
+///
+/// [`MAX_STRETCH_US`]: constant.MAX_STRETCH_US.html
+///
+/// # Examples
+///
+/// Probing an empty bus
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::bitbang::i2c::I2c;
+/// use hal::blocking::i2c::Write;
+/// use hal::i2c::{ErrorKind, NoAcknowledgeSource};
+///
+/// fn main() {
+///     let (pb6, pb7, delay): (Pb6, Pb7, Delay) = {
+///         // ..
+/// #       (Pin(true), Pin(true), Delay)
+///     };
+///
+///     // 100 kHz: the clock is high for 5 us, and low for 5 us
+///     let mut i2c = I2c::new(pb6, pb7, delay, 5);
+///
+///     assert_eq!(
+///         i2c.write(0x76, &[0xd0]),
+///         Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+///     );
+/// }
+///
+/// # use hal::blocking::delay::DelayUs;
+/// # use hal::digital::{InputPin, OutputPin};
+/// # struct Pin(bool);
+/// # type Pb6 = Pin; type Pb7 = Pin;
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) { self.0 = false }
+/// #     fn set_high(&mut self) { self.0 = true }
+/// # }
+/// # impl InputPin for Pin {
+/// #     fn is_high(&self) -> bool { self.0 }
+/// #     fn is_low(&self) -> bool { !self.0 }
+/// # }
+/// # struct Delay;
+/// # impl DelayUs<u32> for Delay { fn delay_us(&mut self, _: u32) {} }
+/// ```
+#[derive(Debug)]
+pub struct I2c<SCL, SDA, D> {
+    scl: SCL,
+    sda: SDA,
+    delay: D,
+    half_period_us: u32,
+}
+
+impl<SCL, SDA, D> I2c<SCL, SDA, D>
+where
+    SCL: OutputPin + InputPin,
+    SDA: OutputPin + InputPin,
+    D: DelayUs<u32>,
+{
+    /// Creates a master whose clock is high, and low, for `half_period_us` microseconds
+    ///
+    /// Both lines are released.
+    pub fn new(mut scl: SCL, mut sda: SDA, delay: D, half_period_us: u32) -> Self {
+        scl.set_high();
+        sda.set_high();
+        I2c {
+            scl,
+            sda,
+            delay,
+            half_period_us,
+        }
+    }
+
+    /// Returns the clock and data pins and the delay
+    pub fn into_inner(self) -> (SCL, SDA, D) {
+        (self.scl, self.sda, self.delay)
+    }
+
+    fn wait(&mut self) {
+        self.delay.delay_us(self.half_period_us);
+    }
+
+    /// Releases the clock line and waits, up to `MAX_STRETCH_US`, for the slaves to release it too
+    fn release_scl(&mut self) -> Result<(), ErrorKind> {
+        self.scl.set_high();
+        let step = cmp::max(self.half_period_us, 1);
+        let mut waited = 0;
+        while self.scl.is_low() {
+            if waited >= MAX_STRETCH_US {
+                return Err(ErrorKind::Bus);
+            }
+            self.delay.delay_us(step);
+            waited += step;
+        }
+        Ok(())
+    }
+
+    /// Start condition, or repeated start condition if the clock line is low
+    fn start(&mut self) -> Result<(), ErrorKind> {
+        self.sda.set_high();
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+        self.sda.set_low();
+        self.wait();
+        self.scl.set_low();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), ErrorKind> {
+        self.sda.set_low();
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+        self.sda.set_high();
+        self.wait();
+        Ok(())
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), ErrorKind> {
+        if bit {
+            self.sda.set_high();
+        } else {
+            self.sda.set_low();
+        }
+        self.wait();
+        self.release_scl()?;
+        if bit && self.sda.is_low() {
+            return Err(ErrorKind::ArbitrationLoss);
+        }
+        self.wait();
+        self.scl.set_low();
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, ErrorKind> {
+        self.sda.set_high();
+        self.wait();
+        self.release_scl()?;
+        let bit = self.sda.is_high();
+        self.wait();
+        self.scl.set_low();
+        Ok(bit)
+    }
+
+    fn write_byte(&mut self, byte: u8, source: NoAcknowledgeSource) -> Result<(), ErrorKind> {
+        for bit in (0..8).rev() {
+            self.write_bit(byte & (1 << bit) != 0)?;
+        }
+
+        // a high acknowledge bit is a NACK
+        if self.read_bit()? {
+            Err(ErrorKind::NoAcknowledge(source))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn read_byte(&mut self, ack: bool) -> Result<u8, ErrorKind> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | self.read_bit()? as u8;
+        }
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+
+    /// Transfers `operations`, with a (repeated) start condition and the address whenever the
+    /// direction changes, skipping the empty reads
+    ///
+    /// Returns whether a start condition was sent.
+    fn operations(&mut self, address: u8, operations: &mut [Operation]) -> Result<bool, ErrorKind> {
+        let mut reading = None;
+        for i in 0..operations.len() {
+            let mut read_follows = false;
+            for next in &operations[i + 1..] {
+                match *next {
+                    Operation::Read(&mut []) => continue,
+                    Operation::Read(_) => read_follows = true,
+                    Operation::Write(_) => {}
+                }
+                break;
+            }
+            match operations[i] {
+                Operation::Read(ref mut buffer) => {
+                    if buffer.is_empty() {
+                        continue;
+                    }
+                    if reading != Some(true) {
+                        self.start()?;
+                        self.write_byte((address << 1) | 1, NoAcknowledgeSource::Address)?;
+                    }
+                    let len = buffer.len();
+                    for (i, byte) in buffer.iter_mut().enumerate() {
+                        // NACK the last byte before a repeated start or a stop
+                        *byte = self.read_byte(i + 1 < len || read_follows)?;
+                    }
+                    reading = Some(true);
+                }
+                Operation::Write(bytes) => {
+                    if reading != Some(false) {
+                        self.start()?;
+                        self.write_byte(address << 1, NoAcknowledgeSource::Address)?;
+                    }
+                    for &byte in bytes {
+                        self.write_byte(byte, NoAcknowledgeSource::Data)?;
+                    }
+                    reading = Some(false);
+                }
+            }
+        }
+        Ok(reading.is_some())
+    }
+
+    fn transaction(&mut self, address: u8, operations: &mut [Operation]) -> Result<(), ErrorKind> {
+        match self.operations(address, operations) {
+            // only empty reads: nothing was sent
+            Ok(false) => Ok(()),
+            // the other masters own the bus after an arbitration loss, and no stop condition can
+            // be sent while a slave holds the clock line low
+            Err(ErrorKind::ArbitrationLoss) => Err(ErrorKind::ArbitrationLoss),
+            Err(ErrorKind::Bus) => Err(ErrorKind::Bus),
+            result => {
+                let stopped = self.stop();
+                result.and(stopped)
+            }
+        }
+    }
+}
+
+impl<SCL, SDA, D> ErrorType for I2c<SCL, SDA, D> {
+    type Error = ErrorKind;
+}
+
+impl<SCL, SDA, D> Read for I2c<SCL, SDA, D>
+where
+    SCL: OutputPin + InputPin,
+    SDA: OutputPin + InputPin,
+    D: DelayUs<u32>,
+{
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), ErrorKind> {
+        self.transaction(address, &mut [Operation::Read(buffer)])
+    }
+}
+
+impl<SCL, SDA, D> Write for I2c<SCL, SDA, D>
+where
+    SCL: OutputPin + InputPin,
+    SDA: OutputPin + InputPin,
+    D: DelayUs<u32>,
+{
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), ErrorKind> {
+        self.transaction(address, &mut [Operation::Write(bytes)])
+    }
+}
+
+impl<SCL, SDA, D> WriteRead for I2c<SCL, SDA, D>
+where
+    SCL: OutputPin + InputPin,
+    SDA: OutputPin + InputPin,
+    D: DelayUs<u32>,
+{
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), ErrorKind> {
+        self.transaction(address, &mut [Operation::Write(bytes), Operation::Read(buffer)])
+    }
+}
+
+impl<SCL, SDA, D> Transactional for I2c<SCL, SDA, D>
+where
+    SCL: OutputPin + InputPin,
+    SDA: OutputPin + InputPin,
+    D: DelayUs<u32>,
+{
+    fn exec(&mut self, address: u8, operations: &mut [Operation]) -> Result<(), ErrorKind> {
+        self.transaction(address, operations)
+    }
+}
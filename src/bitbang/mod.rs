@@ -0,0 +1,60 @@
+//! Software implementations of the buses
+//!
+//! *This module is available if embedded-hal is built with the `"bitbang"` feature, which enables
+//! the `"digital-unproven"` feature.*
+//!
+//! [`Spi`] and [`I2c`] drive general purpose pins, and time the clock with a blocking delay, to
+//! implement the blocking bus traits on any device that implements the digital traits: they are
+//! a fallback for devices with too few peripherals, or for pins that aren't routed to one. They
+//! are slow, and their timing is only as accurate as the delay, so a hardware peripheral should
+//! be preferred when there's one.
+//!
+//! [`Spi`]: spi/struct.Spi.html
+//! [`I2c`]: i2c/struct.I2c.html
+//!
+//! # Examples
+//!
+//! A device on a software SPI bus, whose chip select is handled by an
+//! [`ExclusiveDevice`](../blocking/spi/struct.ExclusiveDevice.html)
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::bitbang::spi::Spi;
+//! use hal::blocking::spi::{ExclusiveDevice, Operation, SpiDevice};
+//! use hal::spi::{Mode, Phase, Polarity};
+//!
+//! fn main() {
+//!     let (pb3, pb4, pb5, pa15, delay): (Pb3, Pb4, Pb5, Pa15, Delay) = {
+//!         // ..
+//! #       (Pin, Pin, Pin, Pin, Delay)
+//!     };
+//!
+//!     let mode = Mode {
+//!         polarity: Polarity::IdleLow,
+//!         phase: Phase::CaptureOnFirstTransition,
+//!     };
+//!     // 100 kHz: the clock is high for 5 us, and low for 5 us
+//!     let spi = Spi::new(mode, pb3, pb5, pb4, delay, 5);
+//!     let mut device = ExclusiveDevice::new(spi, pa15);
+//!
+//!     let mut id = [0];
+//!     device.transaction(&mut [Operation::Write(&[0xd0]), Operation::Transfer(&mut id)]).unwrap();
+//! #   assert_eq!(id, [0xff]);
+//! }
+//!
+//! # use hal::blocking::delay::DelayUs;
+//! # use hal::digital::{InputPin, OutputPin};
+//! # struct Pin;
+//! # type Pb3 = Pin; type Pb4 = Pin; type Pb5 = Pin; type Pa15 = Pin;
+//! # impl OutputPin for Pin { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+//! # impl InputPin for Pin {
+//! #     fn is_high(&self) -> bool { true }
+//! #     fn is_low(&self) -> bool { false }
+//! # }
+//! # struct Delay;
+//! # impl DelayUs<u32> for Delay { fn delay_us(&mut self, _: u32) {} }
+//! ```
+
+pub mod i2c;
+pub mod spi;
@@ -0,0 +1,209 @@
+//! Software SPI
+
+use blocking;
+use blocking::delay::DelayUs;
+use digital::{InputPin, OutputPin, PinState};
+use nb;
+use spi::{self, ErrorType, Mode, Phase, Polarity};
+use void::Void;
+
+/// SPI bus driven by three pins
+///
+/// The words are `u8`s, sent most significant bit first. `Spi` implements `spi::FullDuplex<u8>`,
+/// where `send` shifts a whole word out, and in, before returning, and the blocking traits on top
+/// of it; like with the hardware peripherals, `read` must be called after each `send`, and
+/// returns `WouldBlock` otherwise. It never fails.
+///
+/// `Spi` is a bus: the chip select pins of the devices are driven by e.g. an
+/// [`ExclusiveDevice`](../../blocking/spi/struct.ExclusiveDevice.html).
+///
+/// # Examples
+///
+/// Checking the wiring by connecting MOSI to MISO
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::bitbang::spi::Spi;
+/// use hal::blocking::spi::Transfer;
+/// use hal::spi::{Mode, Phase, Polarity};
+///
+/// use std::cell::Cell;
+///
+/// fn main() {
+///     let wire = Cell::new(false);
+///     let (sck, mosi, miso) = (Pin(Cell::new(false)), Wire(&wire), Wire(&wire));
+///
+///     let mode = Mode {
+///         polarity: Polarity::IdleHigh,
+///         phase: Phase::CaptureOnSecondTransition,
+///     };
+///     let mut spi = Spi::new(mode, sck, mosi, miso, Delay, 1);
+///
+///     assert_eq!(spi.transfer(&mut [0xa5, 0x0f]).unwrap(), [0xa5, 0x0f]);
+/// #   assert!(spi.into_inner().0 .0.get());
+/// }
+///
+/// # use hal::blocking::delay::DelayUs;
+/// # use hal::digital::{InputPin, OutputPin};
+/// # struct Pin(Cell<bool>);
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) { self.0.set(false) }
+/// #     fn set_high(&mut self) { self.0.set(true) }
+/// # }
+/// # struct Wire<'a>(&'a Cell<bool>);
+/// # impl<'a> OutputPin for Wire<'a> {
+/// #     fn set_low(&mut self) { self.0.set(false) }
+/// #     fn set_high(&mut self) { self.0.set(true) }
+/// # }
+/// # impl<'a> InputPin for Wire<'a> {
+/// #     fn is_high(&self) -> bool { self.0.get() }
+/// #     fn is_low(&self) -> bool { !self.0.get() }
+/// # }
+/// # struct Delay;
+/// # impl DelayUs<u32> for Delay { fn delay_us(&mut self, _: u32) {} }
+/// ```
+#[derive(Debug)]
+pub struct Spi<SCK, MOSI, MISO, D> {
+    sck: SCK,
+    mosi: MOSI,
+    miso: MISO,
+    delay: D,
+    mode: Mode,
+    half_period_us: u32,
+    received: Option<u8>,
+}
+
+impl<SCK, MOSI, MISO, D> Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+    /// Creates a bus in `mode`, whose clock is high, and low, for `half_period_us` microseconds
+    ///
+    /// `sck` is driven to the idle level of the clock.
+    pub fn new(
+        mode: Mode,
+        sck: SCK,
+        mosi: MOSI,
+        miso: MISO,
+        delay: D,
+        half_period_us: u32,
+    ) -> Self {
+        let mut spi = Spi {
+            sck,
+            mosi,
+            miso,
+            delay,
+            mode,
+            half_period_us,
+            received: None,
+        };
+        spi.idle();
+        spi
+    }
+
+    /// Returns the clock, MOSI and MISO pins and the delay
+    pub fn into_inner(self) -> (SCK, MOSI, MISO, D) {
+        (self.sck, self.mosi, self.miso, self.delay)
+    }
+
+    fn idle(&mut self) {
+        match self.mode.polarity {
+            Polarity::IdleLow => self.sck.set_low(),
+            Polarity::IdleHigh => self.sck.set_high(),
+        }
+    }
+
+    fn active(&mut self) {
+        match self.mode.polarity {
+            Polarity::IdleLow => self.sck.set_high(),
+            Polarity::IdleHigh => self.sck.set_low(),
+        }
+    }
+
+    fn shift(&mut self, word: u8) -> u8 {
+        let mut read = 0;
+        for bit in (0..8).rev() {
+            let state = PinState::from(word & (1 << bit) != 0);
+            match self.mode.phase {
+                Phase::CaptureOnFirstTransition => {
+                    self.mosi.set_state(state);
+                    self.delay.delay_us(self.half_period_us);
+                    self.active();
+                    read = (read << 1) | self.miso.is_high() as u8;
+                    self.delay.delay_us(self.half_period_us);
+                    self.idle();
+                }
+                Phase::CaptureOnSecondTransition => {
+                    self.active();
+                    self.mosi.set_state(state);
+                    self.delay.delay_us(self.half_period_us);
+                    self.idle();
+                    read = (read << 1) | self.miso.is_high() as u8;
+                    self.delay.delay_us(self.half_period_us);
+                }
+            }
+        }
+        read
+    }
+}
+
+impl<SCK, MOSI, MISO, D> ErrorType for Spi<SCK, MOSI, MISO, D> {
+    type Error = Void;
+}
+
+impl<SCK, MOSI, MISO, D> spi::FullDuplex<u8> for Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+    fn read(&mut self) -> nb::Result<u8, Void> {
+        self.received.take().ok_or(nb::Error::WouldBlock)
+    }
+
+    fn send(&mut self, word: u8) -> nb::Result<(), Void> {
+        self.received = Some(self.shift(word));
+        Ok(())
+    }
+}
+
+impl<SCK, MOSI, MISO, D> blocking::spi::transfer::Default<u8> for Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+}
+
+impl<SCK, MOSI, MISO, D> blocking::spi::write::Default<u8> for Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+}
+
+impl<SCK, MOSI, MISO, D> blocking::spi::write_iter::Default<u8> for Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+}
+
+impl<SCK, MOSI, MISO, D> blocking::spi::transactional::Default<u8> for Spi<SCK, MOSI, MISO, D>
+where
+    SCK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    D: DelayUs<u32>,
+{
+}
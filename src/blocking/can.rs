@@ -0,0 +1,83 @@
+//! Blocking CAN API
+//!
+//! *This module is available if embedded-hal is built with the `"can"` feature.*
+
+/// Transmitting frames (blocking variant)
+pub trait Transmit<F>: ::can::ErrorType
+where
+    F: ::can::Frame,
+{
+    /// Transmits `frame`, blocking until it has been put in a transmit buffer
+    fn transmit(&mut self, frame: &F) -> Result<(), Self::Error>;
+}
+
+/// Receiving frames (blocking variant)
+pub trait Receive<F>: ::can::ErrorType
+where
+    F: ::can::Frame,
+{
+    /// Blocks until a frame is received, and returns it
+    fn receive(&mut self) -> Result<F, Self::Error>;
+}
+
+/// Blocking CAN transmit
+pub mod transmit {
+    /// Marker trait to opt into default blocking transmit implementation
+    ///
+    /// Implementers of [`can::Transmit`] can implement this marker trait for their type. Doing so
+    /// will automatically provide the default implementation of [`blocking::can::Transmit`] for
+    /// the type.
+    ///
+    /// The default implementation transmits again the lower priority frames `can::Transmit`
+    /// returns, so it only returns once they have all been put back in a transmit buffer.
+    ///
+    /// [`can::Transmit`]: ../../../can/trait.Transmit.html
+    /// [`blocking::can::Transmit`]: ../trait.Transmit.html
+    pub trait Default<F>: ::can::Transmit<F>
+    where
+        F: ::can::Frame,
+    {
+    }
+
+    impl<C, F> ::blocking::can::Transmit<F> for C
+    where
+        C: Default<F>,
+        F: ::can::Frame,
+    {
+        fn transmit(&mut self, frame: &F) -> Result<(), Self::Error> {
+            let mut replaced = block!(::can::Transmit::transmit(self, frame))?;
+            while let Some(frame) = replaced {
+                replaced = block!(::can::Transmit::transmit(self, &frame))?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Blocking CAN receive
+pub mod receive {
+    /// Marker trait to opt into default blocking receive implementation
+    ///
+    /// Implementers of [`can::Receive`] can implement this marker trait for their type. Doing so
+    /// will automatically provide the default implementation of [`blocking::can::Receive`] for
+    /// the type.
+    ///
+    /// [`can::Receive`]: ../../../can/trait.Receive.html
+    /// [`blocking::can::Receive`]: ../trait.Receive.html
+    pub trait Default<F>: ::can::Receive<F>
+    where
+        F: ::can::Frame,
+    {
+    }
+
+    impl<C, F> ::blocking::can::Receive<F> for C
+    where
+        C: Default<F>,
+        F: ::can::Frame,
+    {
+        fn receive(&mut self) -> Result<F, Self::Error> {
+            block!(::can::Receive::receive(self))
+        }
+    }
+}
@@ -6,6 +6,98 @@
 //! higher level abstractions like I/O operations with timeouts. OTOH, these delays traits only
 //! provide *blocking* functionality. Note that you can also use the `timer::CountDown` trait to
 //! implement blocking delays.
+//!
+//! # Implementing the traits for several widths
+//!
+//! Drivers pick the width of the delays they need, e.g. `DelayUs<u16>` for the 18 ms start pulse
+//! of a DHT22 sensor, so implementations SHOULD implement the traits for `u8`, `u16` and `u32`.
+//! The narrower widths can forward to the widest one.
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::delay::{DelayMs, DelayUs};
+//! use hal::digital::OutputPin;
+//!
+//! /// A delay based on a 1 MHz timer
+//! struct Delay {
+//!     // ..
+//! #   elapsed_us: u64,
+//! }
+//!
+//! impl DelayUs<u32> for Delay {
+//!     fn delay_us(&mut self, us: u32) {
+//!         // ..
+//! #       self.elapsed_us += u64::from(us);
+//!     }
+//! }
+//!
+//! impl DelayUs<u16> for Delay {
+//!     fn delay_us(&mut self, us: u16) {
+//!         self.delay_us(u32::from(us))
+//!     }
+//! }
+//!
+//! impl DelayUs<u8> for Delay {
+//!     fn delay_us(&mut self, us: u8) {
+//!         self.delay_us(u32::from(us))
+//!     }
+//! }
+//!
+//! impl DelayMs<u32> for Delay {
+//!     fn delay_ms(&mut self, ms: u32) {
+//!         // avoid overflowing the microseconds
+//!         for _ in 0..ms {
+//!             self.delay_us(1_000_u32)
+//!         }
+//!     }
+//! }
+//!
+//! impl DelayMs<u16> for Delay {
+//!     fn delay_ms(&mut self, ms: u16) {
+//!         self.delay_ms(u32::from(ms))
+//!     }
+//! }
+//!
+//! impl DelayMs<u8> for Delay {
+//!     fn delay_ms(&mut self, ms: u8) {
+//!         self.delay_ms(u32::from(ms))
+//!     }
+//! }
+//!
+//! /// Sends the start signal of a DHT22
+//! fn start<P, D>(data: &mut P, delay: &mut D)
+//! where
+//!     P: OutputPin,
+//!     D: DelayUs<u16> + DelayUs<u8>,
+//! {
+//!     data.set_low();
+//!     delay.delay_us(18_000_u16);
+//!     data.set_high();
+//!     delay.delay_us(30_u8);
+//! }
+//!
+//! fn main() {
+//!     let mut data: Pa1 = {
+//!         // ..
+//! #       Pa1
+//!     };
+//!     let mut delay = Delay {
+//!         // ..
+//! #       elapsed_us: 0,
+//!     };
+//!
+//!     start(&mut data, &mut delay);
+//!     delay.delay_ms(1_u8);
+//! #   assert_eq!(delay.elapsed_us, 19_030);
+//! }
+//!
+//! # struct Pa1;
+//! # impl OutputPin for Pa1 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 
 /// Millisecond delay
 ///
@@ -16,6 +108,25 @@ pub trait DelayMs<UXX> {
     fn delay_ms(&mut self, ms: UXX);
 }
 
+#[cfg(feature = "alloc")]
+impl<T, UXX> DelayMs<UXX> for Box<T>
+where
+    T: DelayMs<UXX> + ?Sized,
+{
+    fn delay_ms(&mut self, ms: UXX) {
+        (**self).delay_ms(ms)
+    }
+}
+
+impl<T, UXX> DelayMs<UXX> for &mut T
+where
+    T: DelayMs<UXX> + ?Sized,
+{
+    fn delay_ms(&mut self, ms: UXX) {
+        (**self).delay_ms(ms)
+    }
+}
+
 /// Microsecond delay
 ///
 /// `UXX` denotes the range type of the delay time. `UXX` can be `u8`, `u16`, etc. A single type can
@@ -24,3 +135,22 @@ pub trait DelayUs<UXX> {
     /// Pauses execution for `us` microseconds
     fn delay_us(&mut self, us: UXX);
 }
+
+#[cfg(feature = "alloc")]
+impl<T, UXX> DelayUs<UXX> for Box<T>
+where
+    T: DelayUs<UXX> + ?Sized,
+{
+    fn delay_us(&mut self, us: UXX) {
+        (**self).delay_us(us)
+    }
+}
+
+impl<T, UXX> DelayUs<UXX> for &mut T
+where
+    T: DelayUs<UXX> + ?Sized,
+{
+    fn delay_us(&mut self, us: UXX) {
+        (**self).delay_us(us)
+    }
+}
@@ -1,10 +1,25 @@
 //! Blocking I2C API
+//!
+//! The traits are generic over the [`AddressMode`], either 7-bit addresses, [`SevenBitAddress`],
+//! the default, or 10-bit addresses, [`TenBitAddress`]. Every implementation supports 7-bit
+//! addresses; the ones that support 10-bit addresses also implement the traits for
+//! `TenBitAddress`.
+//!
+//! In both modes the address is right-aligned, in a `u8` or a `u16`, and the R/W bit is *not*
+//! part of it: the implementation adds it, and the `11110` prefix of 10-bit addresses. For example
+//! a BME280 whose SDO pin is tied to ground has the 7-bit address `0x76`, not `0xec`.
+//!
+//! [`AddressMode`]: ../../i2c/trait.AddressMode.html
+//! [`SevenBitAddress`]: ../../i2c/type.SevenBitAddress.html
+//! [`TenBitAddress`]: ../../i2c/type.TenBitAddress.html
 
-/// Blocking read
-pub trait Read {
-    /// Error type
-    type Error;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use i2c::{AddressMode, SevenBitAddress};
 
+/// Blocking read
+pub trait Read<A: AddressMode = SevenBitAddress>: ::i2c::ErrorType {
     /// Reads enough bytes from slave with `address` to fill `buffer`
     ///
     /// # I2C Events (contract)
@@ -23,14 +38,32 @@ pub trait Read {
     /// - `MAK` = master acknowledge
     /// - `NMAK` = master no acknowledge
     /// - `SP` = stop condition
-    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+    fn read(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>;
 }
 
-/// Blocking write
-pub trait Write {
-    /// Error type
-    type Error;
+#[cfg(feature = "alloc")]
+impl<T, A> Read<A> for Box<T>
+where
+    T: Read<A> + ?Sized,
+    A: AddressMode,
+{
+    fn read(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(address, buffer)
+    }
+}
+
+impl<T, A> Read<A> for &mut T
+where
+    T: Read<A> + ?Sized,
+    A: AddressMode,
+{
+    fn read(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(address, buffer)
+    }
+}
 
+/// Blocking write
+pub trait Write<A: AddressMode = SevenBitAddress>: ::i2c::ErrorType {
     /// Sends bytes to slave with address `addr`
     ///
     /// # I2C Events (contract)
@@ -47,14 +80,32 @@ pub trait Write {
     /// - `SAK` = slave acknowledge
     /// - `Bi` = ith byte of data
     /// - `SP` = stop condition
-    fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Self::Error>;
+    fn write(&mut self, addr: A, bytes: &[u8]) -> Result<(), Self::Error>;
 }
 
-/// Blocking write + read
-pub trait WriteRead {
-    /// Error type
-    type Error;
+#[cfg(feature = "alloc")]
+impl<T, A> Write<A> for Box<T>
+where
+    T: Write<A> + ?Sized,
+    A: AddressMode,
+{
+    fn write(&mut self, addr: A, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(addr, bytes)
+    }
+}
 
+impl<T, A> Write<A> for &mut T
+where
+    T: Write<A> + ?Sized,
+    A: AddressMode,
+{
+    fn write(&mut self, addr: A, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(addr, bytes)
+    }
+}
+
+/// Blocking write + read
+pub trait WriteRead<A: AddressMode = SevenBitAddress>: ::i2c::ErrorType {
     /// Sends bytes to slave with address `addr` and then reads enough bytes to fill `buffer` *in a
     /// single transaction*
     ///
@@ -79,8 +130,213 @@ pub trait WriteRead {
     /// - `SP` = stop condition
     fn write_read(
         &mut self,
-        address: u8,
+        address: A,
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error>;
 }
+
+#[cfg(feature = "alloc")]
+impl<T, A> WriteRead<A> for Box<T>
+where
+    T: WriteRead<A> + ?Sized,
+    A: AddressMode,
+{
+    fn write_read(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
+
+impl<T, A> WriteRead<A> for &mut T
+where
+    T: WriteRead<A> + ?Sized,
+    A: AddressMode,
+{
+    fn write_read(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
+
+/// Segment of a transaction
+#[derive(Debug, Eq, PartialEq)]
+pub enum Operation<'a> {
+    /// Reads enough bytes to fill the buffer
+    Read(&'a mut [u8]),
+    /// Writes the bytes
+    Write(&'a [u8]),
+}
+
+/// Blocking transactions made of any sequence of reads and writes
+///
+/// Devices such as EEPROMs need combined transactions that `WriteRead` can't express, e.g. a write
+/// of the memory address followed by the write of the data from a separate buffer: as both are
+/// writes, they are sent as one, and the device sees the data right after the address.
+///
+/// # Examples
+///
+/// Writing a page of an AT24C32 EEPROM without copying the data after the memory address
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::i2c::{Operation, Transactional};
+///
+/// fn write_page<I>(i2c: &mut I, address: u16, data: &[u8]) -> Result<(), I::Error>
+/// where
+///     I: Transactional,
+/// {
+///     let address = address.to_be_bytes();
+///     i2c.exec(0x50, &mut [Operation::Write(&address), Operation::Write(data)])
+/// }
+///
+/// fn main() {
+///     let mut i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     write_page(&mut i2c, 0x0100, b"hello").unwrap();
+/// }
+///
+/// # use hal::i2c::{ErrorKind, ErrorType};
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 { type Error = ErrorKind; }
+/// # impl Transactional for I2c1 {
+/// #     fn exec(&mut self, _: u8, _: &mut [Operation]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// ```
+pub trait Transactional<A: AddressMode = SevenBitAddress>: ::i2c::ErrorType {
+    /// Executes `operations` on slave with `address` *in a single transaction*
+    ///
+    /// # I2C Events (contract)
+    ///
+    /// ``` text
+    /// Master: ST SAD+R/W <operations 0..i> SR SAD+R/W <operations i..j> SR ... <operations k..N> SP
+    /// ```
+    ///
+    /// Where
+    ///
+    /// - `ST` = start condition
+    /// - `SAD+R/W` = slave address with 8th bit set to 1 before reads, and to 0 before writes
+    /// - `SR` = repeated start condition
+    /// - `SP` = stop condition
+    ///
+    /// Consecutive operations of the same kind are merged: their bytes are transferred as if they
+    /// were a single `Read::read` or `Write::write`, with no `SR` and no address in between. `SR`
+    /// and `SAD+R/W` are only sent when the direction changes, i.e. between a write and a read or
+    /// between a read and a write. In particular, the master acknowledges the last byte of a read
+    /// that is followed by another read, and sends `NMAK` only for the last byte before `SR` or
+    /// `SP`.
+    fn exec(&mut self, address: A, operations: &mut [Operation]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A> Transactional<A> for Box<T>
+where
+    T: Transactional<A> + ?Sized,
+    A: AddressMode,
+{
+    fn exec(&mut self, address: A, operations: &mut [Operation]) -> Result<(), Self::Error> {
+        (**self).exec(address, operations)
+    }
+}
+
+impl<T, A> Transactional<A> for &mut T
+where
+    T: Transactional<A> + ?Sized,
+    A: AddressMode,
+{
+    fn exec(&mut self, address: A, operations: &mut [Operation]) -> Result<(), Self::Error> {
+        (**self).exec(address, operations)
+    }
+}
+
+/// Fixed-size reads, for implementers of [`Read`]
+///
+/// [`Read`]: trait.Read.html
+///
+/// # Examples
+///
+/// Reading a 6-byte register block
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::i2c::{ReadExt, WriteReadExt};
+///
+/// fn main() {
+///     let mut i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     let id: [u8; 2] = i2c.read_exact(0x68).unwrap();
+///     let [x, _, y, _, z, _] = i2c.write_read_exact::<6>(0x68, &[0x3b]).unwrap();
+///     # let _ = (id, x, y, z);
+/// }
+///
+/// # use hal::blocking::i2c::{Read, WriteRead};
+/// # use hal::i2c::{ErrorKind, ErrorType};
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 { type Error = ErrorKind; }
+/// # impl Read for I2c1 {
+/// #     fn read(&mut self, _: u8, _: &mut [u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// # impl WriteRead for I2c1 {
+/// #     fn write_read(&mut self, _: u8, _: &[u8], _: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait ReadExt<A: AddressMode = SevenBitAddress>: Read<A> {
+    /// Reads `N` bytes from slave with `address`, and returns them
+    fn read_exact<const N: usize>(&mut self, address: A) -> Result<[u8; N], Self::Error> {
+        let mut buffer = [0; N];
+        self.read(address, &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl<T, A> ReadExt<A> for T
+where
+    T: Read<A> + ?Sized,
+    A: AddressMode,
+{
+}
+
+/// Fixed-size reads, for implementers of [`WriteRead`]
+///
+/// See [`ReadExt`] for an example.
+///
+/// [`WriteRead`]: trait.WriteRead.html
+/// [`ReadExt`]: trait.ReadExt.html
+pub trait WriteReadExt<A: AddressMode = SevenBitAddress>: WriteRead<A> {
+    /// Sends `bytes` to slave with `address` and then reads `N` bytes *in a single transaction*,
+    /// and returns them
+    fn write_read_exact<const N: usize>(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+    ) -> Result<[u8; N], Self::Error> {
+        let mut buffer = [0; N];
+        self.write_read(address, bytes, &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl<T, A> WriteReadExt<A> for T
+where
+    T: WriteRead<A> + ?Sized,
+    A: AddressMode,
+{
+}
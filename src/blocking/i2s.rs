@@ -0,0 +1,114 @@
+//! Blocking I2S API
+//!
+//! *This module is available if embedded-hal is built with the `"i2s"` feature.*
+//!
+//! # Examples
+//!
+//! Playing a square wave on a codec, whose interface implements the `nb` traits
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::i2s::Write;
+//!
+//! /// Plays `periods` periods of a 1 kHz square wave, at 48 kHz
+//! fn beep<I: Write<i16>>(i2s: &mut I, periods: usize) -> Result<(), I::Error> {
+//!     let mut period = [[0; 2]; 48];
+//!     for (i, frame) in period.iter_mut().enumerate() {
+//!         let sample = if i < 24 { i16::MAX / 4 } else { i16::MIN / 4 };
+//!         *frame = [sample, sample];
+//!     }
+//!
+//!     for _ in 0..periods {
+//!         i2s.write(&period)?;
+//!     }
+//!     Ok(())
+//! }
+//!
+//! fn main() {
+//!     let mut spi2: Spi2 = {
+//!         // ..
+//! #       Spi2(0)
+//!     };
+//!
+//!     beep(&mut spi2, 100).unwrap();
+//! #   assert_eq!(spi2.0, 4800);
+//! }
+//!
+//! # use hal::i2s::{ErrorKind, ErrorType};
+//! # struct Spi2(usize);
+//! # impl ErrorType for Spi2 { type Error = ErrorKind; }
+//! # impl hal::i2s::Write<i16> for Spi2 {
+//! #     fn write(&mut self, _: [i16; 2]) -> nb::Result<(), ErrorKind> {
+//! #         self.0 += 1;
+//! #         Ok(())
+//! #     }
+//! # }
+//! # impl hal::blocking::i2s::write::Default<i16> for Spi2 {}
+//! # extern crate nb;
+//! ```
+
+/// Read a block of stereo frames (blocking variant)
+pub trait Read<Word>: ::i2s::ErrorType {
+    /// Reads enough frames, `[left, right]`, to fill `frames`, blocking until `frames` is full
+    fn read(&mut self, frames: &mut [[Word; 2]]) -> Result<(), Self::Error>;
+}
+
+/// Write a block of stereo frames (blocking variant)
+pub trait Write<Word>: ::i2s::ErrorType {
+    /// Writes all the `frames`, `[left, right]`, blocking until the last one has been handed to
+    /// the interface
+    fn write(&mut self, frames: &[[Word; 2]]) -> Result<(), Self::Error>;
+}
+
+/// Blocking I2S read
+pub mod read {
+    /// Marker trait to opt into default blocking read implementation
+    ///
+    /// Implementers of [`i2s::Read`] can implement this marker trait for their type. Doing so will
+    /// automatically provide the default implementation of [`blocking::i2s::Read`] for the type.
+    ///
+    /// [`i2s::Read`]: ../../../i2s/trait.Read.html
+    /// [`blocking::i2s::Read`]: ../trait.Read.html
+    pub trait Default<Word>: ::i2s::Read<Word> {}
+
+    impl<I, Word> ::blocking::i2s::Read<Word> for I
+    where
+        I: Default<Word>,
+    {
+        fn read(&mut self, frames: &mut [[Word; 2]]) -> Result<(), Self::Error> {
+            for frame in frames.iter_mut() {
+                *frame = block!(::i2s::Read::read(self))?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Blocking I2S write
+pub mod write {
+    /// Marker trait to opt into default blocking write implementation
+    ///
+    /// Implementers of [`i2s::Write`] can implement this marker trait for their type. Doing so
+    /// will automatically provide the default implementation of [`blocking::i2s::Write`] for the
+    /// type.
+    ///
+    /// [`i2s::Write`]: ../../../i2s/trait.Write.html
+    /// [`blocking::i2s::Write`]: ../trait.Write.html
+    pub trait Default<Word>: ::i2s::Write<Word> {}
+
+    impl<I, Word> ::blocking::i2s::Write<Word> for I
+    where
+        I: Default<Word>,
+        Word: Copy,
+    {
+        fn write(&mut self, frames: &[[Word; 2]]) -> Result<(), Self::Error> {
+            for frame in frames {
+                block!(::i2s::Write::write(self, *frame))?;
+            }
+
+            Ok(())
+        }
+    }
+}
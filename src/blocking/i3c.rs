@@ -0,0 +1,236 @@
+//! Blocking I3C API
+//!
+//! *This module is available if embedded-hal is built with the `"i3c"` feature.*
+//!
+//! All the `address`es in this module are *dynamic* addresses.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use i3c::{Ccc, Target};
+
+/// Dynamic Address Assignment
+pub trait AssignDynamicAddresses: ::i3c::ErrorType {
+    /// Runs the `ENTDAA` procedure, assigning a dynamic address to every target that takes part
+    /// in it
+    ///
+    /// The implementation chooses free addresses and stores a description of each target that
+    /// was assigned one in `targets`. Returns the number of targets stored. Assignment stops once
+    /// `targets` is full.
+    fn assign_dynamic_addresses(&mut self, targets: &mut [Target]) -> Result<usize, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AssignDynamicAddresses for Box<T>
+where
+    T: AssignDynamicAddresses + ?Sized,
+{
+    fn assign_dynamic_addresses(&mut self, targets: &mut [Target]) -> Result<usize, Self::Error> {
+        (**self).assign_dynamic_addresses(targets)
+    }
+}
+
+impl<T> AssignDynamicAddresses for &mut T
+where
+    T: AssignDynamicAddresses + ?Sized,
+{
+    fn assign_dynamic_addresses(&mut self, targets: &mut [Target]) -> Result<usize, Self::Error> {
+        (**self).assign_dynamic_addresses(targets)
+    }
+}
+
+/// Blocking private read
+pub trait Read: ::i3c::ErrorType {
+    /// Reads enough bytes from target with `address` to fill `buffer`
+    ///
+    /// # I3C Events (contract)
+    ///
+    /// ``` text
+    /// Controller: S 7E+W     Sr DA+R            T ... P
+    /// Target:            ACK         ACK D0 T D1 ... DN
+    /// ```
+    ///
+    /// Where
+    ///
+    /// - `S` = start condition
+    /// - `7E+W` = broadcast address with the write bit
+    /// - `Sr` = repeated start condition
+    /// - `DA+R` = dynamic address with the read bit
+    /// - `ACK` = acknowledge
+    /// - `Di` = ith byte of data
+    /// - `T` = transition bit
+    /// - `P` = stop condition
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Read for Box<T>
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(address, buffer)
+    }
+}
+
+impl<T> Read for &mut T
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(address, buffer)
+    }
+}
+
+/// Blocking private write
+pub trait Write: ::i3c::ErrorType {
+    /// Sends bytes to target with address `address`
+    ///
+    /// # I3C Events (contract)
+    ///
+    /// ``` text
+    /// Controller: S 7E+W     Sr DA+W     D0 T D1 T ... DN T P
+    /// Target:            ACK         ACK
+    /// ```
+    ///
+    /// Where
+    ///
+    /// - `S` = start condition
+    /// - `7E+W` = broadcast address with the write bit
+    /// - `Sr` = repeated start condition
+    /// - `DA+W` = dynamic address with the write bit
+    /// - `ACK` = acknowledge
+    /// - `Di` = ith byte of data
+    /// - `T` = parity bit
+    /// - `P` = stop condition
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Write for Box<T>
+where
+    T: Write + ?Sized,
+{
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(address, bytes)
+    }
+}
+
+impl<T> Write for &mut T
+where
+    T: Write + ?Sized,
+{
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(address, bytes)
+    }
+}
+
+/// Blocking private write + read
+pub trait WriteRead: ::i3c::ErrorType {
+    /// Sends bytes to target with address `address` and then reads enough bytes to fill `buffer`
+    /// *in a single transaction*, i.e. with a repeated start in between and no stop condition
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WriteRead for Box<T>
+where
+    T: WriteRead + ?Sized,
+{
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
+
+impl<T> WriteRead for &mut T
+where
+    T: WriteRead + ?Sized,
+{
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
+
+/// Broadcast Common Command Codes
+pub trait Broadcast: ::i3c::ErrorType {
+    /// Sends the broadcast command `ccc`, followed by `data`, to all targets
+    ///
+    /// Implementations must return an error if `ccc` is not a broadcast command
+    fn broadcast(&mut self, ccc: Ccc, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Broadcast for Box<T>
+where
+    T: Broadcast + ?Sized,
+{
+    fn broadcast(&mut self, ccc: Ccc, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).broadcast(ccc, data)
+    }
+}
+
+impl<T> Broadcast for &mut T
+where
+    T: Broadcast + ?Sized,
+{
+    fn broadcast(&mut self, ccc: Ccc, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).broadcast(ccc, data)
+    }
+}
+
+/// Direct Common Command Codes
+pub trait Direct: ::i3c::ErrorType {
+    /// Sends the direct command `ccc`, followed by `data`, to the target with address `address`
+    ///
+    /// Implementations must return an error if `ccc` is not a direct command
+    fn direct_write(&mut self, ccc: Ccc, address: u8, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends the direct command `ccc` to the target with address `address` and reads its response
+    /// into `buffer`
+    ///
+    /// Implementations must return an error if `ccc` is not a direct command
+    fn direct_read(&mut self, ccc: Ccc, address: u8, buffer: &mut [u8])
+        -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Direct for Box<T>
+where
+    T: Direct + ?Sized,
+{
+    fn direct_write(&mut self, ccc: Ccc, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).direct_write(ccc, address, data)
+    }
+
+    fn direct_read(&mut self, ccc: Ccc, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).direct_read(ccc, address, buffer)
+    }
+}
+
+impl<T> Direct for &mut T
+where
+    T: Direct + ?Sized,
+{
+    fn direct_write(&mut self, ccc: Ccc, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).direct_write(ccc, address, data)
+    }
+
+    fn direct_read(&mut self, ccc: Ccc, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).direct_read(ccc, address, buffer)
+    }
+}
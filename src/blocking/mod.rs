@@ -4,8 +4,20 @@
 //! traits. To save boilerplate when that's the case a `Default` marker trait may be provided.
 //! Implementing that marker trait will opt in your type into a blanket implementation.
 
+#[cfg(feature = "can")]
+pub mod can;
 pub mod delay;
 pub mod i2c;
+#[cfg(feature = "i2s")]
+pub mod i2s;
+#[cfg(feature = "i3c")]
+pub mod i3c;
+#[cfg(feature = "pdm")]
+pub mod pdm;
+#[cfg(feature = "qspi")]
+pub mod qspi;
+#[cfg(feature = "retry")]
+pub mod retry;
 pub mod serial;
 pub mod spi;
 pub mod rng;
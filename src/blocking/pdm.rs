@@ -0,0 +1,34 @@
+//! Blocking PDM API
+//!
+//! *This module is available if embedded-hal is built with the `"pdm"` feature.*
+
+/// Read a block of PCM samples (blocking variant)
+pub trait Read<Word>: ::pdm::ErrorType {
+    /// Reads enough samples to fill `buffer`, blocking until `buffer` is full
+    fn read(&mut self, buffer: &mut [Word]) -> Result<(), Self::Error>;
+}
+
+/// Blocking PDM read
+pub mod read {
+    /// Marker trait to opt into default blocking read implementation
+    ///
+    /// Implementers of [`pdm::Read`] can implement this marker trait for their type. Doing so will
+    /// automatically provide the default implementation of [`blocking::pdm::Read`] for the type.
+    ///
+    /// [`pdm::Read`]: ../../../pdm/trait.Read.html
+    /// [`blocking::pdm::Read`]: ../trait.Read.html
+    pub trait Default<Word>: ::pdm::Read<Word> {}
+
+    impl<P, Word> ::blocking::pdm::Read<Word> for P
+    where
+        P: Default<Word>,
+    {
+        fn read(&mut self, buffer: &mut [Word]) -> Result<(), Self::Error> {
+            for sample in buffer.iter_mut() {
+                *sample = block!(::pdm::Read::read(self))?;
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Blocking QSPI API (indirect mode)
+//!
+//! *This module is available if embedded-hal is built with the `"qspi"` feature.*
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use qspi::Command;
+
+/// Blocking indirect read
+pub trait Read: ::qspi::ErrorType {
+    /// Sends `command` and fills `buffer` with the bytes received during the data phase
+    ///
+    /// Implementations must return an error if they don't support the widths or data rates
+    /// requested by `command`
+    fn read(&mut self, command: Command, buffer: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Read for Box<T>
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, command: Command, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(command, buffer)
+    }
+}
+
+impl<T> Read for &mut T
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, command: Command, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(command, buffer)
+    }
+}
+
+/// Blocking indirect write
+pub trait Write: ::qspi::ErrorType {
+    /// Sends `command`, then sends `data` during the data phase
+    ///
+    /// If `data` is empty the data phase is skipped, e.g. for a "write enable" command.
+    ///
+    /// Implementations must return an error if they don't support the widths or data rates
+    /// requested by `command`
+    fn write(&mut self, command: Command, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Write for Box<T>
+where
+    T: Write + ?Sized,
+{
+    fn write(&mut self, command: Command, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(command, data)
+    }
+}
+
+impl<T> Write for &mut T
+where
+    T: Write + ?Sized,
+{
+    fn write(&mut self, command: Command, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(command, data)
+    }
+}
@@ -0,0 +1,109 @@
+//! Retrying operations that fail transiently
+//!
+//! *This module is available if embedded-hal is built with the `"retry"` feature.*
+//!
+//! Some errors, like a slave that doesn't acknowledge its address because it's busy or a receive
+//! buffer overrun, usually go away if the operation is tried again. These functions block on an
+//! `nb` operation, like the [`block!`] macro, but also retry it when it fails.
+//!
+//! Only the errors for which `retry_if` returns `true` are retried; the others are returned right
+//! away. `WouldBlock` is never a failure: the operation is simply polled again.
+//!
+//! Blocking operations can be retried by wrapping their errors in `nb::Error::Other`.
+//!
+//! [`block!`]: https://docs.rs/nb/0.1.1/nb/macro.block.html
+//!
+//! # Examples
+//!
+//! Reading a byte from a noisy serial line, backing off before each retry
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::retry;
+//! use hal::mock::delay::Mock as Delay;
+//! use hal::mock::serial::{Mock as Serial, Transaction};
+//! use hal::serial::{Error, ErrorKind, Read};
+//!
+//! fn main() {
+//!     let mut serial = Serial::new(&[
+//!         Transaction::read(0).with_error(ErrorKind::Noise),
+//!         Transaction::read(0).with_error(ErrorKind::Noise),
+//!         Transaction::read(b'x'),
+//!     ]);
+//!     let mut delay = Delay::new();
+//!
+//!     let byte = retry::retry_with_backoff(
+//!         3,
+//!         &mut delay,
+//!         100,
+//!         |e: &ErrorKind| e.kind() == ErrorKind::Noise,
+//!         || serial.read(),
+//!     );
+//!
+//!     assert_eq!(byte, Ok(b'x'));
+//!     // waited 100 us before the first retry and 200 us before the second one
+//!     assert_eq!(delay.elapsed_us(), 300);
+//!     serial.done();
+//! }
+//! ```
+
+use nb;
+
+use blocking::delay::DelayUs;
+
+/// Blocks on `operation`, retrying it up to `retries` times when it fails with an error for which
+/// `retry_if` returns `true`
+///
+/// Returns the result of the last attempt.
+pub fn retry_n<T, E, P, F>(retries: u32, retry_if: P, operation: F) -> Result<T, E>
+where
+    P: FnMut(&E) -> bool,
+    F: FnMut() -> nb::Result<T, E>,
+{
+    retry(retries, retry_if, operation, |_| {})
+}
+
+/// Like [`retry_n`], but waits `initial_us` microseconds before the first retry, and twice as
+/// long as the previous time before each of the next ones
+///
+/// [`retry_n`]: fn.retry_n.html
+pub fn retry_with_backoff<T, E, D, P, F>(
+    retries: u32,
+    delay: &mut D,
+    initial_us: u32,
+    retry_if: P,
+    operation: F,
+) -> Result<T, E>
+where
+    D: DelayUs<u32>,
+    P: FnMut(&E) -> bool,
+    F: FnMut() -> nb::Result<T, E>,
+{
+    retry(retries, retry_if, operation, |retry| {
+        delay.delay_us(initial_us.saturating_mul(1 << retry.min(31)))
+    })
+}
+
+fn retry<T, E, P, F, B>(
+    retries: u32,
+    mut retry_if: P,
+    mut operation: F,
+    mut backoff: B,
+) -> Result<T, E>
+where
+    P: FnMut(&E) -> bool,
+    F: FnMut() -> nb::Result<T, E>,
+    B: FnMut(u32),
+{
+    let mut retry = 0;
+    loop {
+        match block!(operation()) {
+            Err(ref e) if retry < retries && retry_if(e) => {
+                backoff(retry);
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -1,13 +1,60 @@
 //! Blocking hardware random number generator
 
+#[cfg(all(feature = "alloc", feature = "rng"))]
+use alloc::boxed::Box;
+
 /// Blocking read
 ///
-/// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
-#[cfg(feature = "unproven")]
-pub trait Read {
-    /// Error type
-    type Error;
-
+/// *This trait is available if embedded-hal is built with the `"rng"` feature.*
+///
+/// # Examples
+///
+/// An adapter that provides the methods of `rand_core::RngCore` on top of a hardware RNG, so that
+/// crypto and networking stacks can use it
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::rng::Read;
+/// use hal::rng::{Error, ErrorKind};
+///
+/// struct HwRng<R>(R);
+///
+/// impl<R: Read> HwRng<R> {
+///     // `rand_core::RngCore::try_fill_bytes`
+///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ErrorKind> {
+///         self.0.read(dest).map_err(|e| e.kind())
+///     }
+///
+///     // `rand_core::RngCore::next_u32`
+///     fn next_u32(&mut self) -> u32 {
+///         let mut bytes = [0; 4];
+///         self.try_fill_bytes(&mut bytes).expect("hardware RNG failure");
+///         u32::from_le_bytes(bytes)
+///     }
+/// }
+///
+/// fn main() {
+///     let rng: Rng = {
+///         // ..
+/// #       Rng
+///     };
+///
+///     let mut rng = HwRng(rng);
+///     assert_eq!(rng.next_u32(), 0x2a2a_2a2a);
+/// }
+///
+/// # struct Rng;
+/// # impl hal::rng::ErrorType for Rng { type Error = ErrorKind; }
+/// # impl Read for Rng {
+/// #     fn read(&mut self, buffer: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         for byte in buffer { *byte = 42 }
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+#[cfg(feature = "rng")]
+pub trait Read: ::rng::ErrorType {
     /// Reads enough bytes from hardware random number generator to fill `buffer`
     ///
     /// If any error is encountered then this function immediately returns. The contents of buf are
@@ -17,3 +64,23 @@ pub trait Read {
     /// will never read more than would be necessary to completely fill the buffer.
     fn read(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error>;
 }
+
+#[cfg(all(feature = "alloc", feature = "rng"))]
+impl<T> Read for Box<T>
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(buffer)
+    }
+}
+
+#[cfg(feature = "rng")]
+impl<T> Read for &mut T
+where
+    T: Read + ?Sized,
+{
+    fn read(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(buffer)
+    }
+}
@@ -2,10 +2,7 @@
 
 
 /// Write half of a serial interface (blocking variant)
-pub trait Write<Word> {
-    /// The type of error that can occur when writing
-    type Error;
-
+pub trait Write<Word>: ::serial::ErrorType {
     /// Writes a slice, blocking until everything has been written
     ///
     /// An implementation can choose to buffer the write, returning `Ok(())`
@@ -21,7 +18,69 @@ pub trait Write<Word> {
 }
 
 
+/// Fixed-size blocking reads, for implementers of [`serial::Read`]
+///
+/// [`serial::Read`]: ../../serial/trait.Read.html
+pub trait ReadExt<Word>: ::serial::Read<Word> {
+    /// Reads `N` words, blocking until all of them have been received
+    fn read_exact<const N: usize>(&mut self) -> Result<[Word; N], Self::Error>
+    where
+        Word: Copy + Default,
+    {
+        let mut words = [Word::default(); N];
+        for word in words.iter_mut() {
+            *word = block!(self.read())?;
+        }
+
+        Ok(words)
+    }
+}
+
+impl<S, Word> ReadExt<Word> for S where S: ::serial::Read<Word> + ?Sized {}
+
 /// Blocking serial write
+///
+/// # Examples
+///
+/// A UART that only implements the non-blocking `serial::Write` gets the blocking one by opting
+/// into the default implementation
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// extern crate nb;
+///
+/// use hal::blocking::serial::{write, Write};
+/// use hal::serial::{self, ErrorKind, ErrorType};
+///
+/// struct Uart1 {
+///     sent: Vec<u8>,
+/// }
+///
+/// impl ErrorType for Uart1 {
+///     type Error = ErrorKind;
+/// }
+///
+/// impl serial::Write<u8> for Uart1 {
+///     fn write(&mut self, word: u8) -> nb::Result<(), ErrorKind> {
+///         self.sent.push(word);
+///         Ok(())
+///     }
+///
+///     fn flush(&mut self) -> nb::Result<(), ErrorKind> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl write::Default<u8> for Uart1 {}
+///
+/// fn main() {
+///     let mut uart = Uart1 { sent: vec![] };
+///
+///     uart.bwrite_all(b"AT\r\n").unwrap();
+///     uart.bflush().unwrap();
+///     assert_eq!(uart.sent, b"AT\r\n");
+/// }
+/// ```
 pub mod write {
     /// Marker trait to opt into default blocking write implementation
     ///
@@ -33,14 +92,13 @@ pub mod write {
     /// [`blocking::serial::Write`]: ../trait.Write.html
     pub trait Default<Word>: ::serial::Write<Word> {}
 
+    impl<S, Word> Default<Word> for &mut S where S: Default<Word> + ?Sized {}
+
     impl<S, Word> ::blocking::serial::Write<Word> for S
         where
             S   : Default<Word>,
             Word: Clone,
     {
-        type Error = S::Error;
-
-
         fn bwrite_all(&mut self, buffer: &[Word]) -> Result<(), Self::Error> {
             for word in buffer {
                 block!(self.write(word.clone()))?;
@@ -1,36 +1,371 @@
 //! Blocking SPI API
+//!
+//! The [`Transfer`], [`Write`], [`WriteIter`] and [`Transactional`] traits are implemented by the
+//! SPI peripherals, i.e. by the bus, and leave the chip select of the device to their user.
+//! [`SpiDevice`] is implemented by a device on a bus instead, which asserts its chip select around
+//! each transaction and, if the bus is shared, keeps the other devices off the bus in the
+//! meantime: [`ExclusiveDevice`] for a device that has the bus for itself, and
+//! [`bus::SpiDeviceProxy`] for devices that share one.
+//!
+//! [`Transfer`]: trait.Transfer.html
+//! [`Write`]: trait.Write.html
+//! [`WriteIter`]: trait.WriteIter.html
+//! [`Transactional`]: trait.Transactional.html
+//! [`SpiDevice`]: trait.SpiDevice.html
+//! [`ExclusiveDevice`]: struct.ExclusiveDevice.html
+//! [`bus::SpiDeviceProxy`]: ../../bus/struct.SpiDeviceProxy.html
+//!
+//! All these traits are implemented for mutable references to their implementers, so that a driver
+//! can be given a borrow of the bus. The default implementations, e.g. [`transfer::Default`], are
+//! opted into for references too; peripherals that implement the bus traits themselves also
+//! implement them for `&mut` references to themselves, as `mock::spi::Mock` does, since the
+//! blanket implementations of the `Default` markers rule out a generic one.
+//!
+//! [`transfer::Default`]: transfer/trait.Default.html
 
-/// Blocking transfer
-pub trait Transfer<W> {
-    /// Error type
-    type Error;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use digital::OutputPin;
 
+/// Blocking transfer
+pub trait Transfer<W>: ::spi::ErrorType {
     /// Sends `words` to the slave. Returns the `words` received from the slave
     fn transfer<'w>(&mut self, words: &'w mut [W]) -> Result<&'w [W], Self::Error>;
 }
 
 /// Blocking write
-pub trait Write<W> {
-    /// Error type
-    type Error;
-
+pub trait Write<W>: ::spi::ErrorType {
     /// Sends `words` to the slave, ignoring all the incoming words
     fn write(&mut self, words: &[W]) -> Result<(), Self::Error>;
 }
 
+/// Blocking write (iterator version)
+///
+/// # Examples
+///
+/// Filling a display with a gradient generated on the fly, without a frame buffer
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::spi::WriteIter;
+///
+/// fn gradient<S>(spi: &mut S, width: u16, height: u16) -> Result<(), S::Error>
+/// where
+///     S: WriteIter<u16>,
+/// {
+///     // RGB565 pixels, from black on the left to blue on the right
+///     spi.write_iter((0..height).flat_map(|_| (0..width).map(move |x| x * 32 / width)))
+/// }
+///
+/// fn main() {
+///     let mut spi: Spi1 = {
+///         // ..
+/// #       Spi1 { sent: vec![] }
+///     };
+///
+///     gradient(&mut spi, 4, 2).unwrap();
+///     assert_eq!(spi.sent, [0, 8, 16, 24, 0, 8, 16, 24]);
+/// }
+///
+/// # use hal::spi::{ErrorKind, ErrorType};
+/// # struct Spi1 { sent: Vec<u16> }
+/// # impl ErrorType for Spi1 { type Error = ErrorKind; }
+/// # impl WriteIter<u16> for Spi1 {
+/// #     fn write_iter<WI>(&mut self, words: WI) -> Result<(), ErrorKind>
+/// #     where
+/// #         WI: IntoIterator<Item = u16>,
+/// #     {
+/// #         self.sent.extend(words);
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait WriteIter<W>: ::spi::ErrorType {
+    /// Sends `words` to the slave, ignoring all the incoming words
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), Self::Error>
+    where
+        WI: IntoIterator<Item = W>;
+}
+
+/// Operation of an SPI transaction, see [`Transactional`](trait.Transactional.html)
+#[derive(Debug, Eq, PartialEq)]
+pub enum Operation<'a, W: 'a> {
+    /// Sends the words, ignoring all the incoming words, as `Write::write` does
+    Write(&'a [W]),
+    /// Sends the words and replaces them with the words received, as `Transfer::transfer` does
+    Transfer(&'a mut [W]),
+}
+
+/// Blocking transactions, several operations executed one after the other
+///
+/// # Contract
+///
+/// The operations are executed in order. Implementations that manage the chip select of the
+/// device, e.g. `linux::spi::Spidev`, MUST keep it asserted from the first operation to the last
+/// one; otherwise, as with the other SPI traits, the caller asserts it around the call.
+///
+/// # Examples
+///
+/// Reading a NOR flash, whose read command must be followed by the data in the same chip select
+/// assertion
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::spi::{Operation, Transactional};
+///
+/// fn read<S>(spi: &mut S, address: u32, buffer: &mut [u8]) -> Result<(), S::Error>
+/// where
+///     S: Transactional<u8>,
+/// {
+///     let [_, a2, a1, a0] = address.to_be_bytes();
+///     spi.exec(&mut [Operation::Write(&[0x03, a2, a1, a0]), Operation::Transfer(buffer)])
+/// }
+///
+/// fn main() {
+///     let mut spi: Spidev = {
+///         // ..
+/// #       Spidev
+///     };
+///
+///     let mut buffer = [0; 4];
+///     read(&mut spi, 0x1000, &mut buffer).unwrap();
+/// #   assert_eq!(buffer, [0xff; 4]);
+/// }
+///
+/// # use hal::spi::{ErrorKind, ErrorType};
+/// # struct Spidev;
+/// # impl ErrorType for Spidev { type Error = ErrorKind; }
+/// # impl Transactional<u8> for Spidev {
+/// #     fn exec(&mut self, operations: &mut [Operation<u8>]) -> Result<(), ErrorKind> {
+/// #         for operation in operations {
+/// #             if let Operation::Transfer(ref mut words) = *operation {
+/// #                 for word in words.iter_mut() { *word = 0xff }
+/// #             }
+/// #         }
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait Transactional<W>: ::spi::ErrorType {
+    /// Executes `operations` in a single transaction
+    fn exec(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error>;
+}
+
+/// SPI bus, on which devices are selected by their user
+///
+/// This trait is implemented for every implementer of the blocking [`Transfer`] and [`Write`]
+/// traits; it's a shorthand for their bounds.
+///
+/// [`Transfer`]: trait.Transfer.html
+/// [`Write`]: trait.Write.html
+pub trait SpiBus<W>: Transfer<W> + Write<W> {}
+
+impl<T, W> SpiBus<W> for T where T: Transfer<W> + Write<W> + ?Sized {}
+
+/// SPI device, which manages its chip select
+///
+/// # Contract
+///
+/// - `transaction` MUST assert the chip select of the device before the first operation, and
+///   deassert it after the last one, even if an operation fails.
+/// - The operations of other devices on the same bus MUST NOT be interleaved with those of a
+///   transaction.
+///
+/// # Examples
+///
+/// A driver written against `SpiDevice`, used with a device that has the bus for itself
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::spi::{ExclusiveDevice, Operation, SpiDevice};
+///
+/// struct Bme280<SPI> {
+///     spi: SPI,
+/// }
+///
+/// impl<SPI> Bme280<SPI>
+/// where
+///     SPI: SpiDevice<u8>,
+/// {
+///     fn id(&mut self) -> Result<u8, SPI::Error> {
+///         let mut id = [0];
+///         self.spi.transaction(&mut [Operation::Write(&[0xd0]), Operation::Transfer(&mut id)])?;
+///         Ok(id[0])
+///     }
+/// }
+///
+/// fn main() {
+///     let (spi, cs): (Spi1, Pa4) = {
+///         // ..
+/// #       (Spi1, Pa4)
+///     };
+///
+///     let mut bme280 = Bme280 {
+///         spi: ExclusiveDevice::new(spi, cs),
+///     };
+///     assert_eq!(bme280.id(), Ok(0x60));
+/// }
+///
+/// # use hal::blocking::spi::{Transfer, Write};
+/// # use hal::digital::OutputPin;
+/// # use hal::spi::{ErrorKind, ErrorType};
+/// # struct Spi1;
+/// # impl ErrorType for Spi1 { type Error = ErrorKind; }
+/// # impl Transfer<u8> for Spi1 {
+/// #     fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ErrorKind> {
+/// #         words[0] = 0x60;
+/// #         Ok(words)
+/// #     }
+/// # }
+/// # impl Write<u8> for Spi1 {
+/// #     fn write(&mut self, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// # struct Pa4;
+/// # impl OutputPin for Pa4 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+/// ```
+pub trait SpiDevice<W>: ::spi::ErrorType {
+    /// Executes `operations` in a single transaction, with the device selected
+    fn transaction(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, W> SpiDevice<W> for Box<T>
+where
+    T: SpiDevice<W> + ?Sized,
+{
+    fn transaction(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error> {
+        (**self).transaction(operations)
+    }
+}
+
+impl<T, W> SpiDevice<W> for &mut T
+where
+    T: SpiDevice<W> + ?Sized,
+{
+    fn transaction(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error> {
+        (**self).transaction(operations)
+    }
+}
+
+/// Device that has the SPI bus for itself
+///
+/// It implements [`SpiDevice`](trait.SpiDevice.html) by driving its chip select pin low around
+/// each transaction, with the bus' error type. With the `"async"` feature, it also implements
+/// [`futures::spi::SpiDevice`](../../futures/spi/trait.SpiDevice.html) for the buses that
+/// implement `spi::FullDuplex` and `futures::RegisterWaker`.
+#[derive(Debug)]
+pub struct ExclusiveDevice<BUS, CS> {
+    bus: BUS,
+    cs: CS,
+}
+
+impl<BUS, CS> ExclusiveDevice<BUS, CS>
+where
+    CS: OutputPin,
+{
+    /// Creates a device on `bus`, selected by driving `cs` low
+    ///
+    /// `cs` is driven high, so that the device isn't selected until the first transaction.
+    pub fn new(bus: BUS, mut cs: CS) -> Self {
+        cs.set_high();
+        ExclusiveDevice { bus, cs }
+    }
+
+    /// Returns a mutable reference to the bus
+    pub fn bus_mut(&mut self) -> &mut BUS {
+        &mut self.bus
+    }
+
+    /// Returns the bus and the chip select pin
+    pub fn into_inner(self) -> (BUS, CS) {
+        (self.bus, self.cs)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn parts_mut(&mut self) -> (&mut BUS, &mut CS) {
+        (&mut self.bus, &mut self.cs)
+    }
+}
+
+impl<BUS, CS> ::spi::ErrorType for ExclusiveDevice<BUS, CS>
+where
+    BUS: ::spi::ErrorType,
+{
+    type Error = BUS::Error;
+}
+
+impl<BUS, CS, W> SpiDevice<W> for ExclusiveDevice<BUS, CS>
+where
+    BUS: SpiBus<W>,
+    CS: OutputPin,
+{
+    fn transaction(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error> {
+        self.cs.set_low();
+        let result = exec(&mut self.bus, operations);
+        self.cs.set_high();
+        result
+    }
+}
+
+/// Executes `operations` one after the other on `bus`
+pub(crate) fn exec<B, W>(bus: &mut B, operations: &mut [Operation<W>]) -> Result<(), B::Error>
+where
+    B: SpiBus<W> + ?Sized,
+{
+    for operation in operations {
+        match *operation {
+            Operation::Write(words) => bus.write(words)?,
+            Operation::Transfer(ref mut words) => {
+                bus.transfer(words)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fixed-size transfers, for implementers of [`Transfer`]
+///
+/// [`Transfer`]: trait.Transfer.html
+pub trait TransferExt<W>: Transfer<W> {
+    /// Sends `words` to the slave. Returns the words received from the slave
+    fn transfer_array<const N: usize>(&mut self, mut words: [W; N]) -> Result<[W; N], Self::Error>
+    where
+        W: Copy,
+    {
+        self.transfer(&mut words)?;
+        Ok(words)
+    }
+
+    /// Sends `N` default words, e.g. zeros, to the slave. Returns the words received from the
+    /// slave
+    fn read_exact<const N: usize>(&mut self) -> Result<[W; N], Self::Error>
+    where
+        W: Copy + Default,
+    {
+        self.transfer_array([W::default(); N])
+    }
+}
+
+impl<T, W> TransferExt<W> for T where T: Transfer<W> + ?Sized {}
+
 /// Blocking transfer
 pub mod transfer {
     /// Default implementation of `blocking::spi::Transfer<W>` for implementers of
     /// `spi::FullDuplex<W>`
     pub trait Default<W>: ::spi::FullDuplex<W> {}
 
+    impl<W, S> Default<W> for &mut S where S: Default<W> + ?Sized {}
+
     impl<W, S> ::blocking::spi::Transfer<W> for S
     where
         S: Default<W>,
         W: Clone,
     {
-        type Error = S::Error;
-
         fn transfer<'w>(&mut self, words: &'w mut [W]) -> Result<&'w [W], S::Error> {
             for word in words.iter_mut() {
                 block!(self.send(word.clone()))?;
@@ -47,13 +382,13 @@ pub mod write {
     /// Default implementation of `blocking::spi::Write<W>` for implementers of `spi::FullDuplex<W>`
     pub trait Default<W>: ::spi::FullDuplex<W> {}
 
+    impl<W, S> Default<W> for &mut S where S: Default<W> + ?Sized {}
+
     impl<W, S> ::blocking::spi::Write<W> for S
     where
         S: Default<W>,
         W: Clone,
     {
-        type Error = S::Error;
-
         fn write(&mut self, words: &[W]) -> Result<(), S::Error> {
             for word in words {
                 block!(self.send(word.clone()))?;
@@ -64,3 +399,59 @@ pub mod write {
         }
     }
 }
+
+/// Blocking write (iterator version)
+pub mod write_iter {
+    /// Default implementation of `blocking::spi::WriteIter<W>` for implementers of
+    /// `spi::FullDuplex<W>`
+    pub trait Default<W>: ::spi::FullDuplex<W> {}
+
+    impl<W, S> Default<W> for &mut S where S: Default<W> + ?Sized {}
+
+    impl<W, S> ::blocking::spi::WriteIter<W> for S
+    where
+        S: Default<W>,
+        W: Clone,
+    {
+        fn write_iter<WI>(&mut self, words: WI) -> Result<(), S::Error>
+        where
+            WI: IntoIterator<Item = W>,
+        {
+            for word in words.into_iter() {
+                // `send` takes the word by value and `block!` calls it again after `WouldBlock`
+                block!(self.send(word.clone()))?;
+                block!(self.read())?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Blocking transactions
+pub mod transactional {
+    use blocking::spi::{exec, Operation, Transfer, Write};
+
+    /// Default implementation of `blocking::spi::Transactional<W>` for implementers of
+    /// `blocking::spi::Write<W>` and `blocking::spi::Transfer<W>`
+    ///
+    /// It executes the operations one after the other, so the chip select must be asserted by the
+    /// caller around the transaction.
+    pub trait Default<W>: Write<W> + Transfer<W> {}
+
+    impl<'a, W, S> Default<W> for &'a mut S
+    where
+        S: Default<W> + ?Sized,
+        &'a mut S: Write<W> + Transfer<W>,
+    {
+    }
+
+    impl<W, S> ::blocking::spi::Transactional<W> for S
+    where
+        S: Default<W>,
+    {
+        fn exec(&mut self, operations: &mut [Operation<W>]) -> Result<(), S::Error> {
+            exec(self, operations)
+        }
+    }
+}
@@ -0,0 +1,614 @@
+//! Sharing a bus between several drivers
+//!
+//! *This module is available if embedded-hal is built with the `"bus"` feature.*
+//!
+//! Drivers take ownership of, or a mutable reference to, the bus their device is on, so two
+//! drivers can't normally use the same I2C or SPI bus. A [`BusManager`] owns the bus instead, in a
+//! [`BusMutex`], and hands out proxies that implement the blocking I2C or SPI traits by locking
+//! the mutex for the duration of each operation.
+//!
+//! The mutex decides where the proxies can be used:
+//!
+//! - `RefCell` has no overhead, but its proxies can only be used from the thread, or the
+//!   execution context, that owns the manager. Operations that would nest, e.g. from an interrupt
+//!   handler that preempts one, panic.
+//! - `critical_section::Mutex<RefCell<_>>` (with the `"critical-section"` feature) locks the bus
+//!   in a critical section, so its proxies can be used from interrupt handlers as well.
+//! - `std::sync::Mutex` (with the `"std"` feature) lets the proxies be used from several threads.
+//!
+//! RTOS users can plug in the mutex of their RTOS by implementing `BusMutex` for it.
+//!
+//! Drivers that need exclusive access to a peripheral for longer than one operation, e.g. for a
+//! sequence of transfers that must not be interleaved with those of other drivers, can instead
+//! [`Claim`] it from an arbiter, and [`Release`] it once they are done. The claim doesn't block:
+//! it returns `WouldBlock` while another driver holds the peripheral, so that executors and RTOSes
+//! can schedule other tasks in the meantime. `Claim` is implemented for `RefCell` and, with the
+//! `"std"` feature, for `std::sync::Mutex`; RTOS arbiters, e.g. semaphores, can implement it as
+//! well.
+//!
+//! Only the blocking traits are forwarded: each of their operations is a complete transaction on
+//! the bus. Chip select lines are still up to the drivers that use an [`SpiProxy`], which must not
+//! interleave their transfers with those of the other drivers while they assert them; an
+//! [`SpiDeviceProxy`] instead implements [`SpiDevice`], asserting the chip select of its device
+//! and keeping the bus locked for the whole transaction.
+//!
+//! [`BusManager`]: struct.BusManager.html
+//! [`BusMutex`]: trait.BusMutex.html
+//! [`Claim`]: trait.Claim.html
+//! [`Release`]: trait.Release.html
+//! [`SpiProxy`]: struct.SpiProxy.html
+//! [`SpiDeviceProxy`]: struct.SpiDeviceProxy.html
+//! [`SpiDevice`]: ../blocking/spi/trait.SpiDevice.html
+//!
+//! # Examples
+//!
+//! Two drivers on the same I2C bus
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use std::cell::RefCell;
+//!
+//! use hal::blocking::i2c::{Write, WriteRead};
+//! use hal::bus::BusManager;
+//!
+//! struct Thermometer<I2C> {
+//!     i2c: I2C,
+//! }
+//!
+//! struct Display<I2C> {
+//!     i2c: I2C,
+//! }
+//!
+//! fn main() {
+//!     let i2c: I2c1 = {
+//!         // ..
+//! #       I2c1
+//!     };
+//!
+//!     let manager = BusManager::<RefCell<_>>::new(i2c);
+//!     let mut thermometer = Thermometer { i2c: manager.acquire_i2c() };
+//!     let mut display = Display { i2c: manager.acquire_i2c() };
+//!
+//!     let mut temperature = [0; 2];
+//!     thermometer.i2c.write_read(0x48, &[0x00], &mut temperature).unwrap();
+//!     display.i2c.write(0x3c, &temperature).unwrap();
+//! }
+//!
+//! # use hal::i2c::{ErrorKind, ErrorType};
+//! # struct I2c1;
+//! # impl ErrorType for I2c1 { type Error = ErrorKind; }
+//! # impl Write for I2c1 {
+//! #     fn write(&mut self, _: u8, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//! # impl WriteRead for I2c1 {
+//! #     fn write_read(&mut self, _: u8, _: &[u8], _: &mut [u8]) -> Result<(), ErrorKind> {
+//! #         Ok(())
+//! #     }
+//! # }
+//! ```
+
+use core::cell::{RefCell, RefMut};
+use core::ops::DerefMut;
+
+use nb;
+use void::Void;
+
+use blocking;
+use blocking::spi::{Operation, SpiBus, SpiDevice};
+use digital::OutputPin;
+use i2c;
+use spi;
+
+/// Mutex that protects a shared bus
+pub trait BusMutex {
+    /// The bus
+    type Bus;
+
+    /// Creates a mutex that protects `bus`
+    fn create(bus: Self::Bus) -> Self;
+
+    /// Runs `f` with exclusive access to the bus, and returns its result
+    fn lock<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Self::Bus) -> R;
+}
+
+/// Single context sharing
+///
+/// # Panics
+///
+/// `lock` panics if the bus is already locked, i.e. if it's called from within `f`.
+impl<T> BusMutex for RefCell<T> {
+    type Bus = T;
+
+    fn create(bus: T) -> Self {
+        RefCell::new(bus)
+    }
+
+    fn lock<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.borrow_mut())
+    }
+}
+
+/// Sharing with interrupt handlers
+///
+/// *This implementation is available if embedded-hal is built with the `"critical-section"`
+/// feature.*
+///
+/// # Panics
+///
+/// `lock` panics if the bus is already locked, i.e. if it's called from within `f`.
+#[cfg(feature = "critical-section")]
+impl<T> BusMutex for ::critical_section::Mutex<RefCell<T>> {
+    type Bus = T;
+
+    fn create(bus: T) -> Self {
+        ::critical_section::Mutex::new(RefCell::new(bus))
+    }
+
+    fn lock<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        ::critical_section::with(|cs| f(&mut self.borrow_ref_mut(cs)))
+    }
+}
+
+/// Sharing between threads
+///
+/// *This implementation is available if embedded-hal is built with the `"std"` feature.*
+///
+/// # Panics
+///
+/// `lock` panics if a thread panicked while it had the bus locked.
+#[cfg(feature = "std")]
+impl<T> BusMutex for ::std::sync::Mutex<T> {
+    type Bus = T;
+
+    fn create(bus: T) -> Self {
+        ::std::sync::Mutex::new(bus)
+    }
+
+    fn lock<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.lock().expect("the bus mutex is poisoned"))
+    }
+}
+
+/// Arbiter that grants exclusive access to a shared peripheral
+///
+/// # Examples
+///
+/// A driver that claims its bus for a multi-step sequence
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use std::cell::RefCell;
+///
+/// use hal::blocking::i2c::Write;
+/// use hal::bus::{Claim, Release};
+///
+/// fn reset<C>(bus: &C) -> Result<(), <C::Peripheral as hal::i2c::ErrorType>::Error>
+/// where
+///     C: Claim,
+///     C::Error: std::fmt::Debug,
+///     C::Peripheral: Write,
+/// {
+///     let mut i2c = block!(bus.claim()).unwrap();
+///     // no other driver can use the bus in between these writes
+///     i2c.write(0x76, &[0xe0, 0xb6])?;
+///     i2c.write(0x76, &[0xf4, 0x27])?;
+///     i2c.release();
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     let i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     let bus = RefCell::new(i2c);
+///     reset(&bus).unwrap();
+/// }
+///
+/// # use hal::i2c::{ErrorKind, ErrorType};
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 { type Error = ErrorKind; }
+/// # impl Write for I2c1 {
+/// #     fn write(&mut self, _: u8, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// ```
+pub trait Claim {
+    /// The shared peripheral
+    type Peripheral;
+
+    /// The error returned when the peripheral can't be claimed
+    type Error;
+
+    /// The exclusive access to the peripheral, which is released when it's dropped
+    type Claimed<'a>: DerefMut<Target = Self::Peripheral> + Release
+    where
+        Self: 'a;
+
+    /// Claims exclusive access to the peripheral
+    ///
+    /// Returns `WouldBlock` while the peripheral is claimed by another driver.
+    fn claim(&self) -> nb::Result<Self::Claimed<'_>, Self::Error>;
+}
+
+/// Exclusive access to a peripheral, granted by a [`Claim`] arbiter
+///
+/// [`Claim`]: trait.Claim.html
+pub trait Release {
+    /// Gives up the exclusive access to the peripheral
+    ///
+    /// This is what dropping the claimed peripheral does as well; this method makes it explicit.
+    fn release(self);
+}
+
+/// Single context sharing
+impl<T> Claim for RefCell<T> {
+    type Peripheral = T;
+    type Error = Void;
+    type Claimed<'a>
+        = RefMut<'a, T>
+    where
+        T: 'a;
+
+    fn claim(&self) -> nb::Result<RefMut<'_, T>, Void> {
+        self.try_borrow_mut().map_err(|_| nb::Error::WouldBlock)
+    }
+}
+
+impl<'a, T> Release for RefMut<'a, T> {
+    fn release(self) {}
+}
+
+/// Sharing between threads
+///
+/// *This implementation is available if embedded-hal is built with the `"std"` feature.*
+///
+/// # Panics
+///
+/// `claim` panics if a thread panicked while it had claimed the peripheral.
+#[cfg(feature = "std")]
+impl<T> Claim for ::std::sync::Mutex<T> {
+    type Peripheral = T;
+    type Error = Void;
+    type Claimed<'a>
+        = ::std::sync::MutexGuard<'a, T>
+    where
+        T: 'a;
+
+    fn claim(&self) -> nb::Result<::std::sync::MutexGuard<'_, T>, Void> {
+        match self.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(::std::sync::TryLockError::WouldBlock) => Err(nb::Error::WouldBlock),
+            Err(::std::sync::TryLockError::Poisoned(_)) => panic!("the bus mutex is poisoned"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> Release for ::std::sync::MutexGuard<'a, T> {
+    fn release(self) {}
+}
+
+/// Owner of a shared bus, which hands out proxies to it
+#[derive(Debug)]
+pub struct BusManager<M> {
+    mutex: M,
+}
+
+impl<M> BusManager<M>
+where
+    M: BusMutex,
+{
+    /// Takes ownership of `bus` to share it
+    pub fn new(bus: M::Bus) -> Self {
+        BusManager {
+            mutex: M::create(bus),
+        }
+    }
+
+    /// Claims exclusive access to the bus, if its mutex is also a [`Claim`] arbiter
+    ///
+    /// [`Claim`]: trait.Claim.html
+    pub fn claim(&self) -> nb::Result<<M as Claim>::Claimed<'_>, <M as Claim>::Error>
+    where
+        M: Claim<Peripheral = M::Bus>,
+    {
+        self.mutex.claim()
+    }
+
+    /// Returns a new proxy to the bus, which implements the blocking I2C traits
+    pub fn acquire_i2c(&self) -> I2cProxy<'_, M> {
+        I2cProxy { mutex: &self.mutex }
+    }
+
+    /// Returns a new proxy to the bus, which implements the blocking SPI traits
+    pub fn acquire_spi(&self) -> SpiProxy<'_, M> {
+        SpiProxy { mutex: &self.mutex }
+    }
+
+    /// Returns a new proxy to the device on the bus selected by driving `cs` low, which implements
+    /// `blocking::spi::SpiDevice`
+    ///
+    /// `cs` is driven high, so that the device isn't selected until the first transaction.
+    pub fn acquire_spi_device<CS>(&self, mut cs: CS) -> SpiDeviceProxy<'_, M, CS>
+    where
+        CS: OutputPin,
+    {
+        cs.set_high();
+        SpiDeviceProxy {
+            mutex: &self.mutex,
+            cs,
+        }
+    }
+}
+
+/// Proxy to a shared I2C bus
+///
+/// It implements the blocking I2C traits that the bus implements, with the bus' error type.
+#[derive(Debug)]
+pub struct I2cProxy<'a, M>
+where
+    M: 'a,
+{
+    mutex: &'a M,
+}
+
+impl<'a, M> Clone for I2cProxy<'a, M> {
+    fn clone(&self) -> Self {
+        I2cProxy { mutex: self.mutex }
+    }
+}
+
+impl<'a, M> i2c::ErrorType for I2cProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: i2c::ErrorType,
+{
+    type Error = <M::Bus as i2c::ErrorType>::Error;
+}
+
+impl<'a, M, A> blocking::i2c::Read<A> for I2cProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: blocking::i2c::Read<A>,
+    A: i2c::AddressMode,
+{
+    fn read(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.mutex.lock(|bus| bus.read(address, buffer))
+    }
+}
+
+impl<'a, M, A> blocking::i2c::Write<A> for I2cProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: blocking::i2c::Write<A>,
+    A: i2c::AddressMode,
+{
+    fn write(&mut self, address: A, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.mutex.lock(|bus| bus.write(address, bytes))
+    }
+}
+
+impl<'a, M, A> blocking::i2c::WriteRead<A> for I2cProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: blocking::i2c::WriteRead<A>,
+    A: i2c::AddressMode,
+{
+    fn write_read(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.mutex
+            .lock(|bus| bus.write_read(address, bytes, buffer))
+    }
+}
+
+impl<'a, M, A> blocking::i2c::Transactional<A> for I2cProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: blocking::i2c::Transactional<A>,
+    A: i2c::AddressMode,
+{
+    fn exec(
+        &mut self,
+        address: A,
+        operations: &mut [blocking::i2c::Operation],
+    ) -> Result<(), Self::Error> {
+        self.mutex.lock(|bus| bus.exec(address, operations))
+    }
+}
+
+/// Proxy to a shared SPI bus
+///
+/// It implements the blocking SPI traits, for 8-bit and 16-bit words, that the bus implements,
+/// with the bus' error type.
+#[derive(Debug)]
+pub struct SpiProxy<'a, M>
+where
+    M: 'a,
+{
+    mutex: &'a M,
+}
+
+impl<'a, M> Clone for SpiProxy<'a, M> {
+    fn clone(&self) -> Self {
+        SpiProxy { mutex: self.mutex }
+    }
+}
+
+impl<'a, M> spi::ErrorType for SpiProxy<'a, M>
+where
+    M: BusMutex,
+    M::Bus: spi::ErrorType,
+{
+    type Error = <M::Bus as spi::ErrorType>::Error;
+}
+
+// NOTE these can't be generic over the word type, as that would conflict with the default
+// implementations of the blocking SPI traits
+macro_rules! impl_spi {
+    ($($W:ty),+) => {
+        $(
+            impl<'a, M> blocking::spi::Transfer<$W> for SpiProxy<'a, M>
+            where
+                M: BusMutex,
+                M::Bus: blocking::spi::Transfer<$W>,
+            {
+                fn transfer<'w>(&mut self, words: &'w mut [$W]) -> Result<&'w [$W], Self::Error> {
+                    self.mutex.lock(move |bus| bus.transfer(words))
+                }
+            }
+
+            impl<'a, M> blocking::spi::Write<$W> for SpiProxy<'a, M>
+            where
+                M: BusMutex,
+                M::Bus: blocking::spi::Write<$W>,
+            {
+                fn write(&mut self, words: &[$W]) -> Result<(), Self::Error> {
+                    self.mutex.lock(|bus| bus.write(words))
+                }
+            }
+
+            impl<'a, M> blocking::spi::Transactional<$W> for SpiProxy<'a, M>
+            where
+                M: BusMutex,
+                M::Bus: blocking::spi::Transactional<$W>,
+            {
+                fn exec(
+                    &mut self,
+                    operations: &mut [blocking::spi::Operation<$W>],
+                ) -> Result<(), Self::Error> {
+                    self.mutex.lock(move |bus| bus.exec(operations))
+                }
+            }
+
+            impl<'a, M> blocking::spi::WriteIter<$W> for SpiProxy<'a, M>
+            where
+                M: BusMutex,
+                M::Bus: blocking::spi::WriteIter<$W>,
+            {
+                fn write_iter<WI>(&mut self, words: WI) -> Result<(), Self::Error>
+                where
+                    WI: IntoIterator<Item = $W>,
+                {
+                    self.mutex.lock(move |bus| bus.write_iter(words))
+                }
+            }
+        )+
+    }
+}
+
+impl_spi!(u8, u16);
+
+/// Proxy to a device on a shared SPI bus
+///
+/// It implements `blocking::spi::SpiDevice` for the word types the bus implements the blocking
+/// `Transfer` and `Write` traits for, with the bus' error type. The bus is locked for the whole
+/// transaction, during which the chip select pin of the device is driven low.
+///
+/// # Examples
+///
+/// Two devices on the same SPI bus
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use std::cell::RefCell;
+///
+/// use hal::blocking::spi::{Operation, SpiDevice};
+/// use hal::bus::BusManager;
+///
+/// fn main() {
+///     let (spi, pa3, pa4): (Spi1, Pa3, Pa4) = {
+///         // ..
+/// #       (Spi1, Pa3, Pa4)
+///     };
+///
+///     let manager = BusManager::<RefCell<_>>::new(spi);
+///     let mut flash = manager.acquire_spi_device(pa3);
+///     let mut display = manager.acquire_spi_device(pa4);
+///
+///     let mut pixels = [0; 4];
+///     let read = [0x03, 0, 0, 0];
+///     flash
+///         .transaction(&mut [Operation::Write(&read), Operation::Transfer(&mut pixels)])
+///         .unwrap();
+///     display.transaction(&mut [Operation::Write(&pixels)]).unwrap();
+/// }
+///
+/// # use hal::blocking::spi::{Transfer, Write};
+/// # use hal::digital::OutputPin;
+/// # use hal::spi::{ErrorKind, ErrorType};
+/// # struct Spi1;
+/// # impl ErrorType for Spi1 { type Error = ErrorKind; }
+/// # impl Transfer<u8> for Spi1 {
+/// #     fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ErrorKind> {
+/// #         Ok(words)
+/// #     }
+/// # }
+/// # impl Write<u8> for Spi1 {
+/// #     fn write(&mut self, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// # struct Pa3;
+/// # impl OutputPin for Pa3 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+/// # struct Pa4;
+/// # impl OutputPin for Pa4 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+/// ```
+#[derive(Debug)]
+pub struct SpiDeviceProxy<'a, M, CS>
+where
+    M: 'a,
+{
+    mutex: &'a M,
+    cs: CS,
+}
+
+impl<'a, M, CS> SpiDeviceProxy<'a, M, CS> {
+    /// Returns the chip select pin, giving up the proxy
+    pub fn into_cs(self) -> CS {
+        self.cs
+    }
+}
+
+impl<'a, M, CS> spi::ErrorType for SpiDeviceProxy<'a, M, CS>
+where
+    M: BusMutex,
+    M::Bus: spi::ErrorType,
+{
+    type Error = <M::Bus as spi::ErrorType>::Error;
+}
+
+impl<'a, M, CS, W> SpiDevice<W> for SpiDeviceProxy<'a, M, CS>
+where
+    M: BusMutex,
+    M::Bus: SpiBus<W>,
+    CS: OutputPin,
+{
+    fn transaction(&mut self, operations: &mut [Operation<W>]) -> Result<(), Self::Error> {
+        let cs = &mut self.cs;
+        self.mutex.lock(move |bus| {
+            cs.set_low();
+            let result = blocking::spi::exec(bus, operations);
+            cs.set_high();
+            result
+        })
+    }
+}
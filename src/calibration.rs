@@ -0,0 +1,212 @@
+//! Calibration
+//!
+//! *This module is available if embedded-hal is built with the `"calibration"` feature.*
+//!
+//! ADCs, internal oscillators and capacitive touch peripherals, among others, have to be
+//! calibrated to compensate for the process variations of each chip. The [`Calibrate`] trait lets
+//! production test firmware run the calibration of any of them and store the result, e.g. in
+//! flash, and the application restore it at boot without running the calibration again.
+//!
+//! [`Calibrate`]: trait.Calibrate.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use void::Void;
+
+/// Calibration error kind
+///
+/// This represents a common set of calibration errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic code can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The calibration didn't complete in time, e.g. because the reference is missing
+    Timeout,
+    /// The measured deviation is too large to be compensated
+    OutOfRange,
+    /// The calibration data to apply is invalid, e.g. it's corrupted or from another peripheral
+    InvalidData,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Timeout => "the calibration didn't complete in time",
+            ErrorKind::OutOfRange => "the deviation is too large to be compensated",
+            ErrorKind::InvalidData => "the calibration data is invalid",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// Calibration error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Calibration error type
+///
+/// All the calibration traits inherit from this trait so that a peripheral defines its error type
+/// only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Calibration of a peripheral
+///
+/// # Contract
+///
+/// - `calibrate` MUST run the calibration, apply its result and return it. The peripheral MAY
+///   not be usable, and MAY need its reference, e.g. a reference voltage or an external crystal,
+///   to be available, while the calibration runs.
+/// - `apply` MUST apply calibration data returned by an earlier `calibrate` call, possibly made
+///   by another firmware, e.g. in production, on the same peripheral of the same chip.
+///
+/// # Examples
+///
+/// Calibrating an ADC once, in production, and at every boot afterwards
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::calibration::Calibrate;
+///
+/// fn calibrate<C>(
+///     peripheral: &mut C,
+///     stored: &mut Option<C::CalibrationData>,
+/// ) -> Result<(), C::Error>
+/// where
+///     C: Calibrate,
+///     C::CalibrationData: Clone,
+/// {
+///     match *stored {
+///         Some(ref data) => peripheral.apply(data.clone()),
+///         None => {
+///             *stored = Some(peripheral.calibrate()?);
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let mut adc: Adc1 = {
+///         // ..
+/// #       Adc1 { offset: 0 }
+///     };
+///     // e.g. in flash
+///     let mut stored = None;
+///
+///     calibrate(&mut adc, &mut stored).unwrap();
+/// #   assert_eq!(stored, Some(-3));
+/// }
+///
+/// # use hal::calibration::{ErrorKind, ErrorType};
+/// # struct Adc1 { offset: i8 }
+/// # impl ErrorType for Adc1 { type Error = ErrorKind; }
+/// # impl Calibrate for Adc1 {
+/// #     type CalibrationData = i8;
+/// #     fn calibrate(&mut self) -> Result<i8, ErrorKind> { self.offset = -3; Ok(-3) }
+/// #     fn apply(&mut self, offset: i8) -> Result<(), ErrorKind> { self.offset = offset; Ok(()) }
+/// # }
+/// ```
+pub trait Calibrate: ErrorType {
+    /// The result of a calibration, which can be stored to be applied later
+    type CalibrationData;
+
+    /// Runs the calibration, applies its result and returns it
+    fn calibrate(&mut self) -> Result<Self::CalibrationData, Self::Error>;
+
+    /// Applies the result of an earlier calibration
+    fn apply(&mut self, data: Self::CalibrationData) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Calibrate for Box<T>
+where
+    T: Calibrate + ?Sized,
+{
+    type CalibrationData = T::CalibrationData;
+
+    fn calibrate(&mut self) -> Result<Self::CalibrationData, Self::Error> {
+        (**self).calibrate()
+    }
+
+    fn apply(&mut self, data: Self::CalibrationData) -> Result<(), Self::Error> {
+        (**self).apply(data)
+    }
+}
+
+impl<T> Calibrate for &mut T
+where
+    T: Calibrate + ?Sized,
+{
+    type CalibrationData = T::CalibrationData;
+
+    fn calibrate(&mut self) -> Result<Self::CalibrationData, Self::Error> {
+        (**self).calibrate()
+    }
+
+    fn apply(&mut self, data: Self::CalibrationData) -> Result<(), Self::Error> {
+        (**self).apply(data)
+    }
+}
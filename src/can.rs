@@ -0,0 +1,688 @@
+//! Controller Area Network
+//!
+//! *This module is available if embedded-hal is built with the `"can"` feature.*
+//!
+//! HALs implement the [`Frame`] trait for the frames of their controllers, and the non-blocking
+//! [`Transmit`] and [`Receive`] traits, generic over the frame type, for the controllers
+//! themselves; the blocking variants live in the [`blocking::can`] module. The bit timing of a
+//! controller is configured with the [`NominalTiming`] and [`DataTiming`] traits.
+//!
+//! [`Frame`]: trait.Frame.html
+//! [`Transmit`]: trait.Transmit.html
+//! [`Receive`]: trait.Receive.html
+//! [`blocking::can`]: ../blocking/can/index.html
+//! [`NominalTiming`]: trait.NominalTiming.html
+//! [`DataTiming`]: trait.DataTiming.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// Bit timing parameters
+///
+/// A bit is divided into time quanta (tq) of `prescaler / clock` seconds each: one quantum for the
+/// synchronization segment, `seg1` quanta (propagation segment plus phase segment 1) and `seg2`
+/// quanta (phase segment 2). The bus is sampled between `seg1` and `seg2`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct BitTiming {
+    /// Clock prescaler; the length of a time quantum in clock cycles
+    pub prescaler: u16,
+    /// Time quanta before the sample point, excluding the synchronization segment
+    pub seg1: u8,
+    /// Time quanta after the sample point
+    pub seg2: u8,
+    /// Synchronization jump width, in time quanta
+    pub sjw: u8,
+}
+
+impl BitTiming {
+    /// Computes bit timing parameters for `bitrate` given the frequency of the CAN `clock`, both in
+    /// Hz
+    ///
+    /// The sample point is placed as close as possible to 87.5% of the bit time, as recommended by
+    /// CiA. Only timings with 8 to 25 time quanta per bit, `seg1` no greater than 16, `seg2` no
+    /// greater than 8 and a prescaler no greater than 1024 are considered; these limits are
+    /// supported by most controllers. Returns `None` if `bitrate` can't be reached exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_hal::can::BitTiming;
+    ///
+    /// let timing = BitTiming::from_bitrate(36_000_000, 500_000).unwrap();
+    /// assert_eq!(timing.bitrate(36_000_000), 500_000);
+    /// assert_eq!(
+    ///     timing,
+    ///     BitTiming { prescaler: 4, seg1: 15, seg2: 2, sjw: 2 },
+    /// );
+    ///
+    /// // 7 MHz can't be divided into 1 Mbit/s bits
+    /// assert!(BitTiming::from_bitrate(7_000_000, 1_000_000).is_none());
+    /// ```
+    pub fn from_bitrate(clock: u32, bitrate: u32) -> Option<BitTiming> {
+        if bitrate == 0 {
+            return None;
+        }
+
+        // prefer more time quanta per bit: finer sample point placement and resynchronization
+        for tq in (8..26).rev() {
+            let quanta_rate = match bitrate.checked_mul(tq) {
+                Some(rate) => rate,
+                None => continue,
+            };
+
+            let prescaler = clock / quanta_rate;
+            if prescaler * quanta_rate != clock || prescaler == 0 || prescaler > 1024 {
+                continue;
+            }
+
+            // round `tq * 12.5%` to the nearest integer
+            let seg2 = ((tq + 4) / 8).max(1);
+            let seg1 = tq - 1 - seg2;
+            if seg1 > 16 || seg2 > 8 {
+                continue;
+            }
+
+            return Some(BitTiming {
+                prescaler: prescaler as u16,
+                seg1: seg1 as u8,
+                seg2: seg2 as u8,
+                sjw: seg2.min(4) as u8,
+            });
+        }
+
+        None
+    }
+
+    /// Returns the bitrate, in bits per second, these parameters result in when the CAN clock runs
+    /// at `clock` Hz
+    pub fn bitrate(&self, clock: u32) -> u32 {
+        clock / (u32::from(self.prescaler) * self.quanta())
+    }
+
+    /// Returns the sample point, in per mille of the bit time
+    pub fn sample_point(&self) -> u32 {
+        (1 + u32::from(self.seg1)) * 1000 / self.quanta()
+    }
+
+    fn quanta(&self) -> u32 {
+        1 + u32::from(self.seg1) + u32::from(self.seg2)
+    }
+}
+
+/// Standard 11-bit CAN identifier
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct StandardId(u16);
+
+impl StandardId {
+    /// The identifier with the highest priority, `0`
+    pub const ZERO: StandardId = StandardId(0);
+
+    /// The identifier with the lowest priority, `0x7ff`
+    pub const MAX: StandardId = StandardId(0x7ff);
+
+    /// Creates a standard identifier, returning `None` if `raw` doesn't fit in 11 bits
+    pub fn new(raw: u16) -> Option<StandardId> {
+        if raw <= StandardId::MAX.0 {
+            Some(StandardId(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the identifier as an integer
+    pub fn as_raw(&self) -> u16 {
+        self.0
+    }
+}
+
+/// Extended 29-bit CAN identifier
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct ExtendedId(u32);
+
+impl ExtendedId {
+    /// The identifier with the highest priority, `0`
+    pub const ZERO: ExtendedId = ExtendedId(0);
+
+    /// The identifier with the lowest priority, `0x1fff_ffff`
+    pub const MAX: ExtendedId = ExtendedId(0x1fff_ffff);
+
+    /// Creates an extended identifier, returning `None` if `raw` doesn't fit in 29 bits
+    pub fn new(raw: u32) -> Option<ExtendedId> {
+        if raw <= ExtendedId::MAX.0 {
+            Some(ExtendedId(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the identifier as an integer
+    pub fn as_raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the 11 most significant bits of the identifier, the base ID
+    pub fn standard_id(&self) -> StandardId {
+        StandardId((self.0 >> 18) as u16)
+    }
+}
+
+/// CAN identifier, standard or extended
+///
+/// Identifiers are ordered by their priority in the bus arbitration: the lesser wins, and a
+/// standard identifier wins over the extended identifiers that have the same base ID.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::can::{ExtendedId, Id, StandardId};
+///
+/// let standard = Id::from(StandardId::new(0x123).unwrap());
+/// let extended = Id::from(ExtendedId::new(0x123 << 18).unwrap());
+///
+/// assert!(standard < extended);
+/// assert!(extended < Id::from(StandardId::new(0x124).unwrap()));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Id {
+    /// Standard 11-bit identifier
+    Standard(StandardId),
+    /// Extended 29-bit identifier
+    Extended(ExtendedId),
+}
+
+impl Id {
+    /// Splits the identifier into the fields the arbitration compares, in order
+    fn arbitration_fields(&self) -> (u16, bool, u32) {
+        match *self {
+            Id::Standard(id) => (id.as_raw(), false, 0),
+            Id::Extended(id) => (id.standard_id().as_raw(), true, id.as_raw() & 0x3_ffff),
+        }
+    }
+}
+
+impl Ord for Id {
+    fn cmp(&self, other: &Id) -> Ordering {
+        self.arbitration_fields().cmp(&other.arbitration_fields())
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Id) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<StandardId> for Id {
+    fn from(id: StandardId) -> Id {
+        Id::Standard(id)
+    }
+}
+
+impl From<ExtendedId> for Id {
+    fn from(id: ExtendedId) -> Id {
+        Id::Extended(id)
+    }
+}
+
+/// CAN frame
+///
+/// HALs implement this trait for the frames their controllers transmit and receive, so that
+/// drivers can build and inspect them.
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::can::{Frame, Id, StandardId};
+///
+/// /// CANopen NMT command that starts all the nodes
+/// fn start_all<F: Frame>() -> F {
+///     F::new(StandardId::ZERO, &[0x01, 0x00]).unwrap()
+/// }
+///
+/// fn main() {
+///     let frame: Can1Frame = start_all();
+///
+///     assert_eq!(frame.id(), Id::Standard(StandardId::ZERO));
+///     assert!(frame.is_standard() && frame.is_data_frame());
+///     assert_eq!(frame.data(), [0x01, 0x00]);
+/// }
+///
+/// # struct Can1Frame { id: Id, remote: bool, dlc: usize, data: [u8; 8] }
+/// # impl Frame for Can1Frame {
+/// #     fn new<I: Into<Id>>(id: I, data: &[u8]) -> Option<Self> {
+/// #         if data.len() > 8 { return None; }
+/// #         let mut buffer = [0; 8];
+/// #         buffer[..data.len()].copy_from_slice(data);
+/// #         Some(Can1Frame { id: id.into(), remote: false, dlc: data.len(), data: buffer })
+/// #     }
+/// #     fn new_remote<I: Into<Id>>(id: I, dlc: usize) -> Option<Self> {
+/// #         if dlc > 8 { return None; }
+/// #         Some(Can1Frame { id: id.into(), remote: true, dlc, data: [0; 8] })
+/// #     }
+/// #     fn is_remote_frame(&self) -> bool { self.remote }
+/// #     fn id(&self) -> Id { self.id }
+/// #     fn dlc(&self) -> usize { self.dlc }
+/// #     fn data(&self) -> &[u8] { if self.remote { &[] } else { &self.data[..self.dlc] } }
+/// # }
+/// ```
+pub trait Frame: Sized {
+    /// Creates a data frame
+    ///
+    /// Returns `None` if `data` is longer than the frames of the controller can be.
+    fn new<I>(id: I, data: &[u8]) -> Option<Self>
+    where
+        I: Into<Id>;
+
+    /// Creates a remote frame, which requests `dlc` bytes of data
+    ///
+    /// Returns `None` if `dlc` is out of the range the controller supports.
+    fn new_remote<I>(id: I, dlc: usize) -> Option<Self>
+    where
+        I: Into<Id>;
+
+    /// Is this an extended frame?
+    fn is_extended(&self) -> bool {
+        match self.id() {
+            Id::Standard(_) => false,
+            Id::Extended(_) => true,
+        }
+    }
+
+    /// Is this a standard frame?
+    fn is_standard(&self) -> bool {
+        !self.is_extended()
+    }
+
+    /// Is this a remote frame?
+    fn is_remote_frame(&self) -> bool;
+
+    /// Is this a data frame?
+    fn is_data_frame(&self) -> bool {
+        !self.is_remote_frame()
+    }
+
+    /// Returns the identifier of the frame
+    fn id(&self) -> Id;
+
+    /// Returns the data length code, the number of data bytes the frame carries or, for remote
+    /// frames, requests
+    fn dlc(&self) -> usize;
+
+    /// Returns the data of the frame, which is empty for remote frames
+    fn data(&self) -> &[u8];
+}
+
+/// CAN error kind
+///
+/// This represents a common set of CAN operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The requested bit timing is out of the range supported by the controller
+    Unsupported,
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// A transmitted bit was read back with the opposite level
+    Bit,
+    /// Six consecutive bits of equal level were received
+    Stuff,
+    /// A fixed-form bit field contained an illegal level
+    Form,
+    /// A transmitted frame was not acknowledged
+    Acknowledge,
+    /// The CRC of a received frame didn't match
+    Crc,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Unsupported => "the bit timing is not supported by the controller",
+            ErrorKind::Overrun => "the peripheral receive buffer was overrun",
+            ErrorKind::Bit => "a transmitted bit was read back with the opposite level",
+            ErrorKind::Stuff => "six consecutive bits of equal level were received",
+            ErrorKind::Form => "a fixed-form bit field contained an illegal level",
+            ErrorKind::Acknowledge => "a transmitted frame was not acknowledged",
+            ErrorKind::Crc => "the CRC of a received frame did not match",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// CAN error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// CAN error type
+///
+/// All the CAN traits inherit from this trait so that a CAN controller defines its error type only
+/// once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Nominal (arbitration phase) bit timing configuration
+///
+/// # Contract
+///
+/// Implementations take care of entering and leaving the configuration mode of the controller, so
+/// the bit timing can be changed at any time. Doing so may abort pending transmissions.
+///
+/// # Examples
+///
+/// Switching the bitrate of a bus at runtime
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::can::{BitTiming, NominalTiming};
+///
+/// fn set_bitrate<C>(can: &mut C, bitrate: u32) -> Result<(), C::Error>
+/// where
+///     C: NominalTiming,
+/// {
+///     let timing = BitTiming::from_bitrate(can.clock(), bitrate)
+///         .expect("bitrate can't be derived from the CAN clock");
+///
+///     can.set_nominal_timing(timing)
+/// }
+///
+/// fn main() {
+///     let mut can: Can1 = {
+///         // ..
+/// #       Can1 { timing: None }
+///     };
+///
+///     for &bitrate in &[125_000, 250_000, 500_000] {
+///         set_bitrate(&mut can, bitrate).unwrap();
+///         assert_eq!(can.timing.unwrap().bitrate(can.clock()), bitrate);
+///     }
+/// }
+///
+/// # use hal::can::{ErrorKind, ErrorType};
+/// # struct Can1 { timing: Option<BitTiming> }
+/// # impl ErrorType for Can1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl NominalTiming for Can1 {
+/// #     fn clock(&self) -> u32 { 48_000_000 }
+/// #     fn set_nominal_timing(&mut self, timing: BitTiming) -> Result<(), ErrorKind> {
+/// #         self.timing = Some(timing);
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait NominalTiming: ErrorType {
+    /// Returns the frequency, in Hz, of the clock the bit timing prescaler divides
+    fn clock(&self) -> u32;
+
+    /// Sets the bit timing used during the arbitration phase (and, for classic CAN, the whole
+    /// frame)
+    ///
+    /// Returns an error if the controller doesn't support `timing`
+    fn set_nominal_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NominalTiming for Box<T>
+where
+    T: NominalTiming + ?Sized,
+{
+    fn clock(&self) -> u32 {
+        (**self).clock()
+    }
+
+    fn set_nominal_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error> {
+        (**self).set_nominal_timing(timing)
+    }
+}
+
+impl<T> NominalTiming for &mut T
+where
+    T: NominalTiming + ?Sized,
+{
+    fn clock(&self) -> u32 {
+        (**self).clock()
+    }
+
+    fn set_nominal_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error> {
+        (**self).set_nominal_timing(timing)
+    }
+}
+
+/// Data phase bit timing configuration, for CAN FD controllers with bitrate switching
+///
+/// The contract of [`NominalTiming`](trait.NominalTiming.html) applies to this trait too.
+pub trait DataTiming: NominalTiming {
+    /// Sets the bit timing used during the data phase of frames with bitrate switching enabled
+    ///
+    /// Returns an error if the controller doesn't support `timing`
+    fn set_data_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DataTiming for Box<T>
+where
+    T: DataTiming + ?Sized,
+{
+    fn set_data_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error> {
+        (**self).set_data_timing(timing)
+    }
+}
+
+impl<T> DataTiming for &mut T
+where
+    T: DataTiming + ?Sized,
+{
+    fn set_data_timing(&mut self, timing: BitTiming) -> Result<(), Self::Error> {
+        (**self).set_data_timing(timing)
+    }
+}
+
+/// Transmitting frames
+///
+/// # Examples
+///
+/// A CANopen node that answers the SDO requests of the master
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::can::{Frame, Receive, StandardId, Transmit};
+///
+/// fn serve<C, F>(can: &mut C, node: u16) -> Result<(), C::Error>
+/// where
+///     C: Transmit<F> + Receive<F>,
+///     F: Frame,
+/// {
+///     let request = block!(can.receive())?;
+///     if request.id() == StandardId::new(0x600 + node).unwrap().into() {
+///         // .. expedited upload of a single byte
+///         let response = F::new(StandardId::new(0x580 + node).unwrap(), &[0x4f, 0, 0x10, 0, 42])
+///             .unwrap();
+///         block!(can.transmit(&response))?;
+///     }
+///
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     let mut can: Can1 = {
+///         // ..
+/// #       Can1 { sent: vec![] }
+///     };
+///
+///     serve(&mut can, 5).unwrap();
+///     assert_eq!(can.sent, [(0x585, vec![0x4f, 0, 0x10, 0, 42])]);
+/// }
+///
+/// # use hal::can::{ErrorKind, ErrorType, Id};
+/// # struct Can1 { sent: Vec<(u16, Vec<u8>)> }
+/// # struct Can1Frame { id: Id, data: Vec<u8> }
+/// # impl Frame for Can1Frame {
+/// #     fn new<I: Into<Id>>(id: I, data: &[u8]) -> Option<Self> {
+/// #         Some(Can1Frame { id: id.into(), data: data.to_vec() })
+/// #     }
+/// #     fn new_remote<I: Into<Id>>(_: I, _: usize) -> Option<Self> { None }
+/// #     fn is_remote_frame(&self) -> bool { false }
+/// #     fn id(&self) -> Id { self.id }
+/// #     fn dlc(&self) -> usize { self.data.len() }
+/// #     fn data(&self) -> &[u8] { &self.data }
+/// # }
+/// # impl ErrorType for Can1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl Transmit<Can1Frame> for Can1 {
+/// #     fn transmit(&mut self, frame: &Can1Frame) -> nb::Result<Option<Can1Frame>, ErrorKind> {
+/// #         match frame.id() {
+/// #             Id::Standard(id) => self.sent.push((id.as_raw(), frame.data.clone())),
+/// #             Id::Extended(_) => unreachable!(),
+/// #         }
+/// #         Ok(None)
+/// #     }
+/// # }
+/// # impl Receive<Can1Frame> for Can1 {
+/// #     fn receive(&mut self) -> nb::Result<Can1Frame, ErrorKind> {
+/// #         Ok(Can1Frame::new(StandardId::new(0x605).unwrap(), &[0x40, 0, 0x10, 0]).unwrap())
+/// #     }
+/// # }
+/// ```
+pub trait Transmit<F>: ErrorType
+where
+    F: Frame,
+{
+    /// Puts `frame` in a transmit buffer
+    ///
+    /// If all the buffers are full, a buffer holding a frame of lower priority, i.e. with a
+    /// greater [`Id`](enum.Id.html), may be freed for `frame`: that frame is then returned, for
+    /// the caller to transmit it again later. Otherwise `WouldBlock` is returned until a buffer is
+    /// free.
+    fn transmit(&mut self, frame: &F) -> nb::Result<Option<F>, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> Transmit<F> for Box<T>
+where
+    T: Transmit<F> + ?Sized,
+    F: Frame,
+{
+    fn transmit(&mut self, frame: &F) -> nb::Result<Option<F>, Self::Error> {
+        (**self).transmit(frame)
+    }
+}
+
+impl<T, F> Transmit<F> for &mut T
+where
+    T: Transmit<F> + ?Sized,
+    F: Frame,
+{
+    fn transmit(&mut self, frame: &F) -> nb::Result<Option<F>, Self::Error> {
+        (**self).transmit(frame)
+    }
+}
+
+/// Receiving frames
+///
+/// See [`Transmit`](trait.Transmit.html) for an example.
+pub trait Receive<F>: ErrorType
+where
+    F: Frame,
+{
+    /// Returns a received frame, or `WouldBlock` if there's none
+    fn receive(&mut self) -> nb::Result<F, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> Receive<F> for Box<T>
+where
+    T: Receive<F> + ?Sized,
+    F: Frame,
+{
+    fn receive(&mut self) -> nb::Result<F, Self::Error> {
+        (**self).receive()
+    }
+}
+
+impl<T, F> Receive<F> for &mut T
+where
+    T: Receive<F> + ?Sized,
+    F: Frame,
+{
+    fn receive(&mut self) -> nb::Result<F, Self::Error> {
+        (**self).receive()
+    }
+}
@@ -0,0 +1,11 @@
+//! Compatibility with other versions of the traits
+//!
+//! *This module is available if embedded-hal is built with the `"compat"` feature.*
+//!
+//! Breaking changes to the traits would otherwise split the ecosystem: a driver written against
+//! one version of the traits can't use a HAL that implements another one. The modules in here
+//! contain wrapper types that convert implementations of the traits of another version into
+//! implementations of the traits of this crate, which is called *forwarding* them, and the other
+//! way around, called *reversing* them, so that drivers and HALs can migrate independently.
+
+pub mod v0_2;
@@ -0,0 +1,939 @@
+//! Compatibility with embedded-hal 0.2
+//!
+//! [`Forward`] implements the traits of this crate for implementations of the embedded-hal 0.2
+//! traits, e.g. to use a HAL that hasn't been ported yet with an up to date driver, and
+//! [`Reverse`] implements the embedded-hal 0.2 traits for implementations of the traits of this
+//! crate, e.g. to use a driver that hasn't been ported yet with an up to date HAL. The
+//! [`ForwardCompat`] and [`ReverseCompat`] extension traits wrap any value with their `forward`
+//! and `reverse` methods.
+//!
+//! The wrappers cover the digital, delay, timer, `PwmPin`, watchdog, SPI, serial and I2C traits
+//! that exist in both versions:
+//!
+//! - The errors of the embedded-hal 0.2 implementations are wrapped in an [`Error`] of kind
+//!   `Other`, as they can't be classified. `Forward`'s second type parameter is the type of these
+//!   errors, and is inferred from the traits it's used through.
+//! - The digital traits are forwarded to, and reversed from, the fallible traits of
+//!   [`digital::v2`], so the errors of the pins reach the caller. A reversed pin that implements
+//!   the infallible traits never returns an error; a forwarded pin can be used with the infallible
+//!   traits through the wrappers of [`digital::v1_compat`], which panic on errors.
+//! - The blocking SPI and serial traits are only forwarded and reversed for 8-bit and 16-bit
+//!   words, as both versions provide default implementations of them for all word types.
+//!
+//! [`Forward`]: struct.Forward.html
+//! [`Reverse`]: struct.Reverse.html
+//! [`ForwardCompat`]: trait.ForwardCompat.html
+//! [`ReverseCompat`]: trait.ReverseCompat.html
+//! [`Error`]: struct.Error.html
+//! [`digital::v2`]: ../../digital/v2/index.html
+//! [`digital::v1_compat`]: ../../digital/v1_compat/index.html
+//!
+//! # Examples
+//!
+//! Using a HAL that only implements the embedded-hal 0.2 traits with a driver that uses the
+//! traits of this crate
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//! extern crate embedded_hal_v0_2 as hal02;
+//!
+//! use hal::blocking::i2c::WriteRead;
+//! use hal::compat::v0_2::ForwardCompat;
+//! use hal::i2c::{Error, ErrorKind};
+//!
+//! /// Driver that uses the traits of this crate
+//! fn read_id<I: WriteRead>(i2c: &mut I) -> Result<u8, ErrorKind> {
+//!     let mut id = [0];
+//!     i2c.write_read(0x76, &[0xd0], &mut id).map_err(|e| e.kind())?;
+//!     Ok(id[0])
+//! }
+//!
+//! fn main() {
+//!     let i2c: I2c1 = {
+//!         // ..
+//! #       I2c1
+//!     };
+//!
+//!     let mut i2c = i2c.forward();
+//!     assert_eq!(read_id(&mut i2c), Ok(0x60));
+//!
+//!     // get the HAL's I2C back
+//!     let _i2c: I2c1 = i2c.into_inner();
+//! }
+//!
+//! /// I2C bus of a HAL that implements the embedded-hal 0.2 traits
+//! struct I2c1;
+//!
+//! impl hal02::blocking::i2c::WriteRead for I2c1 {
+//!     type Error = ();
+//!
+//!     fn write_read(&mut self, _: u8, _: &[u8], buffer: &mut [u8]) -> Result<(), ()> {
+//!         // ..
+//! #       buffer[0] = 0x60;
+//! #       Ok(())
+//!     }
+//! }
+//! ```
+
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+
+use embedded_hal_v0_2 as v02;
+use nb;
+use void::Void;
+
+use blocking;
+use digital::{self, v2, PinState};
+use i2c;
+use serial;
+use spi;
+#[cfg(feature = "timer-unproven")]
+use timer::Cancel;
+use timer::{CountDown, Periodic};
+#[cfg(feature = "watchdog")]
+use watchdog::{Watchdog, WatchdogDisable, WatchdogEnable};
+use PwmPin;
+
+/// Error of an embedded-hal 0.2 implementation, as reported by a forwarded implementation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Error<E> {
+    inner: E,
+}
+
+impl<E> Error<E> {
+    /// Returns a reference to the error of the embedded-hal 0.2 implementation
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns the error of the embedded-hal 0.2 implementation, consuming this error
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E> fmt::Display for Error<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "embedded-hal 0.2 error: {:?}", self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> ::std::error::Error for Error<E> where E: Debug {}
+
+impl<E> digital::Error for Error<E>
+where
+    E: Debug,
+{
+    fn kind(&self) -> digital::ErrorKind {
+        digital::ErrorKind::Other
+    }
+}
+
+impl<E> i2c::Error for Error<E>
+where
+    E: Debug,
+{
+    fn kind(&self) -> i2c::ErrorKind {
+        i2c::ErrorKind::Other
+    }
+}
+
+impl<E> serial::Error for Error<E>
+where
+    E: Debug,
+{
+    fn kind(&self) -> serial::ErrorKind {
+        serial::ErrorKind::Other
+    }
+}
+
+impl<E> spi::Error for Error<E>
+where
+    E: Debug,
+{
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::Other
+    }
+}
+
+fn forward<E>(error: E) -> Error<E> {
+    Error { inner: error }
+}
+
+fn forward_nb<E>(error: nb::Error<E>) -> nb::Error<Error<E>> {
+    match error {
+        nb::Error::Other(e) => nb::Error::Other(forward(e)),
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+    }
+}
+
+/// Implementation of the traits of this crate for an implementation of the embedded-hal 0.2
+/// traits
+///
+/// `E` is the error type of the wrapped implementation; for implementations of traits without
+/// errors, like the delays, it's `Void`.
+pub struct Forward<T, E = Void> {
+    inner: T,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<T, E> Forward<T, E> {
+    /// Wraps `inner`
+    pub fn new(inner: T) -> Self {
+        Forward {
+            inner,
+            _error: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the wrapped implementation
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped implementation
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped implementation, consuming the wrapper
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, E> Debug for Forward<T, E>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Forward").field(&self.inner).finish()
+    }
+}
+
+/// Wraps values in a [`Forward`]
+///
+/// [`Forward`]: struct.Forward.html
+pub trait ForwardCompat: Sized {
+    /// Wraps `self` in a `Forward`, to use it through the traits of this crate
+    fn forward<E>(self) -> Forward<Self, E> {
+        Forward::new(self)
+    }
+}
+
+impl<T> ForwardCompat for T {}
+
+/// Implementation of the embedded-hal 0.2 traits for an implementation of the traits of this
+/// crate
+pub struct Reverse<T> {
+    inner: T,
+}
+
+impl<T> Reverse<T> {
+    /// Wraps `inner`
+    pub fn new(inner: T) -> Self {
+        Reverse { inner }
+    }
+
+    /// Returns a reference to the wrapped implementation
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped implementation
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped implementation, consuming the wrapper
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Debug for Reverse<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Reverse").field(&self.inner).finish()
+    }
+}
+
+/// Wraps values in a [`Reverse`]
+///
+/// [`Reverse`]: struct.Reverse.html
+pub trait ReverseCompat: Sized {
+    /// Wraps `self` in a `Reverse`, to use it through the embedded-hal 0.2 traits
+    fn reverse(self) -> Reverse<Self> {
+        Reverse::new(self)
+    }
+}
+
+impl<T> ReverseCompat for T {}
+
+// Digital I/O
+
+impl From<v02::digital::v2::PinState> for PinState {
+    fn from(state: v02::digital::v2::PinState) -> Self {
+        match state {
+            v02::digital::v2::PinState::Low => PinState::Low,
+            v02::digital::v2::PinState::High => PinState::High,
+        }
+    }
+}
+
+impl From<PinState> for v02::digital::v2::PinState {
+    fn from(state: PinState) -> Self {
+        match state {
+            PinState::Low => v02::digital::v2::PinState::Low,
+            PinState::High => v02::digital::v2::PinState::High,
+        }
+    }
+}
+
+impl<T, E> v2::OutputPin for Forward<T, E>
+where
+    T: v02::digital::v2::OutputPin<Error = E>,
+    E: Debug,
+{
+    type Error = Error<E>;
+
+    fn set_low(&mut self) -> Result<(), Error<E>> {
+        self.inner.set_low().map_err(forward)
+    }
+
+    fn set_high(&mut self) -> Result<(), Error<E>> {
+        self.inner.set_high().map_err(forward)
+    }
+
+    fn set_state(&mut self, state: PinState) -> Result<(), Error<E>> {
+        self.inner.set_state(state.into()).map_err(forward)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T, E> v2::StatefulOutputPin for Forward<T, E>
+where
+    T: v02::digital::v2::StatefulOutputPin<Error = E>,
+    E: Debug,
+{
+    fn is_set_high(&self) -> Result<bool, Error<E>> {
+        self.inner.is_set_high().map_err(forward)
+    }
+
+    fn is_set_low(&self) -> Result<bool, Error<E>> {
+        self.inner.is_set_low().map_err(forward)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T, E> v2::ToggleableOutputPin for Forward<T, E>
+where
+    T: v02::digital::v2::ToggleableOutputPin<Error = E>,
+    E: Debug,
+{
+    type Error = Error<E>;
+
+    fn toggle(&mut self) -> Result<(), Error<E>> {
+        self.inner.toggle().map_err(forward)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T, E> v2::InputPin for Forward<T, E>
+where
+    T: v02::digital::v2::InputPin<Error = E>,
+    E: Debug,
+{
+    type Error = Error<E>;
+
+    fn is_high(&self) -> Result<bool, Error<E>> {
+        self.inner.is_high().map_err(forward)
+    }
+
+    fn is_low(&self) -> Result<bool, Error<E>> {
+        self.inner.is_low().map_err(forward)
+    }
+}
+
+impl<T> v02::digital::v2::OutputPin for Reverse<T>
+where
+    T: v2::OutputPin,
+{
+    type Error = T::Error;
+
+    fn set_low(&mut self) -> Result<(), T::Error> {
+        self.inner.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), T::Error> {
+        self.inner.set_high()
+    }
+
+    fn set_state(&mut self, state: v02::digital::v2::PinState) -> Result<(), T::Error> {
+        self.inner.set_state(state.into())
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v02::digital::v2::StatefulOutputPin for Reverse<T>
+where
+    T: v2::StatefulOutputPin,
+{
+    fn is_set_high(&self) -> Result<bool, T::Error> {
+        self.inner.is_set_high()
+    }
+
+    fn is_set_low(&self) -> Result<bool, T::Error> {
+        self.inner.is_set_low()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v02::digital::v2::ToggleableOutputPin for Reverse<T>
+where
+    T: v2::ToggleableOutputPin,
+{
+    type Error = T::Error;
+
+    fn toggle(&mut self) -> Result<(), T::Error> {
+        self.inner.toggle()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v02::digital::v2::InputPin for Reverse<T>
+where
+    T: v2::InputPin,
+{
+    type Error = T::Error;
+
+    fn is_high(&self) -> Result<bool, T::Error> {
+        self.inner.is_high()
+    }
+
+    fn is_low(&self) -> Result<bool, T::Error> {
+        self.inner.is_low()
+    }
+}
+
+// Delays
+
+impl<T, UXX> blocking::delay::DelayMs<UXX> for Forward<T, Void>
+where
+    T: v02::blocking::delay::DelayMs<UXX>,
+{
+    fn delay_ms(&mut self, ms: UXX) {
+        self.inner.delay_ms(ms)
+    }
+}
+
+impl<T, UXX> blocking::delay::DelayUs<UXX> for Forward<T, Void>
+where
+    T: v02::blocking::delay::DelayUs<UXX>,
+{
+    fn delay_us(&mut self, us: UXX) {
+        self.inner.delay_us(us)
+    }
+}
+
+impl<T, UXX> v02::blocking::delay::DelayMs<UXX> for Reverse<T>
+where
+    T: blocking::delay::DelayMs<UXX>,
+{
+    fn delay_ms(&mut self, ms: UXX) {
+        self.inner.delay_ms(ms)
+    }
+}
+
+impl<T, UXX> v02::blocking::delay::DelayUs<UXX> for Reverse<T>
+where
+    T: blocking::delay::DelayUs<UXX>,
+{
+    fn delay_us(&mut self, us: UXX) {
+        self.inner.delay_us(us)
+    }
+}
+
+// Timers and PWM
+
+impl<T> CountDown for Forward<T, Void>
+where
+    T: v02::timer::CountDown,
+{
+    type Time = T::Time;
+
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<T::Time>,
+    {
+        self.inner.start(count)
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        self.inner.wait()
+    }
+}
+
+impl<T> Periodic for Forward<T, Void> where T: v02::timer::Periodic {}
+
+#[cfg(feature = "timer-unproven")]
+impl<T> Cancel for Forward<T, Void>
+where
+    T: v02::timer::Cancel,
+{
+    type Error = T::Error;
+
+    fn cancel(&mut self) -> Result<(), T::Error> {
+        self.inner.cancel()
+    }
+}
+
+impl<T> PwmPin for Forward<T, Void>
+where
+    T: v02::PwmPin,
+{
+    type Duty = T::Duty;
+
+    fn disable(&mut self) {
+        self.inner.disable()
+    }
+
+    fn enable(&mut self) {
+        self.inner.enable()
+    }
+
+    fn get_duty(&self) -> T::Duty {
+        self.inner.get_duty()
+    }
+
+    fn get_max_duty(&self) -> T::Duty {
+        self.inner.get_max_duty()
+    }
+
+    fn set_duty(&mut self, duty: T::Duty) {
+        self.inner.set_duty(duty)
+    }
+}
+
+impl<T> v02::timer::CountDown for Reverse<T>
+where
+    T: CountDown,
+{
+    type Time = T::Time;
+
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<T::Time>,
+    {
+        self.inner.start(count)
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        self.inner.wait()
+    }
+}
+
+impl<T> v02::timer::Periodic for Reverse<T> where T: Periodic {}
+
+#[cfg(feature = "timer-unproven")]
+impl<T> v02::timer::Cancel for Reverse<T>
+where
+    T: Cancel,
+{
+    type Error = T::Error;
+
+    fn cancel(&mut self) -> Result<(), T::Error> {
+        self.inner.cancel()
+    }
+}
+
+impl<T> v02::PwmPin for Reverse<T>
+where
+    T: PwmPin,
+{
+    type Duty = T::Duty;
+
+    fn disable(&mut self) {
+        self.inner.disable()
+    }
+
+    fn enable(&mut self) {
+        self.inner.enable()
+    }
+
+    fn get_duty(&self) -> T::Duty {
+        self.inner.get_duty()
+    }
+
+    fn get_max_duty(&self) -> T::Duty {
+        self.inner.get_max_duty()
+    }
+
+    fn set_duty(&mut self, duty: T::Duty) {
+        self.inner.set_duty(duty)
+    }
+}
+
+// Watchdogs
+
+#[cfg(feature = "watchdog")]
+impl<T> Watchdog for Forward<T, Void>
+where
+    T: v02::watchdog::Watchdog,
+{
+    fn feed(&mut self) {
+        self.inner.feed()
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> WatchdogEnable for Forward<T, Void>
+where
+    T: v02::watchdog::WatchdogEnable,
+{
+    type Time = T::Time;
+
+    fn start<P>(&mut self, period: P)
+    where
+        P: Into<T::Time>,
+    {
+        self.inner.start(period)
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> WatchdogDisable for Forward<T, Void>
+where
+    T: v02::watchdog::WatchdogDisable,
+{
+    fn disable(&mut self) {
+        self.inner.disable()
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> v02::watchdog::Watchdog for Reverse<T>
+where
+    T: Watchdog,
+{
+    fn feed(&mut self) {
+        self.inner.feed()
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> v02::watchdog::WatchdogEnable for Reverse<T>
+where
+    T: WatchdogEnable,
+{
+    type Time = T::Time;
+
+    fn start<P>(&mut self, period: P)
+    where
+        P: Into<T::Time>,
+    {
+        self.inner.start(period)
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> v02::watchdog::WatchdogDisable for Reverse<T>
+where
+    T: WatchdogDisable,
+{
+    fn disable(&mut self) {
+        self.inner.disable()
+    }
+}
+
+// SPI
+
+impl<T, E> spi::ErrorType for Forward<T, E>
+where
+    E: Debug,
+{
+    type Error = Error<E>;
+}
+
+impl<T, E, W> spi::FullDuplex<W> for Forward<T, E>
+where
+    T: v02::spi::FullDuplex<W, Error = E>,
+    E: Debug,
+{
+    fn read(&mut self) -> nb::Result<W, Error<E>> {
+        self.inner.read().map_err(forward_nb)
+    }
+
+    fn send(&mut self, word: W) -> nb::Result<(), Error<E>> {
+        self.inner.send(word).map_err(forward_nb)
+    }
+}
+
+impl<T, W> v02::spi::FullDuplex<W> for Reverse<T>
+where
+    T: spi::FullDuplex<W>,
+{
+    type Error = T::Error;
+
+    fn read(&mut self) -> nb::Result<W, T::Error> {
+        self.inner.read()
+    }
+
+    fn send(&mut self, word: W) -> nb::Result<(), T::Error> {
+        self.inner.send(word)
+    }
+}
+
+macro_rules! blocking_spi {
+    ($($W:ty),+) => {
+        $(
+            impl<T, E> blocking::spi::Transfer<$W> for Forward<T, E>
+            where
+                T: v02::blocking::spi::Transfer<$W, Error = E>,
+                E: Debug,
+            {
+                fn transfer<'w>(&mut self, words: &'w mut [$W]) -> Result<&'w [$W], Error<E>> {
+                    self.inner.transfer(words).map_err(forward)
+                }
+            }
+
+            impl<T, E> blocking::spi::Write<$W> for Forward<T, E>
+            where
+                T: v02::blocking::spi::Write<$W, Error = E>,
+                E: Debug,
+            {
+                fn write(&mut self, words: &[$W]) -> Result<(), Error<E>> {
+                    self.inner.write(words).map_err(forward)
+                }
+            }
+
+            impl<T, E> blocking::spi::WriteIter<$W> for Forward<T, E>
+            where
+                T: v02::blocking::spi::WriteIter<$W, Error = E>,
+                E: Debug,
+            {
+                fn write_iter<WI>(&mut self, words: WI) -> Result<(), Error<E>>
+                where
+                    WI: IntoIterator<Item = $W>,
+                {
+                    self.inner.write_iter(words).map_err(forward)
+                }
+            }
+
+            impl<T> v02::blocking::spi::Transfer<$W> for Reverse<T>
+            where
+                T: blocking::spi::Transfer<$W>,
+            {
+                type Error = T::Error;
+
+                fn transfer<'w>(&mut self, words: &'w mut [$W]) -> Result<&'w [$W], T::Error> {
+                    self.inner.transfer(words)
+                }
+            }
+
+            impl<T> v02::blocking::spi::Write<$W> for Reverse<T>
+            where
+                T: blocking::spi::Write<$W>,
+            {
+                type Error = T::Error;
+
+                fn write(&mut self, words: &[$W]) -> Result<(), T::Error> {
+                    self.inner.write(words)
+                }
+            }
+
+            impl<T> v02::blocking::spi::WriteIter<$W> for Reverse<T>
+            where
+                T: blocking::spi::WriteIter<$W>,
+            {
+                type Error = T::Error;
+
+                fn write_iter<WI>(&mut self, words: WI) -> Result<(), T::Error>
+                where
+                    WI: IntoIterator<Item = $W>,
+                {
+                    self.inner.write_iter(words)
+                }
+            }
+        )+
+    }
+}
+
+blocking_spi!(u8, u16);
+
+// Serial
+
+impl<T, E> serial::ErrorType for Forward<T, E>
+where
+    E: Debug,
+{
+    type Error = Error<E>;
+}
+
+impl<T, E, Word> serial::Read<Word> for Forward<T, E>
+where
+    T: v02::serial::Read<Word, Error = E>,
+    E: Debug,
+{
+    fn read(&mut self) -> nb::Result<Word, Error<E>> {
+        self.inner.read().map_err(forward_nb)
+    }
+}
+
+impl<T, E, Word> serial::Write<Word> for Forward<T, E>
+where
+    T: v02::serial::Write<Word, Error = E>,
+    E: Debug,
+{
+    fn write(&mut self, word: Word) -> nb::Result<(), Error<E>> {
+        self.inner.write(word).map_err(forward_nb)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Error<E>> {
+        self.inner.flush().map_err(forward_nb)
+    }
+}
+
+impl<T, Word> v02::serial::Read<Word> for Reverse<T>
+where
+    T: serial::Read<Word>,
+{
+    type Error = T::Error;
+
+    fn read(&mut self) -> nb::Result<Word, T::Error> {
+        self.inner.read()
+    }
+}
+
+impl<T, Word> v02::serial::Write<Word> for Reverse<T>
+where
+    T: serial::Write<Word>,
+{
+    type Error = T::Error;
+
+    fn write(&mut self, word: Word) -> nb::Result<(), T::Error> {
+        self.inner.write(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), T::Error> {
+        self.inner.flush()
+    }
+}
+
+macro_rules! blocking_serial {
+    ($($Word:ty),+) => {
+        $(
+            impl<T, E> blocking::serial::Write<$Word> for Forward<T, E>
+            where
+                T: v02::blocking::serial::Write<$Word, Error = E>,
+                E: Debug,
+            {
+                fn bwrite_all(&mut self, buffer: &[$Word]) -> Result<(), Error<E>> {
+                    self.inner.bwrite_all(buffer).map_err(forward)
+                }
+
+                fn bflush(&mut self) -> Result<(), Error<E>> {
+                    self.inner.bflush().map_err(forward)
+                }
+            }
+
+            impl<T> v02::blocking::serial::Write<$Word> for Reverse<T>
+            where
+                T: blocking::serial::Write<$Word>,
+            {
+                type Error = T::Error;
+
+                fn bwrite_all(&mut self, buffer: &[$Word]) -> Result<(), T::Error> {
+                    self.inner.bwrite_all(buffer)
+                }
+
+                fn bflush(&mut self) -> Result<(), T::Error> {
+                    self.inner.bflush()
+                }
+            }
+        )+
+    }
+}
+
+blocking_serial!(u8, u16);
+
+// I2C
+
+impl<T, E> i2c::ErrorType for Forward<T, E>
+where
+    E: Debug,
+{
+    type Error = Error<E>;
+}
+
+impl<T, E> blocking::i2c::Read for Forward<T, E>
+where
+    T: v02::blocking::i2c::Read<Error = E>,
+    E: Debug,
+{
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error<E>> {
+        self.inner.read(address, buffer).map_err(forward)
+    }
+}
+
+impl<T, E> blocking::i2c::Write for Forward<T, E>
+where
+    T: v02::blocking::i2c::Write<Error = E>,
+    E: Debug,
+{
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Error<E>> {
+        self.inner.write(address, bytes).map_err(forward)
+    }
+}
+
+impl<T, E> blocking::i2c::WriteRead for Forward<T, E>
+where
+    T: v02::blocking::i2c::WriteRead<Error = E>,
+    E: Debug,
+{
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Error<E>> {
+        self.inner
+            .write_read(address, bytes, buffer)
+            .map_err(forward)
+    }
+}
+
+impl<T> v02::blocking::i2c::Read for Reverse<T>
+where
+    T: blocking::i2c::Read,
+{
+    type Error = T::Error;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), T::Error> {
+        self.inner.read(address, buffer)
+    }
+}
+
+impl<T> v02::blocking::i2c::Write for Reverse<T>
+where
+    T: blocking::i2c::Write,
+{
+    type Error = T::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), T::Error> {
+        self.inner.write(address, bytes)
+    }
+}
+
+impl<T> v02::blocking::i2c::WriteRead for Reverse<T>
+where
+    T: blocking::i2c::WriteRead,
+{
+    type Error = T::Error;
+
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), T::Error> {
+        self.inner.write_read(address, bytes, buffer)
+    }
+}
@@ -0,0 +1,82 @@
+//! Digital I/O conformance checks
+//!
+//! *This module is available if embedded-hal is built with the `"digital-unproven"` feature.*
+
+use digital::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+/// Checks that `is_set_high` and `is_set_low` report the state last set
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::conformance;
+/// use hal::digital::PinState;
+/// use hal::mock::pin::{Mock, Transaction};
+///
+/// fn main() {
+///     let mut pin = Mock::new(&[
+///         Transaction::Set(PinState::High),
+///         Transaction::Set(PinState::Low),
+///     ]);
+///
+///     conformance::digital::stateful_output_reads_back(&mut pin);
+///     pin.done();
+/// }
+/// ```
+pub fn stateful_output_reads_back<P>(pin: &mut P)
+where
+    P: OutputPin + StatefulOutputPin,
+{
+    pin.set_high();
+    assert!(pin.is_set_high(), "is_set_high must be true after set_high");
+    assert!(!pin.is_set_low(), "is_set_low must be false after set_high");
+
+    pin.set_low();
+    assert!(pin.is_set_low(), "is_set_low must be true after set_low");
+    assert!(
+        !pin.is_set_high(),
+        "is_set_high must be false after set_low"
+    );
+}
+
+/// Checks that `toggle` inverts the state reported by `is_set_high`, and restores the state of
+/// the pin
+pub fn toggle_inverts_state<P>(pin: &mut P)
+where
+    P: ToggleableOutputPin + StatefulOutputPin,
+{
+    let high = pin.is_set_high();
+
+    pin.toggle();
+    assert_eq!(
+        pin.is_set_high(),
+        !high,
+        "toggle must invert the state of the pin"
+    );
+    assert_eq!(
+        pin.is_set_low(),
+        high,
+        "toggle must invert the state of the pin"
+    );
+
+    pin.toggle();
+    assert_eq!(
+        pin.is_set_high(),
+        high,
+        "toggling twice must restore the state of the pin"
+    );
+}
+
+/// Checks that an input pin is either high or low
+pub fn input_is_high_or_low<P>(pin: &P)
+where
+    P: InputPin,
+{
+    assert_ne!(
+        pin.is_high(),
+        pin.is_low(),
+        "an input must be either high or low"
+    );
+}
@@ -0,0 +1,74 @@
+//! I2C conformance checks
+//!
+//! These checks need a device on the bus, and an address without one.
+
+use blocking::i2c::{Read, Write, WriteRead};
+use i2c::{Error, ErrorKind, NoAcknowledgeSource};
+
+/// Checks that writing to an `address` without a device fails with `NoAcknowledge`
+///
+/// The source of the error must be `Address`, or `Unknown` if the peripheral can't tell.
+pub fn absent_address_is_not_acknowledged<I>(i2c: &mut I, address: u8)
+where
+    I: Write,
+{
+    match i2c.write(address, &[0]) {
+        Ok(()) => panic!("a write to an address without a device must fail"),
+        Err(e) => match e.kind() {
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)
+            | ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown) => {}
+            kind => panic!(
+                "a write to an address without a device must fail with NoAcknowledge(Address), \
+                 not {:?}",
+                kind
+            ),
+        },
+    }
+}
+
+/// Checks that `write_read` reads what a `write` followed by a `read` does
+///
+/// `register` must select registers whose value doesn't change, e.g. an identification register,
+/// of the device at `address`, and that device must keep its register pointer between
+/// transactions. A `write_read` that doesn't address the device again with a repeated start, or
+/// that sends a stop in between, typically reads from the wrong register.
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::conformance;
+/// use hal::mock::i2c::{Mock, Transaction};
+///
+/// fn main() {
+///     // a BME280, as scripted
+///     let mut i2c = Mock::new(&[
+///         Transaction::write(0x76, &[0xd0]),
+///         Transaction::read(0x76, &[0x60]),
+///         Transaction::write_read(0x76, &[0xd0], &[0x60]),
+///     ]);
+///
+///     conformance::i2c::write_read_matches_write_then_read::<_, 1>(&mut i2c, 0x76, &[0xd0]);
+///     i2c.done();
+/// }
+/// ```
+pub fn write_read_matches_write_then_read<I, const N: usize>(
+    i2c: &mut I,
+    address: u8,
+    register: &[u8],
+) where
+    I: Read + Write + WriteRead,
+{
+    let mut expected = [0; N];
+    i2c.write(address, register).expect("write failed");
+    i2c.read(address, &mut expected).expect("read failed");
+
+    let mut buffer = [0; N];
+    i2c.write_read(address, register, &mut buffer)
+        .expect("write_read failed");
+    assert_eq!(
+        buffer, expected,
+        "write_read must read what a write followed by a read does"
+    );
+}
@@ -0,0 +1,104 @@
+//! Conformance checks for implementations of the HAL traits
+//!
+//! *This module is available if embedded-hal is built with the `"conformance"` feature.*
+//!
+//! The traits only constrain the signatures of the implementations; their contracts are up to
+//! each HAL to follow. The functions in here exercise an implementation and panic, with a message
+//! that names the violated contract, if it doesn't behave as the trait documents. HAL authors can
+//! run them in their test suites, on the target, e.g. with a test harness that runs on the
+//! device, or on the host, against the `linux` implementations or their own host-side backends.
+//!
+//! Some of the checks need some wiring: the loopback ones expect the output of the peripheral,
+//! e.g. MOSI, to be connected to its input, e.g. MISO, and the I2C ones a device on the bus.
+//!
+//! The [`conformance_tests!`] macro generates a `#[test]` function for each of the checks that
+//! apply to a peripheral, from an expression that creates it.
+//!
+//! [`conformance_tests!`]: ../macro.conformance_tests.html
+
+#[cfg(feature = "digital-unproven")]
+pub mod digital;
+pub mod i2c;
+#[cfg(feature = "property")]
+pub mod property;
+pub mod serial;
+pub mod spi;
+
+/// Generates a `#[test]` function for each conformance check of a peripheral
+///
+/// *This macro is available if embedded-hal is built with the `"conformance"` feature.*
+///
+/// Each entry names the kind of peripheral, followed by an expression that creates one, which is
+/// evaluated again in each of the generated test functions:
+///
+/// - `spi_loopback`, for a blocking SPI bus whose MOSI is connected to its MISO
+/// - `serial_loopback`, for a serial interface whose TX is connected to its RX
+/// - `stateful_output_pin`, for an `OutputPin + StatefulOutputPin`
+/// - `toggleable_output_pin`, for a `ToggleableOutputPin + StatefulOutputPin`
+///
+/// The generated functions are named after the entry and the check, so each kind of peripheral
+/// can only be listed once per module.
+///
+/// # Examples
+///
+/// Running the loopback checks on a Raspberry Pi
+///
+/// ```no_run
+/// #[macro_use]
+/// extern crate embedded_hal as hal;
+///
+/// use hal::linux::serial::Serial;
+/// use hal::linux::spi::Spidev;
+///
+/// conformance_tests! {
+///     spi_loopback: Spidev::open("/dev/spidev0.0").unwrap();
+///     serial_loopback: Serial::open("/dev/ttyS0", 115_200).unwrap();
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! conformance_tests {
+    () => {};
+    (spi_loopback: $peripheral:expr; $($rest:tt)*) => {
+        #[test]
+        fn spi_loopback_transfer_returns_sent_words() {
+            $crate::conformance::spi::loopback_transfer(&mut $peripheral, [0x00, 0x55, 0xaa, 0xff]);
+        }
+
+        #[test]
+        fn spi_loopback_write_then_transfer() {
+            $crate::conformance::spi::write_does_not_disturb_transfer(&mut $peripheral);
+        }
+
+        $crate::conformance_tests!($($rest)*);
+    };
+    (serial_loopback: $peripheral:expr; $($rest:tt)*) => {
+        #[test]
+        fn serial_loopback_reads_written_words() {
+            $crate::conformance::serial::loopback(&mut $peripheral, b"\x00\x55\xaa\xff");
+        }
+
+        #[test]
+        fn serial_flush_completes() {
+            $crate::conformance::serial::flush_completes(&mut $peripheral);
+        }
+
+        $crate::conformance_tests!($($rest)*);
+    };
+    (stateful_output_pin: $peripheral:expr; $($rest:tt)*) => {
+        #[test]
+        fn stateful_output_pin_reads_back_set_state() {
+            $crate::conformance::digital::stateful_output_reads_back(&mut $peripheral);
+        }
+
+        $crate::conformance_tests!($($rest)*);
+    };
+    (toggleable_output_pin: $peripheral:expr; $($rest:tt)*) => {
+        #[test]
+        fn toggleable_output_pin_toggle_inverts_state() {
+            $crate::conformance::digital::toggle_inverts_state(&mut $peripheral);
+        }
+
+        $crate::conformance_tests!($($rest)*);
+    };
+}
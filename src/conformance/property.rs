@@ -0,0 +1,558 @@
+//! Property tests with randomized transaction sequences
+//!
+//! *This module is available if embedded-hal is built with the `"property"` feature.*
+//!
+//! Where the other conformance checks run fixed sequences, these functions run sequences of
+//! operations that are generated from a seed, and check properties that must hold for any of
+//! them. Running them with many seeds, e.g. from a loop in a test, covers orderings that
+//! hand-written tests miss. A failure reports the seed and the index of the operation that broke
+//! the property, so the sequence can be replayed.
+//!
+//! There are two kinds of properties:
+//!
+//! - the loopback properties exercise an implementation wired in loopback, e.g. a `linux` SPI
+//!   device whose MOSI is connected to its MISO, or the Linux `i2c-stub` module for I2C
+//! - the error propagation properties, [`SpiScript`], [`SerialScript`] and [`I2cScript`], exercise
+//!   an adapter, e.g. a bus proxy or a wrapper, around the scripted mocks, and check that it
+//!   forwards each operation and reports the kind of each error it gets back
+//!
+//! The sequences are generated by [`TestRng`], a small deterministic generator, so the results
+//! don't depend on the platform. The `"property"` feature enables the `"conformance"` and
+//! `"mock"` features.
+//!
+//! [`SpiScript`]: struct.SpiScript.html
+//! [`SerialScript`]: struct.SerialScript.html
+//! [`I2cScript`]: struct.I2cScript.html
+//! [`TestRng`]: struct.TestRng.html
+//!
+//! # Examples
+//!
+//! Checking that the bus proxies of the `bus` module propagate the errors of the bus
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use std::cell::RefCell;
+//!
+//! use hal::bus::BusManager;
+//! use hal::conformance::property;
+//!
+//! fn main() {
+//!     for seed in 0..100 {
+//!         let script = property::SpiScript::new(seed, 20);
+//!         let manager = BusManager::<RefCell<_>>::new(script.mock());
+//!         script.check(&mut manager.acquire_spi());
+//!     }
+//! }
+//! ```
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use blocking;
+use i2c::{self, Error as _};
+use mock;
+use serial::{self, Error as _};
+use spi::{self, Error as _};
+
+/// Deterministic pseudo random number generator (xorshift64*)
+///
+/// It's *not* suitable for anything but generating test cases.
+#[derive(Clone, Debug)]
+pub struct TestRng {
+    state: u64,
+}
+
+impl TestRng {
+    /// Creates a generator from `seed`
+    pub fn new(seed: u64) -> Self {
+        // the state must not be zero, and `seed ^ 0x9e37_79b9_7f4a_7c15` is zero for one seed
+        let state = match seed ^ 0x9e37_79b9_7f4a_7c15 {
+            0 => 0x9e37_79b9_7f4a_7c15,
+            state => state,
+        };
+        TestRng { state }
+    }
+
+    /// Returns the next random number
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        (self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as u32
+    }
+
+    /// Returns a random number in `0..n`
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero
+    pub fn below(&mut self, n: u32) -> u32 {
+        self.next_u32() % n
+    }
+
+    /// Returns `true` once every `n` calls, on average
+    pub fn one_in(&mut self, n: u32) -> bool {
+        self.below(n) == 0
+    }
+
+    /// Returns `len` random bytes
+    pub fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next_u32() as u8).collect()
+    }
+
+    /// Returns a random element of `items`
+    ///
+    /// # Panics
+    ///
+    /// If `items` is empty
+    pub fn pick<T>(&mut self, items: &[T]) -> T
+    where
+        T: Copy,
+    {
+        items[self.below(items.len() as u32) as usize]
+    }
+
+    /// Returns between 1 and `max_len` random bytes
+    fn bytes_up_to(&mut self, max_len: usize) -> Vec<u8> {
+        let len = 1 + self.below(max_len as u32) as usize;
+        self.bytes(len)
+    }
+}
+
+const SPI_ERRORS: [spi::ErrorKind; 5] = [
+    spi::ErrorKind::Overrun,
+    spi::ErrorKind::ModeFault,
+    spi::ErrorKind::FrameFormat,
+    spi::ErrorKind::ChipSelectFault,
+    spi::ErrorKind::Other,
+];
+
+const SERIAL_ERRORS: [serial::ErrorKind; 5] = [
+    serial::ErrorKind::Overrun,
+    serial::ErrorKind::FrameFormat,
+    serial::ErrorKind::Parity,
+    serial::ErrorKind::Noise,
+    serial::ErrorKind::Other,
+];
+
+const I2C_ERRORS: [i2c::ErrorKind; 7] = [
+    i2c::ErrorKind::Bus,
+    i2c::ErrorKind::ArbitrationLoss,
+    i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address),
+    i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Data),
+    i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Unknown),
+    i2c::ErrorKind::Overrun,
+    i2c::ErrorKind::Other,
+];
+
+/// Checks that random writes and transfers of up to `max_len` words, in a random order, behave as
+/// a loopback: each transfer receives the words it sent, whatever was written before it
+///
+/// MOSI must be connected to MISO.
+pub fn spi_loopback<S>(spi: &mut S, seed: u64, operations: usize, max_len: usize)
+where
+    S: blocking::spi::Transfer<u8> + blocking::spi::Write<u8>,
+{
+    let mut rng = TestRng::new(seed);
+    for i in 0..operations {
+        let words = rng.bytes_up_to(max_len);
+        if rng.one_in(3) {
+            spi.write(&words)
+                .unwrap_or_else(|e| fail(seed, i, &format_args!("write failed: {:?}", e)));
+        } else {
+            let mut buffer = words.clone();
+            let received = spi
+                .transfer(&mut buffer)
+                .unwrap_or_else(|e| fail(seed, i, &format_args!("transfer failed: {:?}", e)));
+            if received != &words[..] {
+                fail(
+                    seed,
+                    i,
+                    &format_args!("sent {:?} but received {:?}", words, received),
+                );
+            }
+        }
+    }
+}
+
+/// Checks that random bursts of up to `max_burst` words, each written and flushed, are read back
+/// in order
+///
+/// TX must be connected to RX, and the receive buffer must hold at least `max_burst` words.
+pub fn serial_loopback<S>(serial: &mut S, seed: u64, operations: usize, max_burst: usize)
+where
+    S: serial::Read<u8> + serial::Write<u8>,
+{
+    let mut rng = TestRng::new(seed);
+    for i in 0..operations {
+        let words = rng.bytes_up_to(max_burst);
+        for &word in &words {
+            block!(serial.write(word))
+                .unwrap_or_else(|e| fail(seed, i, &format_args!("write failed: {:?}", e)));
+        }
+        block!(serial.flush())
+            .unwrap_or_else(|e| fail(seed, i, &format_args!("flush failed: {:?}", e)));
+        for &word in &words {
+            let received = block!(serial.read())
+                .unwrap_or_else(|e| fail(seed, i, &format_args!("read failed: {:?}", e)));
+            if received != word {
+                fail(
+                    seed,
+                    i,
+                    &format_args!(
+                        "wrote {:?} but read {:#04x} for {:#04x}",
+                        words, received, word
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Checks that random single byte register writes and `write_read`s to the device at `address`
+/// behave like a memory: each read returns the value last written to the register
+///
+/// The registers in `registers`, which must not be empty, must be plain read-write registers, as
+/// those of the Linux `i2c-stub` module, or of an EEPROM with one-byte addresses.
+///
+/// # Panics
+///
+/// If `registers` is empty, or if the device doesn't behave like a memory
+pub fn i2c_registers<I>(
+    i2c: &mut I,
+    address: u8,
+    registers: Range<u8>,
+    seed: u64,
+    operations: usize,
+) where
+    I: blocking::i2c::Write + blocking::i2c::WriteRead,
+{
+    assert!(
+        registers.start < registers.end,
+        "i2c_registers: the `registers` range must not be empty"
+    );
+    let mut rng = TestRng::new(seed);
+    let mut model = [None; 256];
+    let count = u32::from(registers.end - registers.start);
+    for i in 0..operations {
+        let register = registers.start + rng.below(count) as u8;
+        match model[usize::from(register)] {
+            Some(expected) if rng.one_in(2) => {
+                let mut value = [0];
+                i2c.write_read(address, &[register], &mut value)
+                    .unwrap_or_else(|e| fail(seed, i, &format_args!("write_read failed: {:?}", e)));
+                if value[0] != expected {
+                    fail(
+                        seed,
+                        i,
+                        &format_args!(
+                            "read {:#04x} from register {:#04x}, which was set to {:#04x}",
+                            value[0], register, expected
+                        ),
+                    );
+                }
+            }
+            _ => {
+                let value = rng.next_u32() as u8;
+                i2c.write(address, &[register, value])
+                    .unwrap_or_else(|e| fail(seed, i, &format_args!("write failed: {:?}", e)));
+                model[usize::from(register)] = Some(value);
+            }
+        }
+    }
+}
+
+/// Whether it's a write, the words sent and received, and the error
+type SpiOperation = (bool, Vec<u8>, Vec<u8>, Option<spi::ErrorKind>);
+
+/// Random sequence of SPI writes and transfers, some of which fail, to check that an adapter
+/// around the SPI mock forwards each of them and reports the kind of each error
+#[derive(Debug)]
+pub struct SpiScript {
+    seed: u64,
+    mock: mock::spi::Mock,
+    operations: Vec<SpiOperation>,
+}
+
+impl SpiScript {
+    /// Generates `operations` random operations from `seed`
+    pub fn new(seed: u64, operations: usize) -> Self {
+        let mut rng = TestRng::new(seed);
+        let mut script = Vec::new();
+        let operations = (0..operations)
+            .map(|_| {
+                let write = rng.one_in(2);
+                let words = rng.bytes_up_to(16);
+                let response = rng.bytes(words.len());
+                let error = if rng.one_in(4) {
+                    Some(rng.pick(&SPI_ERRORS))
+                } else {
+                    None
+                };
+                let transaction = if write {
+                    mock::spi::Transaction::write(&words)
+                } else {
+                    mock::spi::Transaction::transfer(&words, &response)
+                };
+                script.push(match error {
+                    Some(error) => transaction.with_error(error),
+                    None => transaction,
+                });
+                (write, words, response, error)
+            })
+            .collect();
+
+        SpiScript {
+            seed,
+            mock: mock::spi::Mock::new(&script),
+            operations,
+        }
+    }
+
+    /// Returns the mock to build the adapter from
+    pub fn mock(&self) -> mock::spi::Mock {
+        self.mock.clone()
+    }
+
+    /// Runs the operations on `spi`, the adapter, and checks their results
+    pub fn check<S>(mut self, spi: &mut S)
+    where
+        S: blocking::spi::Transfer<u8> + blocking::spi::Write<u8>,
+    {
+        let seed = self.seed;
+        for (i, (write, words, response, error)) in self.operations.into_iter().enumerate() {
+            let result = if write {
+                spi.write(&words).map(|()| None)
+            } else {
+                let mut buffer = words.clone();
+                spi.transfer(&mut buffer)
+                    .map(|received| Some(received.to_vec()))
+            };
+            match result {
+                Ok(Some(ref received)) if error.is_none() && *received != response => fail(
+                    seed,
+                    i,
+                    &format_args!("expected to receive {:?}, got {:?}", response, received),
+                ),
+                result => {
+                    let kind = result.err().map(|e| e.kind());
+                    if kind != error {
+                        fail(
+                            seed,
+                            i,
+                            &format_args!("expected error {:?}, got {:?}", error, kind),
+                        );
+                    }
+                }
+            }
+        }
+        self.mock.done();
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SerialOperation {
+    Read(u8),
+    Write(u8),
+    Flush,
+}
+
+/// Random sequence of serial reads, writes and flushes, some of which fail, to check that an
+/// adapter around the serial mock forwards each of them and reports the kind of each error
+#[derive(Debug)]
+pub struct SerialScript {
+    seed: u64,
+    mock: mock::serial::Mock,
+    operations: Vec<(SerialOperation, Option<serial::ErrorKind>)>,
+}
+
+impl SerialScript {
+    /// Generates `operations` random operations from `seed`
+    pub fn new(seed: u64, operations: usize) -> Self {
+        let mut rng = TestRng::new(seed);
+        let mut script = Vec::new();
+        let operations = (0..operations)
+            .map(|_| {
+                let word = rng.next_u32() as u8;
+                let (operation, transaction) = match rng.below(3) {
+                    0 => (
+                        SerialOperation::Read(word),
+                        mock::serial::Transaction::read(word),
+                    ),
+                    1 => (
+                        SerialOperation::Write(word),
+                        mock::serial::Transaction::write(word),
+                    ),
+                    _ => (SerialOperation::Flush, mock::serial::Transaction::flush()),
+                };
+                let error = if rng.one_in(4) {
+                    Some(rng.pick(&SERIAL_ERRORS))
+                } else {
+                    None
+                };
+                script.push(match error {
+                    Some(error) => transaction.with_error(error),
+                    None => transaction,
+                });
+                (operation, error)
+            })
+            .collect();
+
+        SerialScript {
+            seed,
+            mock: mock::serial::Mock::new(&script),
+            operations,
+        }
+    }
+
+    /// Returns the mock to build the adapter from
+    pub fn mock(&self) -> mock::serial::Mock {
+        self.mock.clone()
+    }
+
+    /// Runs the operations on `serial`, the adapter, and checks their results
+    pub fn check<S>(mut self, serial: &mut S)
+    where
+        S: serial::Read<u8> + serial::Write<u8>,
+    {
+        let seed = self.seed;
+        for (i, (operation, error)) in self.operations.into_iter().enumerate() {
+            let result = match operation {
+                SerialOperation::Read(word) => block!(serial.read()).map(|read| {
+                    if read != word {
+                        fail(
+                            seed,
+                            i,
+                            &format_args!("expected to read {:#04x}, got {:#04x}", word, read),
+                        );
+                    }
+                }),
+                SerialOperation::Write(word) => block!(serial.write(word)),
+                SerialOperation::Flush => block!(serial.flush()),
+            };
+            let kind = result.err().map(|e| e.kind());
+            if kind != error {
+                fail(
+                    seed,
+                    i,
+                    &format_args!("expected error {:?}, got {:?}", error, kind),
+                );
+            }
+        }
+        self.mock.done();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum I2cOperation {
+    Read,
+    Write,
+    WriteRead,
+}
+
+/// The operation, the address, the bytes written and read, and the error
+type I2cTransaction = (I2cOperation, u8, Vec<u8>, Vec<u8>, Option<i2c::ErrorKind>);
+
+/// Random sequence of I2C reads, writes and `write_read`s, some of which fail, to check that an
+/// adapter around the I2C mock forwards each of them and reports the kind of each error
+#[derive(Debug)]
+pub struct I2cScript {
+    seed: u64,
+    mock: mock::i2c::Mock,
+    operations: Vec<I2cTransaction>,
+}
+
+impl I2cScript {
+    /// Generates `operations` random operations from `seed`
+    pub fn new(seed: u64, operations: usize) -> Self {
+        let mut rng = TestRng::new(seed);
+        let mut script = Vec::new();
+        let operations = (0..operations)
+            .map(|_| {
+                let address = rng.below(0x80) as u8;
+                let bytes = rng.bytes_up_to(8);
+                let response = rng.bytes_up_to(8);
+                let (operation, transaction) = match rng.below(3) {
+                    0 => (
+                        I2cOperation::Read,
+                        mock::i2c::Transaction::read(address, &response),
+                    ),
+                    1 => (
+                        I2cOperation::Write,
+                        mock::i2c::Transaction::write(address, &bytes),
+                    ),
+                    _ => (
+                        I2cOperation::WriteRead,
+                        mock::i2c::Transaction::write_read(address, &bytes, &response),
+                    ),
+                };
+                let error = if rng.one_in(4) {
+                    Some(rng.pick(&I2C_ERRORS))
+                } else {
+                    None
+                };
+                script.push(match error {
+                    Some(error) => transaction.with_error(error),
+                    None => transaction,
+                });
+                (operation, address, bytes, response, error)
+            })
+            .collect();
+
+        I2cScript {
+            seed,
+            mock: mock::i2c::Mock::new(&script),
+            operations,
+        }
+    }
+
+    /// Returns the mock to build the adapter from
+    pub fn mock(&self) -> mock::i2c::Mock {
+        self.mock.clone()
+    }
+
+    /// Runs the operations on `i2c`, the adapter, and checks their results
+    pub fn check<I>(mut self, i2c: &mut I)
+    where
+        I: blocking::i2c::Read + blocking::i2c::Write + blocking::i2c::WriteRead,
+    {
+        let seed = self.seed;
+        for (i, (operation, address, bytes, response, error)) in
+            self.operations.into_iter().enumerate()
+        {
+            let mut buffer = response.clone();
+            for byte in buffer.iter_mut() {
+                *byte = !*byte;
+            }
+            let result = match operation {
+                I2cOperation::Read => i2c.read(address, &mut buffer),
+                I2cOperation::Write => i2c.write(address, &bytes),
+                I2cOperation::WriteRead => i2c.write_read(address, &bytes, &mut buffer),
+            };
+            let kind = result.err().map(|e| e.kind());
+            if kind != error {
+                fail(
+                    seed,
+                    i,
+                    &format_args!("expected error {:?}, got {:?}", error, kind),
+                );
+            }
+            if operation != I2cOperation::Write && error.is_none() && buffer != response {
+                fail(
+                    seed,
+                    i,
+                    &format_args!("expected to read {:?}, got {:?}", response, buffer),
+                );
+            }
+        }
+        self.mock.done();
+    }
+}
+
+fn fail(seed: u64, operation: usize, message: &::core::fmt::Arguments) -> ! {
+    panic!(
+        "property failed at operation {} of seed {}: {}",
+        operation, seed, message
+    )
+}
@@ -0,0 +1,56 @@
+//! Serial conformance checks
+
+use serial::{Read, Write};
+
+/// Checks that each word written is read back, as TX is connected to RX
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::conformance;
+/// use hal::mock::serial::{Mock, Transaction};
+///
+/// fn main() {
+///     // a loopback, as scripted
+///     let mut serial = Mock::new(&[
+///         Transaction::write(b'o'),
+///         Transaction::flush(),
+///         Transaction::read(b'o'),
+///         Transaction::write(b'k'),
+///         Transaction::flush(),
+///         Transaction::read(b'k'),
+///     ]);
+///
+///     conformance::serial::loopback(&mut serial, b"ok");
+///     serial.done();
+/// }
+/// ```
+pub fn loopback<S>(serial: &mut S, words: &[u8])
+where
+    S: Read<u8> + Write<u8>,
+{
+    for &word in words {
+        block!(serial.write(word)).expect("write failed");
+        block!(serial.flush()).expect("flush failed");
+        let received = block!(serial.read()).expect("read failed");
+        assert_eq!(
+            received, word,
+            "a loopback read must return the word written"
+        );
+    }
+}
+
+/// Checks that `flush` eventually completes after a write, rather than returning `WouldBlock`
+/// forever
+///
+/// TX must be connected to RX, as the written word is read back afterwards.
+pub fn flush_completes<S>(serial: &mut S)
+where
+    S: Read<u8> + Write<u8>,
+{
+    block!(serial.write(0x55)).expect("write failed");
+    block!(serial.flush()).expect("flush failed");
+    block!(serial.read()).expect("read failed");
+}
@@ -0,0 +1,69 @@
+//! SPI conformance checks
+
+use blocking::spi::{Transfer, Write};
+use spi::FullDuplex;
+
+/// Checks that `transfer` returns as many words as it sent, and, as MOSI is connected to MISO,
+/// the words it sent
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::conformance;
+/// use hal::mock::spi::{Mock, Transaction};
+///
+/// fn main() {
+///     // a loopback, as scripted
+///     let mut spi = Mock::new(&[Transaction::transfer(&[1, 2, 3], &[1, 2, 3])]);
+///
+///     conformance::spi::loopback_transfer(&mut spi, [1, 2, 3]);
+///     spi.done();
+/// }
+/// ```
+pub fn loopback_transfer<S, const N: usize>(spi: &mut S, words: [u8; N])
+where
+    S: Transfer<u8>,
+{
+    let mut buffer = words;
+    let received = spi.transfer(&mut buffer).expect("transfer failed");
+    assert_eq!(
+        received.len(),
+        N,
+        "transfer must return as many words as it sent"
+    );
+    assert_eq!(
+        received,
+        &words[..],
+        "a loopback transfer must receive the sent words"
+    );
+}
+
+/// Checks that the words received during a `write` don't show up in the next `transfer`, i.e.
+/// that `write` drains the receive buffer
+///
+/// MOSI must be connected to MISO.
+pub fn write_does_not_disturb_transfer<S>(spi: &mut S)
+where
+    S: Transfer<u8> + Write<u8>,
+{
+    spi.write(&[0xa5, 0x5a]).expect("write failed");
+    loopback_transfer(spi, [0x0f, 0xf0]);
+}
+
+/// Checks that each `send` is followed by the `read` of the word sent, as MOSI is connected to
+/// MISO
+pub fn loopback_full_duplex<S>(spi: &mut S, words: &[u8])
+where
+    S: FullDuplex<u8>,
+{
+    for &word in words {
+        block!(spi.send(word)).expect("send failed");
+        let received = block!(spi.read()).expect("read failed");
+        assert_eq!(
+            received, word,
+            "a loopback read must return the word sent last"
+        );
+    }
+}
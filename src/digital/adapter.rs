@@ -0,0 +1,276 @@
+//! Adapters that change the behavior of a pin
+//!
+//! - [`InvertedPin`] swaps the levels of a pin, e.g. for an active-low LED or button, so that the
+//!   driver can be written in terms of "on" and "off".
+//! - [`DebouncedInput`] (with the `"digital-unproven"` feature) only reports the level of an
+//!   input once it's stable, e.g. for a mechanical switch.
+//!
+//! Both wrap a pin and implement the pin traits themselves, so they can be combined, e.g. an
+//! active-low button is an `InvertedPin<DebouncedInput<P, D>>`.
+//!
+//! [`InvertedPin`]: struct.InvertedPin.html
+//! [`DebouncedInput`]: struct.DebouncedInput.html
+
+#[cfg(feature = "digital-unproven")]
+use core::cell::RefCell;
+
+#[cfg(feature = "digital-unproven")]
+use blocking::delay::DelayUs;
+use digital::OutputPin;
+#[cfg(feature = "digital-unproven")]
+use digital::{InputPin, StatefulOutputPin, ToggleableOutputPin, Wait};
+#[cfg(feature = "digital-unproven")]
+use nb;
+#[cfg(feature = "digital-unproven")]
+use void::Void;
+
+/// Pin whose levels are swapped
+///
+/// Driving it high drives the wrapped pin low, and it reads low when the wrapped pin is high. It
+/// implements `OutputPin` and, with the `"digital-unproven"` feature, `InputPin`,
+/// `StatefulOutputPin`, `ToggleableOutputPin` and `Wait`, whose rising and falling edges are
+/// swapped too, if the wrapped pin does.
+///
+/// # Examples
+///
+/// An LED that lights up when its pin is low
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::digital::adapter::InvertedPin;
+/// use hal::digital::OutputPin;
+///
+/// struct Led<P> {
+///     pin: P,
+/// }
+///
+/// impl<P: OutputPin> Led<P> {
+///     fn on(&mut self) {
+///         self.pin.set_high()
+///     }
+/// }
+///
+/// fn main() {
+///     let pc13: Pc13 = {
+///         // ..
+/// #       Pc13(true)
+///     };
+///
+///     let mut led = Led {
+///         pin: InvertedPin::new(pc13),
+///     };
+///     led.on();
+/// #   assert!(!led.pin.into_inner().0);
+/// }
+///
+/// # struct Pc13(bool);
+/// # impl OutputPin for Pc13 {
+/// #     fn set_low(&mut self) { self.0 = false }
+/// #     fn set_high(&mut self) { self.0 = true }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct InvertedPin<P> {
+    pin: P,
+}
+
+impl<P> InvertedPin<P> {
+    /// Wraps `pin`
+    pub fn new(pin: P) -> Self {
+        InvertedPin { pin }
+    }
+
+    /// Returns the wrapped pin
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> OutputPin for InvertedPin<P>
+where
+    P: OutputPin,
+{
+    fn set_low(&mut self) {
+        self.pin.set_high()
+    }
+
+    fn set_high(&mut self) {
+        self.pin.set_low()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P> InputPin for InvertedPin<P>
+where
+    P: InputPin,
+{
+    fn is_high(&self) -> bool {
+        self.pin.is_low()
+    }
+
+    fn is_low(&self) -> bool {
+        self.pin.is_high()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P> StatefulOutputPin for InvertedPin<P>
+where
+    P: StatefulOutputPin,
+{
+    fn is_set_high(&self) -> bool {
+        self.pin.is_set_low()
+    }
+
+    fn is_set_low(&self) -> bool {
+        self.pin.is_set_high()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P> ToggleableOutputPin for InvertedPin<P>
+where
+    P: ToggleableOutputPin,
+{
+    fn toggle(&mut self) {
+        self.pin.toggle()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P> Wait for InvertedPin<P>
+where
+    P: Wait,
+{
+    fn wait_for_high(&mut self) -> nb::Result<(), Void> {
+        self.pin.wait_for_low()
+    }
+
+    fn wait_for_low(&mut self) -> nb::Result<(), Void> {
+        self.pin.wait_for_high()
+    }
+
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void> {
+        self.pin.wait_for_falling_edge()
+    }
+
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void> {
+        self.pin.wait_for_rising_edge()
+    }
+
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Void> {
+        self.pin.wait_for_any_edge()
+    }
+}
+
+/// Input pin whose level is only reported once it's stable
+///
+/// *This type is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// Each `is_high` or `is_low` call samples the wrapped pin every `interval_us` microseconds until
+/// it reads the same level `samples` times in a row, and returns that level: it blocks for at
+/// least `(samples - 1) * interval_us` microseconds, and for as long as the input bounces.
+///
+/// # Examples
+///
+/// A push button that must be stable for 4 ms
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::digital::adapter::DebouncedInput;
+/// use hal::digital::InputPin;
+///
+/// fn main() {
+///     let (pa0, delay): (Pa0, Delay) = {
+///         // ..
+/// #       (Pa0(Cell::new(0)), Delay)
+///     };
+///
+///     let button = DebouncedInput::new(pa0, delay, 1_000, 5);
+///     assert!(button.is_high());
+/// }
+///
+/// # use std::cell::Cell;
+/// # use hal::blocking::delay::DelayUs;
+/// # // bounces for the first 3 samples
+/// # struct Pa0(Cell<u32>);
+/// # impl InputPin for Pa0 {
+/// #     fn is_high(&self) -> bool {
+/// #         self.0.set(self.0.get() + 1);
+/// #         self.0.get() > 3 || self.0.get() % 2 == 0
+/// #     }
+/// #     fn is_low(&self) -> bool { !self.is_high() }
+/// # }
+/// # struct Delay;
+/// # impl DelayUs<u32> for Delay { fn delay_us(&mut self, _: u32) {} }
+/// ```
+#[cfg(feature = "digital-unproven")]
+#[derive(Debug)]
+pub struct DebouncedInput<P, D> {
+    pin: P,
+    delay: RefCell<D>,
+    interval_us: u32,
+    samples: u8,
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P, D> DebouncedInput<P, D>
+where
+    P: InputPin,
+    D: DelayUs<u32>,
+{
+    /// Wraps `pin`, which must read the same level `samples` times, `interval_us` microseconds
+    /// apart, for the level to be reported
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is zero
+    pub fn new(pin: P, delay: D, interval_us: u32, samples: u8) -> Self {
+        assert!(samples > 0, "at least one sample is needed");
+        DebouncedInput {
+            pin,
+            delay: RefCell::new(delay),
+            interval_us,
+            samples,
+        }
+    }
+
+    /// Returns the wrapped pin and the delay
+    pub fn into_inner(self) -> (P, D) {
+        (self.pin, self.delay.into_inner())
+    }
+
+    fn level(&self) -> bool {
+        let mut delay = self.delay.borrow_mut();
+        let mut level = self.pin.is_high();
+        let mut stable = 1;
+        while stable < self.samples {
+            delay.delay_us(self.interval_us);
+            let sample = self.pin.is_high();
+            if sample == level {
+                stable += 1;
+            } else {
+                level = sample;
+                stable = 1;
+            }
+        }
+        level
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<P, D> InputPin for DebouncedInput<P, D>
+where
+    P: InputPin,
+    D: DelayUs<u32>,
+{
+    fn is_high(&self) -> bool {
+        self.level()
+    }
+
+    fn is_low(&self) -> bool {
+        !self.level()
+    }
+}
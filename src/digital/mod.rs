@@ -0,0 +1,548 @@
+//! Digital I/O
+//!
+//! The traits of this module are infallible, which fits the pins of a microcontroller, but not
+//! e.g. the pins of an I2C GPIO expander, where every operation can fail. The [`v2`] module
+//! provides fallible versions of them, which return a `Result`:
+//!
+//! - New drivers SHOULD be written against the [`v2`] traits.
+//! - The implementations of these traits also implement the [`v2`] traits, with a `Void` error,
+//!   through the blanket implementations of [`v2_compat`], so existing HALs keep working with new
+//!   drivers.
+//! - Implementations of the [`v2`] traits can be used with older drivers through the wrappers of
+//!   [`v1_compat`], which panic on errors.
+//!
+//! The prelude only contains the traits of this module: as their implementers also implement the
+//! [`v2`] traits, importing both would make the methods of their implementers ambiguous.
+//!
+//! The [`adapter`] module provides wrappers that invert or debounce the pins implementing these
+//! traits.
+//!
+//! [`adapter`]: adapter/index.html
+//! [`v2`]: v2/index.html
+//! [`v2_compat`]: v2_compat/index.html
+//! [`v1_compat`]: v1_compat/index.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+use core::ops::Not;
+
+#[cfg(feature = "digital-unproven")]
+use nb;
+use void::Void;
+
+pub mod adapter;
+pub mod v1_compat;
+pub mod v2;
+pub mod v2_compat;
+
+/// Digital I/O error kind
+///
+/// This represents a common set of digital I/O errors. HAL implementations are free to define
+/// more specific or additional error types; the [`Error`] trait maps them to one of these kinds so
+/// that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// A different error occurred. The original error may contain more information, e.g. the
+    /// error of the bus to a GPIO expander
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// Digital I/O error
+///
+/// The `Error` associated types of the [`v2`](v2/index.html) traits must implement this trait.
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Level of a pin
+///
+/// It converts from and into a `bool`, `true` being `High`.
+///
+/// # Examples
+///
+/// Shifting a byte out, most significant bit first
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::digital::{OutputPin, PinState};
+///
+/// fn shift_out<D, C>(data: &mut D, clock: &mut C, byte: u8)
+/// where
+///     D: OutputPin,
+///     C: OutputPin,
+/// {
+///     for bit in (0..8).rev() {
+///         data.set_state(PinState::from(byte & (1 << bit) != 0));
+///         clock.set_high();
+///         clock.set_low();
+///     }
+/// }
+///
+/// fn main() {
+///     let (mut ser, mut srclk): (Pa0, Pa1) = {
+///         // ..
+/// #       (Pa0(Vec::new()), Pa1)
+///     };
+///
+///     shift_out(&mut ser, &mut srclk, 0b1010_0000);
+/// #   assert_eq!(ser.0, [true, false, true, false, false, false, false, false]);
+/// }
+///
+/// # struct Pa0(Vec<bool>);
+/// # impl OutputPin for Pa0 {
+/// #     fn set_low(&mut self) { self.0.push(false) }
+/// #     fn set_high(&mut self) { self.0.push(true) }
+/// # }
+/// # struct Pa1;
+/// # impl OutputPin for Pa1 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum PinState {
+    /// Low level
+    Low,
+    /// High level
+    High,
+}
+
+impl From<bool> for PinState {
+    fn from(value: bool) -> Self {
+        if value {
+            PinState::High
+        } else {
+            PinState::Low
+        }
+    }
+}
+
+impl From<PinState> for bool {
+    fn from(state: PinState) -> Self {
+        state == PinState::High
+    }
+}
+
+impl Not for PinState {
+    type Output = PinState;
+
+    fn not(self) -> PinState {
+        match self {
+            PinState::Low => PinState::High,
+            PinState::High => PinState::Low,
+        }
+    }
+}
+
+/// Single digital push-pull output pin
+pub trait OutputPin {
+    /// Drives the pin low
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be low, e.g. due to external
+    /// electrical sources
+    fn set_low(&mut self);
+
+    /// Drives the pin high
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be high, e.g. due to external
+    /// electrical sources
+    fn set_high(&mut self);
+
+    /// Drives the pin to `state`
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be `state`, e.g. due to
+    /// external electrical sources
+    fn set_state(&mut self, state: PinState) {
+        match state {
+            PinState::Low => self.set_low(),
+            PinState::High => self.set_high(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> OutputPin for Box<T>
+where
+    T: OutputPin + ?Sized,
+{
+    fn set_low(&mut self) {
+        (**self).set_low()
+    }
+
+    fn set_high(&mut self) {
+        (**self).set_high()
+    }
+
+    fn set_state(&mut self, state: PinState) {
+        (**self).set_state(state)
+    }
+}
+
+impl<T> OutputPin for &mut T
+where
+    T: OutputPin + ?Sized,
+{
+    fn set_low(&mut self) {
+        (**self).set_low()
+    }
+
+    fn set_high(&mut self) {
+        (**self).set_high()
+    }
+
+    fn set_state(&mut self, state: PinState) {
+        (**self).set_state(state)
+    }
+}
+
+/// Push-pull output pin that can read its output state
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+#[cfg(feature = "digital-unproven")]
+pub trait StatefulOutputPin {
+    /// Is the pin in drive high mode?
+    ///
+    /// *NOTE* this does *not* read the electrical state of the pin
+    fn is_set_high(&self) -> bool;
+
+    /// Is the pin in drive low mode?
+    ///
+    /// *NOTE* this does *not* read the electrical state of the pin
+    fn is_set_low(&self) -> bool;
+}
+
+#[cfg(all(feature = "alloc", feature = "digital-unproven"))]
+impl<T> StatefulOutputPin for Box<T>
+where
+    T: StatefulOutputPin + ?Sized,
+{
+    fn is_set_high(&self) -> bool {
+        (**self).is_set_high()
+    }
+
+    fn is_set_low(&self) -> bool {
+        (**self).is_set_low()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> StatefulOutputPin for &mut T
+where
+    T: StatefulOutputPin + ?Sized,
+{
+    fn is_set_high(&self) -> bool {
+        (**self).is_set_high()
+    }
+
+    fn is_set_low(&self) -> bool {
+        (**self).is_set_low()
+    }
+}
+
+/// Output pin that can be toggled
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// See [toggleable](toggleable) to use a software implementation if
+/// both [OutputPin](trait.OutputPin.html) and
+/// [StatefulOutputPin](trait.StatefulOutputPin.html) are
+/// implemented. Otherwise, implement this using hardware mechanisms.
+#[cfg(feature = "digital-unproven")]
+pub trait ToggleableOutputPin {
+    /// Toggle pin output.
+    fn toggle(&mut self);
+}
+
+/// If you can read **and** write the output state, a pin is
+/// toggleable by software.
+///
+/// ```
+/// use embedded_hal::digital::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+/// use embedded_hal::digital::toggleable;
+///
+/// /// A virtual output pin that exists purely in software
+/// struct MyPin {
+///     state: bool
+/// }
+///
+/// impl OutputPin for MyPin {
+///    fn set_low(&mut self) {
+///        self.state = false;
+///    }
+///    fn set_high(&mut self) {
+///        self.state = true;
+///    }
+/// }
+///
+/// impl StatefulOutputPin for MyPin {
+///    fn is_set_low(&self) -> bool {
+///        !self.state
+///    }
+///    fn is_set_high(&self) -> bool {
+///        self.state
+///    }
+/// }
+///
+/// /// Opt-in to the software implementation.
+/// impl toggleable::Default for MyPin {}
+///
+/// let mut pin = MyPin { state: false };
+/// pin.toggle();
+/// assert!(pin.is_set_high());
+/// pin.toggle();
+/// assert!(pin.is_set_low());
+/// ```
+#[cfg(feature = "digital-unproven")]
+pub mod toggleable {
+    use super::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+    /// Software-driven `toggle()` implementation.
+    ///
+    /// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+    pub trait Default: OutputPin + StatefulOutputPin {}
+
+    impl<P> ToggleableOutputPin for P
+    where
+        P: Default,
+    {
+        /// Toggle pin output
+        fn toggle(&mut self) {
+            if self.is_set_low() {
+                self.set_high();
+            } else {
+                self.set_low();
+            }
+        }
+    }
+}
+
+/// Single digital input pin
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+#[cfg(feature = "digital-unproven")]
+pub trait InputPin {
+    /// Is the input pin high?
+    fn is_high(&self) -> bool;
+
+    /// Is the input pin low?
+    fn is_low(&self) -> bool;
+}
+
+#[cfg(all(feature = "alloc", feature = "digital-unproven"))]
+impl<T> InputPin for Box<T>
+where
+    T: InputPin + ?Sized,
+{
+    fn is_high(&self) -> bool {
+        (**self).is_high()
+    }
+
+    fn is_low(&self) -> bool {
+        (**self).is_low()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> InputPin for &mut T
+where
+    T: InputPin + ?Sized,
+{
+    fn is_high(&self) -> bool {
+        (**self).is_high()
+    }
+
+    fn is_low(&self) -> bool {
+        (**self).is_low()
+    }
+}
+
+/// Waiting for the level of an input pin, or for edges on it
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// This lets drivers wait for e.g. a data-ready or interrupt line without polling `is_high`, and
+/// lets the caller choose how to wait: with `block!`, or with `futures::adapter::FromNb`, which
+/// provides `futures::digital::Wait` for the implementers that also implement `RegisterWaker`.
+///
+/// # Contract
+///
+/// - `wait_for_high` and `wait_for_low` return `Ok` if the pin is at the awaited level.
+/// - Each edge method returns `Ok` once per edge that occurred since the edge detection was
+///   armed, which the first call to that method does, or since it last returned `Ok`. Several
+///   edges that occurred between two calls are reported as one.
+/// - Calling another edge method re-arms the edge detection: the edges that occurred before are
+///   forgotten.
+///
+/// # Implementing
+///
+/// Implementations are usually backed by the external interrupt controller of the device, e.g.
+/// the EXTI of an STM32:
+///
+/// - The first call to an edge method selects the trigger of the interrupt line, clears its
+///   pending flag and returns `WouldBlock`.
+/// - The following calls return `WouldBlock` until the pending flag is set, and then clear it and
+///   return `Ok`.
+/// - The interrupt itself only needs to be enabled to wake the CPU or an async task; the level
+///   methods can read the input register.
+///
+/// # Examples
+///
+/// Reading a sample when a sensor signals that it's ready
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::digital::Wait;
+///
+/// fn wait_until_ready<P>(data_ready: &mut P)
+/// where
+///     P: Wait,
+/// {
+///     // the sensor pulls the line low while a sample is available
+///     block!(data_ready.wait_for_low()).unwrap();
+/// }
+///
+/// fn main() {
+///     let mut drdy: Pb0 = {
+///         // ..
+/// #       Pb0 { polls: 0 }
+///     };
+///
+///     wait_until_ready(&mut drdy);
+/// #   assert_eq!(drdy.polls, 3);
+/// }
+///
+/// # extern crate void;
+/// # use hal::digital::InputPin;
+/// # use void::Void;
+/// # struct Pb0 { polls: u32 }
+/// # impl InputPin for Pb0 {
+/// #     fn is_high(&self) -> bool { self.polls < 3 }
+/// #     fn is_low(&self) -> bool { self.polls >= 3 }
+/// # }
+/// # impl Wait for Pb0 {
+/// #     fn wait_for_high(&mut self) -> nb::Result<(), Void> { unimplemented!() }
+/// #     fn wait_for_low(&mut self) -> nb::Result<(), Void> {
+/// #         self.polls += 1;
+/// #         if self.is_low() { Ok(()) } else { Err(nb::Error::WouldBlock) }
+/// #     }
+/// #     fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void> { unimplemented!() }
+/// #     fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void> { unimplemented!() }
+/// #     fn wait_for_any_edge(&mut self) -> nb::Result<(), Void> { unimplemented!() }
+/// # }
+/// ```
+#[cfg(feature = "digital-unproven")]
+pub trait Wait: InputPin {
+    /// Returns `Ok` if the pin is high
+    fn wait_for_high(&mut self) -> nb::Result<(), Void>;
+
+    /// Returns `Ok` if the pin is low
+    fn wait_for_low(&mut self) -> nb::Result<(), Void>;
+
+    /// Returns `Ok` once a transition from low to high occurred
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void>;
+
+    /// Returns `Ok` once a transition from high to low occurred
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void>;
+
+    /// Returns `Ok` once a transition, in either direction, occurred
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Void>;
+}
+
+#[cfg(all(feature = "alloc", feature = "digital-unproven"))]
+impl<T> Wait for Box<T>
+where
+    T: Wait + ?Sized,
+{
+    fn wait_for_high(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_high()
+    }
+
+    fn wait_for_low(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_low()
+    }
+
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_rising_edge()
+    }
+
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_falling_edge()
+    }
+
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_any_edge()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> Wait for &mut T
+where
+    T: Wait + ?Sized,
+{
+    fn wait_for_high(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_high()
+    }
+
+    fn wait_for_low(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_low()
+    }
+
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_rising_edge()
+    }
+
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_falling_edge()
+    }
+
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Void> {
+        (**self).wait_for_any_edge()
+    }
+}
@@ -0,0 +1,196 @@
+//! Wrappers that implement the infallible traits for the implementers of the fallible ones
+//!
+//! [`OldOutputPin`] and [`OldInputPin`] let implementations of the [`v2`](../v2/index.html) traits
+//! be used with drivers written against the traits of the [parent module](../index.html). They
+//! *panic* on errors.
+//!
+//! [`OldOutputPin`]: struct.OldOutputPin.html
+//! [`OldInputPin`]: struct.OldInputPin.html
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::digital::v1_compat::OldOutputPin;
+//! use hal::digital::{v2, ErrorKind, OutputPin};
+//!
+//! /// A driver written against the infallible traits
+//! struct Led<P> {
+//!     pin: P,
+//! }
+//!
+//! impl<P> Led<P>
+//! where
+//!     P: OutputPin,
+//! {
+//!     fn on(&mut self) {
+//!         self.pin.set_high()
+//!     }
+//! }
+//!
+//! /// A pin of a GPIO expander
+//! struct Gpb0 {
+//!     high: bool,
+//! }
+//!
+//! impl v2::OutputPin for Gpb0 {
+//!     type Error = ErrorKind;
+//!
+//!     fn set_low(&mut self) -> Result<(), ErrorKind> {
+//!         self.high = false;
+//!         Ok(())
+//!     }
+//!     fn set_high(&mut self) -> Result<(), ErrorKind> {
+//!         self.high = true;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut led = Led {
+//!         pin: OldOutputPin::new(Gpb0 { high: false }),
+//!     };
+//!     led.on();
+//!     assert!(led.pin.into_inner().high);
+//! }
+//! ```
+
+use super::v2;
+use super::{OutputPin, PinState};
+#[cfg(feature = "digital-unproven")]
+use super::{InputPin, StatefulOutputPin};
+
+/// Implementation of the infallible output traits for a fallible output pin
+///
+/// The operations *panic* if the pin returns an error.
+#[derive(Debug)]
+pub struct OldOutputPin<T> {
+    pin: T,
+}
+
+impl<T> OldOutputPin<T>
+where
+    T: v2::OutputPin,
+{
+    /// Wraps `pin`
+    pub fn new(pin: T) -> Self {
+        OldOutputPin { pin }
+    }
+
+    /// Returns a reference to the wrapped pin
+    pub fn inner(&self) -> &T {
+        &self.pin
+    }
+
+    /// Returns a mutable reference to the wrapped pin
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.pin
+    }
+
+    /// Returns the wrapped pin
+    pub fn into_inner(self) -> T {
+        self.pin
+    }
+}
+
+impl<T> From<T> for OldOutputPin<T>
+where
+    T: v2::OutputPin,
+{
+    fn from(pin: T) -> Self {
+        OldOutputPin::new(pin)
+    }
+}
+
+impl<T> OutputPin for OldOutputPin<T>
+where
+    T: v2::OutputPin,
+{
+    fn set_low(&mut self) {
+        self.pin.set_low().unwrap()
+    }
+
+    fn set_high(&mut self) {
+        self.pin.set_high().unwrap()
+    }
+
+    fn set_state(&mut self, state: PinState) {
+        self.pin.set_state(state).unwrap()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> StatefulOutputPin for OldOutputPin<T>
+where
+    T: v2::StatefulOutputPin,
+{
+    fn is_set_high(&self) -> bool {
+        self.pin.is_set_high().unwrap()
+    }
+
+    fn is_set_low(&self) -> bool {
+        self.pin.is_set_low().unwrap()
+    }
+}
+
+/// Implementation of the infallible input trait for a fallible input pin
+///
+/// *This type is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// The operations *panic* if the pin returns an error.
+#[cfg(feature = "digital-unproven")]
+#[derive(Debug)]
+pub struct OldInputPin<T> {
+    pin: T,
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> OldInputPin<T>
+where
+    T: v2::InputPin,
+{
+    /// Wraps `pin`
+    pub fn new(pin: T) -> Self {
+        OldInputPin { pin }
+    }
+
+    /// Returns a reference to the wrapped pin
+    pub fn inner(&self) -> &T {
+        &self.pin
+    }
+
+    /// Returns a mutable reference to the wrapped pin
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.pin
+    }
+
+    /// Returns the wrapped pin
+    pub fn into_inner(self) -> T {
+        self.pin
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> From<T> for OldInputPin<T>
+where
+    T: v2::InputPin,
+{
+    fn from(pin: T) -> Self {
+        OldInputPin::new(pin)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> InputPin for OldInputPin<T>
+where
+    T: v2::InputPin,
+{
+    fn is_high(&self) -> bool {
+        self.pin.is_high().unwrap()
+    }
+
+    fn is_low(&self) -> bool {
+        self.pin.is_low().unwrap()
+    }
+}
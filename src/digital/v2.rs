@@ -0,0 +1,335 @@
+//! Fallible digital I/O
+//!
+//! These traits mirror the ones of the [parent module](../index.html), but every operation returns
+//! a `Result`, e.g. for the pins of an I2C GPIO expander. [`IoPin`], which switches a pin between
+//! input and output at runtime, only exists in this module.
+//!
+//! `Box`es of, and mutable references to, pins that implement the infallible traits implement
+//! these ones through [`v2_compat`]. A pin that only implements these traits, e.g. a pin of an
+//! expander, implements them for `&mut` references to itself too, so that drivers can be given a
+//! borrow of it: the blanket implementations of [`v2_compat`] leave that to the pin.
+//!
+//! [`v2_compat`]: ../v2_compat/index.html
+//! [`IoPin`]: trait.IoPin.html
+//!
+//! # Examples
+//!
+//! A driver that works with both the pins of the microcontroller and the pins of an expander
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::digital::v2::OutputPin;
+//!
+//! /// Pulses the chip enable line of a radio
+//! fn pulse<P>(ce: &mut P) -> Result<(), P::Error>
+//! where
+//!     P: OutputPin,
+//! {
+//!     ce.set_high()?;
+//!     // ..
+//!     ce.set_low()
+//! }
+//!
+//! fn main() {
+//!     let (mut pa1, mut gpb0): (Pa1, Gpb0) = {
+//!         // ..
+//! #       (Pa1, Gpb0)
+//!     };
+//!
+//!     // a pin of the microcontroller, which can't fail
+//!     pulse(&mut pa1).unwrap();
+//!     // a pin of a MCP23017 whose bus is disconnected
+//!     assert!(pulse(&mut gpb0).is_err());
+//! }
+//!
+//! # use hal::digital::ErrorKind;
+//! # struct Pa1;
+//! # impl hal::digital::OutputPin for Pa1 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+//! # struct Gpb0;
+//! # impl OutputPin for Gpb0 {
+//! #     type Error = ErrorKind;
+//! #     fn set_low(&mut self) -> Result<(), ErrorKind> { Err(ErrorKind::Other) }
+//! #     fn set_high(&mut self) -> Result<(), ErrorKind> { Err(ErrorKind::Other) }
+//! # }
+//! ```
+
+use super::{Error, PinState};
+#[cfg(feature = "digital-unproven")]
+use nb;
+
+/// Single digital push-pull output pin
+pub trait OutputPin {
+    /// Error type
+    type Error: Error;
+
+    /// Drives the pin low
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be low, e.g. due to external
+    /// electrical sources
+    fn set_low(&mut self) -> Result<(), Self::Error>;
+
+    /// Drives the pin high
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be high, e.g. due to external
+    /// electrical sources
+    fn set_high(&mut self) -> Result<(), Self::Error>;
+
+    /// Drives the pin to `state`
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be `state`, e.g. due to
+    /// external electrical sources
+    fn set_state(&mut self, state: PinState) -> Result<(), Self::Error> {
+        match state {
+            PinState::Low => self.set_low(),
+            PinState::High => self.set_high(),
+        }
+    }
+}
+
+/// Push-pull output pin that can read its output state
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+#[cfg(feature = "digital-unproven")]
+pub trait StatefulOutputPin: OutputPin {
+    /// Is the pin in drive high mode?
+    ///
+    /// *NOTE* this does *not* read the electrical state of the pin
+    fn is_set_high(&self) -> Result<bool, Self::Error>;
+
+    /// Is the pin in drive low mode?
+    ///
+    /// *NOTE* this does *not* read the electrical state of the pin
+    fn is_set_low(&self) -> Result<bool, Self::Error>;
+}
+
+/// Output pin that can be toggled
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// See [toggleable](toggleable/index.html) to use a software implementation if both
+/// [OutputPin](trait.OutputPin.html) and [StatefulOutputPin](trait.StatefulOutputPin.html) are
+/// implemented. Otherwise, implement this using hardware mechanisms.
+#[cfg(feature = "digital-unproven")]
+pub trait ToggleableOutputPin {
+    /// Error type
+    type Error: Error;
+
+    /// Toggle pin output.
+    fn toggle(&mut self) -> Result<(), Self::Error>;
+}
+
+/// If you can read **and** write the output state, a pin is
+/// toggleable by software.
+///
+/// ```
+/// use embedded_hal::digital::ErrorKind;
+/// use embedded_hal::digital::v2::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+/// use embedded_hal::digital::v2::toggleable;
+///
+/// /// A virtual output pin that exists purely in software
+/// struct MyPin {
+///     state: bool
+/// }
+///
+/// impl OutputPin for MyPin {
+///    type Error = ErrorKind;
+///
+///    fn set_low(&mut self) -> Result<(), ErrorKind> {
+///        self.state = false;
+///        Ok(())
+///    }
+///    fn set_high(&mut self) -> Result<(), ErrorKind> {
+///        self.state = true;
+///        Ok(())
+///    }
+/// }
+///
+/// impl StatefulOutputPin for MyPin {
+///    fn is_set_low(&self) -> Result<bool, ErrorKind> {
+///        Ok(!self.state)
+///    }
+///    fn is_set_high(&self) -> Result<bool, ErrorKind> {
+///        Ok(self.state)
+///    }
+/// }
+///
+/// /// Opt-in to the software implementation.
+/// impl toggleable::Default for MyPin {}
+///
+/// let mut pin = MyPin { state: false };
+/// pin.toggle().unwrap();
+/// assert!(pin.is_set_high().unwrap());
+/// pin.toggle().unwrap();
+/// assert!(pin.is_set_low().unwrap());
+/// ```
+#[cfg(feature = "digital-unproven")]
+pub mod toggleable {
+    use super::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+    /// Software-driven `toggle()` implementation.
+    ///
+    /// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+    pub trait Default: OutputPin + StatefulOutputPin {}
+
+    impl<P> ToggleableOutputPin for P
+    where
+        P: Default,
+    {
+        type Error = P::Error;
+
+        /// Toggle pin output
+        fn toggle(&mut self) -> Result<(), Self::Error> {
+            if self.is_set_low()? {
+                self.set_high()
+            } else {
+                self.set_low()
+            }
+        }
+    }
+}
+
+/// Single digital input pin
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+#[cfg(feature = "digital-unproven")]
+pub trait InputPin {
+    /// Error type
+    type Error: Error;
+
+    /// Is the input pin high?
+    fn is_high(&self) -> Result<bool, Self::Error>;
+
+    /// Is the input pin low?
+    fn is_low(&self) -> Result<bool, Self::Error>;
+}
+
+/// Waiting for the level of an input pin, or for edges on it
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// See [the infallible version](../trait.Wait.html) for the contract.
+#[cfg(feature = "digital-unproven")]
+pub trait Wait: InputPin {
+    /// Returns `Ok` if the pin is high
+    fn wait_for_high(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Returns `Ok` if the pin is low
+    fn wait_for_low(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Returns `Ok` once a transition from low to high occurred
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Returns `Ok` once a transition from high to low occurred
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Returns `Ok` once a transition, in either direction, occurred
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Self::Error>;
+}
+
+/// Pin that can be switched between input and output at runtime
+///
+/// *This trait is available if embedded-hal is built with the `"digital-unproven"` feature.*
+///
+/// It's meant for the single-wire protocols where the same line alternates direction in the
+/// middle of a transaction, e.g. DHT11/DHT22 sensors, 1-Wire or SWD. The conversions consume the
+/// pin, so HALs can represent each mode with a type of its own; `Input` and `Output` can also be
+/// the same type, e.g. if the direction is only known at runtime.
+///
+/// Converting a pin into the mode it's already in is allowed: `into_input_pin` then gives the pin
+/// back as is, and `into_output_pin` drives it to the requested level. If a conversion fails, the
+/// pin is lost: HALs SHOULD only fail if the pin can't be used anymore, e.g. because the bus to a
+/// GPIO expander is gone.
+///
+/// # Examples
+///
+/// Sending the start signal of a DHT22 and checking that it answers
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::delay::DelayUs;
+/// use hal::digital::v2::{InputPin, IoPin};
+/// use hal::digital::{Error as _, ErrorKind, PinState};
+///
+/// /// Gives the pin back in output mode, driven high, the idle state of the line
+/// fn start<P, D>(pin: P, delay: &mut D) -> Result<(P::Output, bool), ErrorKind>
+/// where
+///     P: IoPin,
+///     D: DelayUs<u16>,
+/// {
+///     // the host pulls the line low for at least 1 ms, then releases it
+///     let pin = pin.into_output_pin(PinState::Low).map_err(|e| e.kind())?;
+///     delay.delay_us(1_000);
+///     let pin = pin.into_input_pin().map_err(|e| e.kind())?;
+///
+///     // and the sensor answers by pulling it low in turn
+///     delay.delay_us(40);
+///     let present = pin.is_low().map_err(|e| e.kind())?;
+///
+///     let pin = pin.into_output_pin(PinState::High).map_err(|e| e.kind())?;
+///     Ok((pin, present))
+/// }
+///
+/// fn main() {
+///     let (pd2, mut delay): (Pd2<Output>, Delay) = {
+///         // ..
+/// #       (Pd2(Output), Delay)
+///     };
+///
+///     let (_pd2, present) = start(pd2, &mut delay).unwrap();
+///     assert!(present);
+/// }
+///
+/// # use hal::digital::v2::OutputPin;
+/// # struct Input;
+/// # struct Output;
+/// # struct Pd2<MODE>(MODE);
+/// # impl IoPin for Pd2<Input> {
+/// #     type Input = Pd2<Input>;
+/// #     type Output = Pd2<Output>;
+/// #     type Error = ErrorKind;
+/// #     fn into_input_pin(self) -> Result<Pd2<Input>, ErrorKind> { Ok(self) }
+/// #     fn into_output_pin(self, _: PinState) -> Result<Pd2<Output>, ErrorKind> {
+/// #         Ok(Pd2(Output))
+/// #     }
+/// # }
+/// # impl IoPin for Pd2<Output> {
+/// #     type Input = Pd2<Input>;
+/// #     type Output = Pd2<Output>;
+/// #     type Error = ErrorKind;
+/// #     fn into_input_pin(self) -> Result<Pd2<Input>, ErrorKind> { Ok(Pd2(Input)) }
+/// #     fn into_output_pin(self, _: PinState) -> Result<Pd2<Output>, ErrorKind> { Ok(self) }
+/// # }
+/// # impl InputPin for Pd2<Input> {
+/// #     type Error = ErrorKind;
+/// #     fn is_high(&self) -> Result<bool, ErrorKind> { Ok(false) }
+/// #     fn is_low(&self) -> Result<bool, ErrorKind> { Ok(true) }
+/// # }
+/// # impl OutputPin for Pd2<Output> {
+/// #     type Error = ErrorKind;
+/// #     fn set_low(&mut self) -> Result<(), ErrorKind> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// # struct Delay;
+/// # impl DelayUs<u16> for Delay { fn delay_us(&mut self, _: u16) {} }
+/// ```
+#[cfg(feature = "digital-unproven")]
+pub trait IoPin: Sized {
+    /// The pin in input mode
+    type Input: InputPin + IoPin<Input = Self::Input, Output = Self::Output, Error = Self::Error>;
+
+    /// The pin in output mode
+    type Output: OutputPin + IoPin<Input = Self::Input, Output = Self::Output, Error = Self::Error>;
+
+    /// Error type
+    type Error: Error;
+
+    /// Switches the pin to input mode
+    fn into_input_pin(self) -> Result<Self::Input, Self::Error>;
+
+    /// Switches the pin to output mode, driving it to `state`
+    ///
+    /// The pin MUST NOT glitch to the opposite level on the way.
+    fn into_output_pin(self, state: PinState) -> Result<Self::Output, Self::Error>;
+}
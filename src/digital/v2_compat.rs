@@ -0,0 +1,132 @@
+//! Implementation of the fallible traits for the implementers of the infallible ones
+//!
+//! Every implementer of the traits of the [parent module](../index.html) implements the
+//! corresponding [`v2`](../v2/index.html) trait, with a `Void` error, so that it can be used with
+//! drivers written against the fallible traits.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::digital::{v2, OutputPin};
+//!
+//! /// A driver written against the fallible traits
+//! fn blink<P>(led: &mut P) -> Result<(), P::Error>
+//! where
+//!     P: v2::OutputPin,
+//! {
+//!     led.set_high()?;
+//!     led.set_low()
+//! }
+//!
+//! /// A pin of a HAL that implements the infallible traits
+//! struct Led {
+//!     on: bool,
+//! }
+//!
+//! impl OutputPin for Led {
+//!     fn set_low(&mut self) {
+//!         self.on = false;
+//!     }
+//!     fn set_high(&mut self) {
+//!         self.on = true;
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut led = Led { on: false };
+//!     blink(&mut led).unwrap();
+//!     assert!(!led.on);
+//! }
+//! ```
+
+#[cfg(feature = "digital-unproven")]
+use nb;
+use void::Void;
+
+use super::v2;
+use super::{OutputPin, PinState};
+#[cfg(feature = "digital-unproven")]
+use super::{toggleable, InputPin, StatefulOutputPin, Wait};
+
+impl<T> v2::OutputPin for T
+where
+    T: OutputPin + ?Sized,
+{
+    type Error = Void;
+
+    fn set_low(&mut self) -> Result<(), Void> {
+        OutputPin::set_low(self);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Void> {
+        OutputPin::set_high(self);
+        Ok(())
+    }
+
+    fn set_state(&mut self, state: PinState) -> Result<(), Void> {
+        OutputPin::set_state(self, state);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v2::StatefulOutputPin for T
+where
+    T: StatefulOutputPin + OutputPin + ?Sized,
+{
+    fn is_set_high(&self) -> Result<bool, Void> {
+        Ok(StatefulOutputPin::is_set_high(self))
+    }
+
+    fn is_set_low(&self) -> Result<bool, Void> {
+        Ok(StatefulOutputPin::is_set_low(self))
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v2::toggleable::Default for T where T: toggleable::Default {}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v2::InputPin for T
+where
+    T: InputPin + ?Sized,
+{
+    type Error = Void;
+
+    fn is_high(&self) -> Result<bool, Void> {
+        Ok(InputPin::is_high(self))
+    }
+
+    fn is_low(&self) -> Result<bool, Void> {
+        Ok(InputPin::is_low(self))
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> v2::Wait for T
+where
+    T: Wait + ?Sized,
+{
+    fn wait_for_high(&mut self) -> nb::Result<(), Void> {
+        Wait::wait_for_high(self)
+    }
+
+    fn wait_for_low(&mut self) -> nb::Result<(), Void> {
+        Wait::wait_for_low(self)
+    }
+
+    fn wait_for_rising_edge(&mut self) -> nb::Result<(), Void> {
+        Wait::wait_for_rising_edge(self)
+    }
+
+    fn wait_for_falling_edge(&mut self) -> nb::Result<(), Void> {
+        Wait::wait_for_falling_edge(self)
+    }
+
+    fn wait_for_any_edge(&mut self) -> nb::Result<(), Void> {
+        Wait::wait_for_any_edge(self)
+    }
+}
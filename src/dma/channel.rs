@@ -0,0 +1,188 @@
+//! DMA channel allocation
+//!
+//! DMA channels are a scarce resource shared by all the peripherals of a device. Instead of
+//! hard-coding a channel, a portable driver can take an [`Allocator`] from the application,
+//! request a channel that meets its needs and fall back to CPU transfers if none is available.
+//!
+//! [`Allocator`]: trait.Allocator.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// Direction of the transfers a channel will be used for
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Direction {
+    /// From memory to a peripheral, e.g. transmission
+    MemoryToPeripheral,
+    /// From a peripheral to memory, e.g. reception
+    PeripheralToMemory,
+    /// From memory to memory
+    MemoryToMemory,
+}
+
+/// Size of the words a channel will transfer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum WordSize {
+    /// 8-bit words
+    _8Bit,
+    /// 16-bit words
+    _16Bit,
+    /// 32-bit words
+    _32Bit,
+}
+
+/// Capabilities a channel must have
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Requirements {
+    /// Direction of the transfers
+    pub direction: Direction,
+    /// Size of the words
+    pub word_size: WordSize,
+    /// Whether the channel must support circular mode
+    pub circular: bool,
+    /// Whether the channel must support scatter-gather (linked-list) transfers
+    pub scatter_gather: bool,
+}
+
+/// Hands out DMA channels
+///
+/// `R` identifies the peripheral request (e.g. a DMAMUX input or a fixed request line) the
+/// channel will be triggered by; its type is device specific. Allocators for memory-to-memory
+/// channels use `()`.
+///
+/// # Contract
+///
+/// - `request` MUST return an error if no free channel can be connected to `request` or meets the
+///   `requirements`.
+/// - A channel MUST NOT be handed out again until it has been `release`d.
+///
+/// # Examples
+///
+/// A display driver that uses DMA for frame transfers if the application lets it
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::channel::{Allocator, Direction, Requirements, WordSize};
+///
+/// struct Display<C> {
+///     channel: Option<C>,
+/// }
+///
+/// impl<C> Display<C> {
+///     fn new<A, R>(dma: &mut A, request: R) -> Self
+///     where
+///         A: Allocator<R, Channel = C>,
+///     {
+///         let requirements = Requirements {
+///             direction: Direction::MemoryToPeripheral,
+///             word_size: WordSize::_16Bit,
+///             circular: false,
+///             scatter_gather: false,
+///         };
+///
+///         Display {
+///             // no channel: frames will be sent by the CPU
+///             channel: dma.request(request, requirements).ok(),
+///         }
+///     }
+///
+///     fn release<A, R>(self, dma: &mut A)
+///     where
+///         A: Allocator<R, Channel = C>,
+///     {
+///         if let Some(channel) = self.channel {
+///             dma.release(channel);
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let mut dma: Dma = {
+///         // ..
+/// #       Dma { free: 1 }
+///     };
+///
+///     let display = Display::new(&mut dma, SpiTx);
+///     assert!(display.channel.is_some());
+///     assert!(Display::new(&mut dma, SpiTx).channel.is_none());
+///
+///     display.release(&mut dma);
+///     assert!(Display::new(&mut dma, SpiTx).channel.is_some());
+/// }
+///
+/// # struct SpiTx;
+/// # struct Dma { free: u8 }
+/// # struct Channel;
+/// # impl Allocator<SpiTx> for Dma {
+/// #     type Channel = Channel;
+/// #     type Error = ();
+/// #     fn request(&mut self, _: SpiTx, _: Requirements) -> Result<Channel, ()> {
+/// #         if self.free == 0 { return Err(()) }
+/// #         self.free -= 1;
+/// #         Ok(Channel)
+/// #     }
+/// #     fn release(&mut self, _: Channel) { self.free += 1 }
+/// # }
+/// ```
+pub trait Allocator<R> {
+    /// A DMA channel, configured for the peripheral request
+    type Channel;
+
+    /// Error returned when no suitable channel is available
+    type Error;
+
+    /// Requests a channel that meets the `requirements` and is triggered by `request`
+    fn request(
+        &mut self,
+        request: R,
+        requirements: Requirements,
+    ) -> Result<Self::Channel, Self::Error>;
+
+    /// Gives back a channel, so it can be handed out again
+    fn release(&mut self, channel: Self::Channel);
+}
+
+#[cfg(feature = "alloc")]
+impl<T, R> Allocator<R> for Box<T>
+where
+    T: Allocator<R> + ?Sized,
+{
+    type Channel = T::Channel;
+    type Error = T::Error;
+
+    fn request(
+        &mut self,
+        request: R,
+        requirements: Requirements,
+    ) -> Result<Self::Channel, Self::Error> {
+        (**self).request(request, requirements)
+    }
+
+    fn release(&mut self, channel: Self::Channel) {
+        (**self).release(channel)
+    }
+}
+
+impl<T, R> Allocator<R> for &mut T
+where
+    T: Allocator<R> + ?Sized,
+{
+    type Channel = T::Channel;
+    type Error = T::Error;
+
+    fn request(
+        &mut self,
+        request: R,
+        requirements: Requirements,
+    ) -> Result<Self::Channel, Self::Error> {
+        (**self).request(request, requirements)
+    }
+
+    fn release(&mut self, channel: Self::Channel) {
+        (**self).release(channel)
+    }
+}
@@ -0,0 +1,197 @@
+//! Circular DMA
+//!
+//! In circular mode the DMA loops over a buffer until it's stopped, which is how continuous
+//! streams, like ADC samples or audio, are moved without gaps. The buffer is split in two halves:
+//! while the hardware fills (or drains) one half, the program processes the other one. The
+//! hardware signals when it's done with a half (the *half-transfer* and *transfer-complete*
+//! events) and moves on to the other one.
+//!
+//! If the program takes longer to process a half than the hardware takes to transfer the other
+//! one, data is lost; this is reported as an error by the traits in this module.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use nb;
+
+use dma::Word;
+
+/// A half of a circular buffer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Half {
+    /// The first half
+    First,
+    /// The second half
+    Second,
+}
+
+/// Continuous reception into a circular buffer
+///
+/// The error type reports *overruns*: the hardware wrote to a half before the program was done
+/// with it.
+///
+/// # Examples
+///
+/// Averaging blocks of ADC samples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::dma::circular::CircularRead;
+///
+/// fn average<C>(samples: &mut C) -> Result<u16, C::Error>
+/// where
+///     C: CircularRead<Word = u16>,
+/// {
+///     block!(samples.readable_half())?;
+///     samples.peek(|half, _| {
+///         let sum: u32 = half.iter().map(|s| u32::from(*s)).sum();
+///         (sum / half.len() as u32) as u16
+///     })
+/// }
+///
+/// fn main() {
+///     let mut samples: Adc = {
+///         // adc.read_circular(buffer)
+/// #       Adc { buffer: [10, 20, 30, 40, 50, 60, 70, 80], writing: Half::First }
+///     };
+///
+///     assert_eq!(average(&mut samples).unwrap(), 25);
+///     assert_eq!(average(&mut samples).unwrap(), 65);
+/// }
+///
+/// # use hal::dma::circular::Half;
+/// # struct Adc { buffer: [u16; 8], writing: Half }
+/// # impl CircularRead for Adc {
+/// #     type Word = u16;
+/// #     type Error = ();
+/// #     type Payload = [u16; 8];
+/// #     fn readable_half(&mut self) -> nb::Result<Half, ()> {
+/// #         let done = self.writing;
+/// #         self.writing = if done == Half::First { Half::Second } else { Half::First };
+/// #         Ok(done)
+/// #     }
+/// #     fn peek<R, F>(&mut self, f: F) -> Result<R, ()>
+/// #     where
+/// #         F: FnOnce(&[u16], Half) -> R,
+/// #     {
+/// #         Ok(match self.writing {
+/// #             Half::First => f(&self.buffer[4..], Half::Second),
+/// #             Half::Second => f(&self.buffer[..4], Half::First),
+/// #         })
+/// #     }
+/// #     fn stop(self) -> [u16; 8] { self.buffer }
+/// # }
+/// ```
+pub trait CircularRead {
+    /// Word the buffer is made of
+    type Word: Word;
+
+    /// Overrun error
+    type Error;
+
+    /// Resources given back when the transfer is stopped
+    type Payload;
+
+    /// Waits for the hardware to be done filling a half, and returns that half
+    ///
+    /// Returns an error if the hardware has completed more than one half since the last call.
+    fn readable_half(&mut self) -> nb::Result<Half, Self::Error>;
+
+    /// Calls `f` with the half of the buffer the hardware is *not* writing to, and which half it
+    /// is
+    ///
+    /// Returns an error if the hardware started writing to that half before `f` returned, in
+    /// which case the data `f` saw may have been partially overwritten.
+    fn peek<R, F>(&mut self, f: F) -> Result<R, Self::Error>
+    where
+        F: FnOnce(&[Self::Word], Half) -> R;
+
+    /// Stops the transfer and gives back its resources
+    fn stop(self) -> Self::Payload;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CircularRead for Box<T>
+where
+    T: CircularRead,
+{
+    type Word = T::Word;
+    type Error = T::Error;
+    type Payload = T::Payload;
+
+    fn readable_half(&mut self) -> nb::Result<Half, Self::Error> {
+        (**self).readable_half()
+    }
+
+    fn peek<R, F>(&mut self, f: F) -> Result<R, Self::Error>
+    where
+        F: FnOnce(&[Self::Word], Half) -> R,
+    {
+        (**self).peek(f)
+    }
+
+    fn stop(self) -> Self::Payload {
+        (*self).stop()
+    }
+}
+
+/// Continuous transmission from a circular buffer
+///
+/// The error type reports *underruns*: the hardware read a half before the program was done
+/// refilling it.
+pub trait CircularWrite {
+    /// Word the buffer is made of
+    type Word: Word;
+
+    /// Underrun error
+    type Error;
+
+    /// Resources given back when the transfer is stopped
+    type Payload;
+
+    /// Waits for the hardware to be done draining a half, and returns that half
+    ///
+    /// Returns an error if the hardware has completed more than one half since the last call.
+    fn writable_half(&mut self) -> nb::Result<Half, Self::Error>;
+
+    /// Calls `f` with the half of the buffer the hardware is *not* reading from, so it can be
+    /// refilled, and which half it is
+    ///
+    /// Returns an error if the hardware started reading from that half before `f` returned, in
+    /// which case the hardware may have sent partially refilled data.
+    fn fill<R, F>(&mut self, f: F) -> Result<R, Self::Error>
+    where
+        F: FnOnce(&mut [Self::Word], Half) -> R;
+
+    /// Stops the transfer and gives back its resources
+    fn stop(self) -> Self::Payload;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CircularWrite for Box<T>
+where
+    T: CircularWrite,
+{
+    type Word = T::Word;
+    type Error = T::Error;
+    type Payload = T::Payload;
+
+    fn writable_half(&mut self) -> nb::Result<Half, Self::Error> {
+        (**self).writable_half()
+    }
+
+    fn fill<R, F>(&mut self, f: F) -> Result<R, Self::Error>
+    where
+        F: FnOnce(&mut [Self::Word], Half) -> R,
+    {
+        (**self).fill(f)
+    }
+
+    fn stop(self) -> Self::Payload {
+        (*self).stop()
+    }
+}
@@ -0,0 +1,157 @@
+//! Memory-to-memory DMA
+//!
+//! Large copies and fills, e.g. of frame buffers, can be offloaded to a DMA channel, freeing the
+//! CPU for other work. [`Software`] implements the same traits with the CPU, so code written
+//! against them also runs on devices, or channels, without memory-to-memory DMA.
+//!
+//! [`Software`]: struct.Software.html
+
+use core::{cmp, ptr};
+
+use void::Void;
+
+use dma::{ReadBuffer, Transfer, WriteBuffer};
+
+/// Copies memory to memory
+///
+/// # Examples
+///
+/// Restoring the background of a frame buffer
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::mem::{MemCopy, Software};
+/// use hal::dma::Transfer;
+///
+/// fn restore<C>(
+///     dma: C,
+///     background: &'static [u16],
+///     frame: &'static mut [u16],
+/// ) -> (C, &'static mut [u16])
+/// where
+///     C: MemCopy<&'static [u16], &'static mut [u16]>,
+/// {
+///     let transfer = dma.copy(background, frame);
+///     // (render the sprites' next positions in the meantime)
+///     let (dma, _, frame) = match transfer.wait() {
+///         Ok(payload) => payload,
+///         Err((_, payload)) => payload,
+///     };
+///     (dma, frame)
+/// }
+///
+/// fn main() {
+///     static BACKGROUND: [u16; 4] = [0x1234; 4];
+///     let frame: &'static mut [u16] = Box::leak(Box::new([0; 4]));
+///
+///     // no memory-to-memory DMA on this device
+///     let (_, frame) = restore(Software, &BACKGROUND, frame);
+///     assert_eq!(*frame, BACKGROUND);
+/// }
+/// ```
+pub trait MemCopy<S, D>: Sized
+where
+    S: ReadBuffer,
+    D: WriteBuffer<Word = S::Word>,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, S, D)>;
+
+    /// Starts copying `src` into `dst`
+    ///
+    /// If the buffers have different lengths, only the length of the shorter one is copied.
+    fn copy(self, src: S, dst: D) -> Self::Transfer;
+}
+
+/// Fills memory with a value
+pub trait MemFill<D>: Sized
+where
+    D: WriteBuffer,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, D)>;
+
+    /// Starts setting all the words of `dst` to `value`
+    fn fill(self, value: D::Word, dst: D) -> Self::Transfer;
+}
+
+/// CPU implementation of the memory-to-memory traits
+///
+/// The operations are done by the time `copy` or `fill` return.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Software;
+
+/// A transfer that's already over
+///
+/// It's returned by the CPU implementations of the DMA traits, e.g. [`Software`] or
+/// [`dma::spi::Blocking`](../spi/struct.Blocking.html), which do the work before returning it.
+///
+/// [`Software`]: struct.Software.html
+#[derive(Debug)]
+pub struct Done<P, E = Void> {
+    result: Result<P, (E, P)>,
+}
+
+impl<P, E> Done<P, E> {
+    pub(crate) fn new(result: Result<P, (E, P)>) -> Self {
+        Done { result }
+    }
+}
+
+impl<P, E> Transfer for Done<P, E> {
+    type Payload = P;
+    type Error = E;
+
+    fn is_done(&self) -> bool {
+        true
+    }
+
+    fn wait(self) -> Result<P, (E, P)> {
+        self.result
+    }
+
+    fn abort(self) -> P {
+        match self.result {
+            Ok(payload) | Err((_, payload)) => payload,
+        }
+    }
+}
+
+impl<S, D> MemCopy<S, D> for Software
+where
+    S: ReadBuffer,
+    D: WriteBuffer<Word = S::Word>,
+{
+    type Transfer = Done<(Self, S, D)>;
+
+    fn copy(self, src: S, mut dst: D) -> Self::Transfer {
+        unsafe {
+            let (from, n) = src.read_buffer();
+            let (to, m) = dst.write_buffer();
+            // the buffer traits don't rule out overlapping buffers
+            ptr::copy(from, to, cmp::min(n, m));
+        }
+
+        Done::new(Ok((self, src, dst)))
+    }
+}
+
+impl<D> MemFill<D> for Software
+where
+    D: WriteBuffer,
+{
+    type Transfer = Done<(Self, D)>;
+
+    fn fill(self, value: D::Word, mut dst: D) -> Self::Transfer {
+        unsafe {
+            let (to, n) = dst.write_buffer();
+            for i in 0..n {
+                ptr::write(to.add(i), value);
+            }
+        }
+
+        Done::new(Ok((self, dst)))
+    }
+}
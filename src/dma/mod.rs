@@ -0,0 +1,307 @@
+//! Direct Memory Access
+//!
+//! *This module is available if embedded-hal is built with the `"dma"` feature.*
+//!
+//! # Memory safety
+//!
+//! A DMA transfer reads or writes memory behind the compiler's back, potentially long after the
+//! function that started it has returned. The memory involved must therefore:
+//!
+//! - stay allocated until the transfer is over, i.e. not be freed nor go out of scope;
+//! - not move, e.g. it can't be a buffer that lives on the stack of the function that started the
+//!   transfer, or an array passed by value;
+//! - not be accessed by the program, other than through the DMA API, while the transfer is in
+//!   progress.
+//!
+//! The buffer traits in this module describe memory that meets the first two requirements. DMA
+//! APIs meet the third one by taking buffers *by value* and only giving them back once the
+//! transfer is over.
+//!
+//! Note that safe code can leak anything, including an in-progress transfer, with `mem::forget`.
+//! That's why the buffer traits are implemented for `'static` references but not, for example,
+//! for references to the stack: leaking a transfer that writes to a `&'static mut` buffer merely
+//! leaves the buffer borrowed forever.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+pub mod channel;
+pub mod circular;
+pub mod mem;
+pub mod scatter_gather;
+pub mod serial;
+pub mod spi;
+
+/// A word that DMA can transfer
+///
+/// # Safety
+///
+/// Every bit pattern of the size of the type must be a valid value of the type, because DMA
+/// writes whatever data the hardware produces.
+pub unsafe trait Word: Copy {}
+
+unsafe impl Word for u8 {}
+unsafe impl Word for u16 {}
+unsafe impl Word for u32 {}
+unsafe impl Word for i8 {}
+unsafe impl Word for i16 {}
+unsafe impl Word for i32 {}
+
+/// A buffer that DMA can read from, i.e. the source of a transfer
+///
+/// # Safety
+///
+/// - `read_buffer` MUST return a pointer to, and the length of, memory that's valid for reads for
+///   as long as `self` is alive, even if `self` is moved.
+/// - Calling `read_buffer` several times MUST return the same pointer and length.
+pub unsafe trait ReadBuffer {
+    /// Word the buffer is made of
+    type Word: Word;
+
+    /// Returns a pointer to the start of the buffer and its length, in words
+    ///
+    /// # Safety
+    ///
+    /// The memory MUST NOT be written to through the returned pointer.
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize);
+}
+
+/// A buffer that DMA can write to, i.e. the destination of a transfer
+///
+/// # Safety
+///
+/// - `write_buffer` MUST return a pointer to, and the length of, memory that's valid for reads
+///   and writes for as long as `self` is alive, even if `self` is moved.
+/// - Calling `write_buffer` several times MUST return the same pointer and length.
+///
+/// # Examples
+///
+/// A receive routine of some DMA controller
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::WriteBuffer;
+///
+/// /// Channel that moves data from a peripheral to memory
+/// struct RxChannel {
+///     // stand-ins for the memory address and count registers
+///     address: usize,
+///     count: usize,
+/// }
+///
+/// impl RxChannel {
+///     /// Starts filling `buffer`
+///     fn start<B>(&mut self, mut buffer: B) -> Rx<B>
+///     where
+///         B: WriteBuffer<Word = u8>,
+///     {
+///         let (ptr, len) = unsafe { buffer.write_buffer() };
+///         self.address = ptr as usize;
+///         self.count = len;
+///         // (enable the channel)
+///
+///         // the buffer is only given back once the transfer is over
+///         Rx { buffer }
+///     }
+/// }
+///
+/// struct Rx<B> {
+///     buffer: B,
+/// }
+///
+/// fn main() {
+///     let mut channel = RxChannel { address: 0, count: 0 };
+///
+///     let buffer: &'static mut [u8; 64] = Box::leak(Box::new([0; 64]));
+///     let rx = channel.start(buffer);
+///
+///     assert_eq!(channel.address, rx.buffer.as_ptr() as usize);
+///     assert_eq!(channel.count, 64);
+/// }
+/// ```
+pub unsafe trait WriteBuffer {
+    /// Word the buffer is made of
+    type Word: Word;
+
+    /// Returns a pointer to the start of the buffer and its length, in words
+    ///
+    /// # Safety
+    ///
+    /// The returned pointer MUST NOT be used once `self` has been dropped.
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize);
+}
+
+unsafe impl<W> ReadBuffer for &'static [W]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const W, usize) {
+        (self.as_ptr(), self.len())
+    }
+}
+
+unsafe impl<W> ReadBuffer for &'static mut [W]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const W, usize) {
+        (self.as_ptr(), self.len())
+    }
+}
+
+unsafe impl<W> WriteBuffer for &'static mut [W]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut W, usize) {
+        (self.as_mut_ptr(), self.len())
+    }
+}
+
+unsafe impl<W, const N: usize> ReadBuffer for &'static [W; N]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const W, usize) {
+        (self.as_ptr(), N)
+    }
+}
+
+unsafe impl<W, const N: usize> ReadBuffer for &'static mut [W; N]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn read_buffer(&self) -> (*const W, usize) {
+        (self.as_ptr(), N)
+    }
+}
+
+unsafe impl<W, const N: usize> WriteBuffer for &'static mut [W; N]
+where
+    W: Word,
+{
+    type Word = W;
+
+    unsafe fn write_buffer(&mut self) -> (*mut W, usize) {
+        (self.as_mut_ptr(), N)
+    }
+}
+
+/// An in-progress DMA transfer
+///
+/// Starting a transfer, through some peripheral-specific trait or method, moves the resources it
+/// uses (the buffers, and usually the peripheral or the DMA channel) into a `Transfer` handle,
+/// which gives them back as its `Payload` once the transfer is over.
+///
+/// # Contract
+///
+/// - `wait` and `abort` MUST NOT return until the hardware has stopped accessing the buffers. They
+///   MUST also issue a compiler fence (`core::sync::atomic::compiler_fence(Ordering::Acquire)` or
+///   stronger) so that the data written by the DMA is visible to the program.
+/// - Dropping a transfer that's not done MUST abort it, as the buffers in the payload are released
+///   by the drop.
+///
+/// # Examples
+///
+/// Doing some work while a transfer is in progress
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::Transfer;
+///
+/// fn checksum_while<T>(transfer: T, data: &[u8]) -> (u8, Result<T::Payload, T::Error>)
+/// where
+///     T: Transfer,
+/// {
+///     let mut sum = 0u8;
+///     for byte in data {
+///         sum = sum.wrapping_add(*byte);
+///     }
+///
+///     // check for completion once the work is done, rather than blocking right away
+///     if !transfer.is_done() {
+///         // ..
+///     }
+///
+///     (sum, transfer.wait().map_err(|(e, _payload)| e))
+/// }
+///
+/// fn main() {
+///     let buffer: &'static mut [u8] = Box::leak(Box::new([0; 4]));
+///     let transfer: Tx<&'static mut [u8]> = {
+///         // channel.write(buffer)
+/// #       Tx { remaining: 3, payload: Some(buffer) }
+///     };
+///
+///     let (sum, payload) = checksum_while(transfer, &[1, 2, 3]);
+///     assert_eq!(sum, 6);
+///     assert_eq!(payload.unwrap().len(), 4);
+/// }
+///
+/// # struct Tx<P> { remaining: u32, payload: Option<P> }
+/// # impl<P> Transfer for Tx<P> {
+/// #     type Payload = P;
+/// #     type Error = ();
+/// #     fn is_done(&self) -> bool { self.remaining == 0 }
+/// #     fn wait(mut self) -> Result<P, ((), P)> {
+/// #         while self.remaining != 0 { self.remaining -= 1 }
+/// #         Ok(self.payload.take().unwrap())
+/// #     }
+/// #     fn abort(mut self) -> P { self.payload.take().unwrap() }
+/// # }
+/// ```
+pub trait Transfer {
+    /// Resources given back once the transfer is over
+    type Payload;
+
+    /// Transfer error
+    type Error;
+
+    /// Has the transfer completed, or failed?
+    ///
+    /// Once this returns `true`, `wait` returns without blocking.
+    fn is_done(&self) -> bool;
+
+    /// Waits for the transfer to be over and gives back the payload
+    ///
+    /// The payload is given back even if the transfer failed.
+    fn wait(self) -> Result<Self::Payload, (Self::Error, Self::Payload)>;
+
+    /// Stops the transfer, if it's still in progress, and gives back the payload
+    ///
+    /// The contents of the buffers are unspecified: they may have been partially transferred.
+    fn abort(self) -> Self::Payload;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Transfer for Box<T>
+where
+    T: Transfer,
+{
+    type Payload = T::Payload;
+    type Error = T::Error;
+
+    fn is_done(&self) -> bool {
+        (**self).is_done()
+    }
+
+    fn wait(self) -> Result<Self::Payload, (Self::Error, Self::Payload)> {
+        (*self).wait()
+    }
+
+    fn abort(self) -> Self::Payload {
+        (*self).abort()
+    }
+}
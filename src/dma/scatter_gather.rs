@@ -0,0 +1,233 @@
+//! Scatter-gather DMA
+//!
+//! A scatter-gather transfer processes a chain of buffers, or *segments*, as a single transfer.
+//! *Gathering* sends the segments one after the other, e.g. to assemble a network frame from a
+//! header and a payload; *scattering* distributes the incoming data over the segments. The
+//! hardware walks a list of descriptors, so the segments don't need to be copied into one
+//! contiguous buffer first.
+//!
+//! Chains are arrays of buffers or, when the segments have different types, tuples of up to three
+//! buffers.
+
+use dma::{ReadBuffer, Transfer, Word, WriteBuffer};
+
+/// A chain of segments that DMA can read from
+///
+/// # Safety
+///
+/// - `read_segment` MUST meet the requirements of [`ReadBuffer::read_buffer`] for every segment.
+/// - `segment_count` and `read_segment` MUST always return the same values.
+///
+/// [`ReadBuffer::read_buffer`]: ../trait.ReadBuffer.html#tymethod.read_buffer
+pub unsafe trait ReadSegments {
+    /// Word the segments are made of
+    type Word: Word;
+
+    /// Returns the number of segments in the chain
+    fn segment_count(&self) -> usize;
+
+    /// Returns a pointer to the start of segment `index` and its length, in words
+    ///
+    /// # Safety
+    ///
+    /// `index` MUST be less than `segment_count()`. The memory MUST NOT be written to through the
+    /// returned pointer.
+    unsafe fn read_segment(&self, index: usize) -> (*const Self::Word, usize);
+}
+
+/// A chain of segments that DMA can write to
+///
+/// # Safety
+///
+/// - `write_segment` MUST meet the requirements of [`WriteBuffer::write_buffer`] for every
+///   segment, and the segments MUST NOT overlap.
+/// - `segment_count` and `write_segment` MUST always return the same values.
+///
+/// [`WriteBuffer::write_buffer`]: ../trait.WriteBuffer.html#tymethod.write_buffer
+pub unsafe trait WriteSegments {
+    /// Word the segments are made of
+    type Word: Word;
+
+    /// Returns the number of segments in the chain
+    fn segment_count(&self) -> usize;
+
+    /// Returns a pointer to the start of segment `index` and its length, in words
+    ///
+    /// # Safety
+    ///
+    /// `index` MUST be less than `segment_count()`. The returned pointer MUST NOT be used once
+    /// `self` has been dropped.
+    unsafe fn write_segment(&mut self, index: usize) -> (*mut Self::Word, usize);
+}
+
+/// Sends a chain of segments as a single transfer
+///
+/// # Contract
+///
+/// Chains of more than `MAX_SEGMENTS` segments MUST NOT be started; the returned transfer MUST be
+/// done right away and its `wait` MUST return an error.
+///
+/// # Examples
+///
+/// Sending a frame without copying its payload next to its header
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::scatter_gather::Gather;
+/// use hal::dma::Transfer;
+///
+/// fn send<G>(tx: G, payload: &'static [u8]) -> G
+/// where
+///     G: Gather<(&'static [u8], &'static [u8])>,
+/// {
+///     static HEADER: [u8; 2] = [0x7e, 0x01];
+///
+///     match tx.gather((&HEADER, payload)).wait() {
+///         Ok((tx, _)) => tx,
+///         Err((_, (tx, _))) => tx,
+///     }
+/// }
+///
+/// fn main() {
+///     let tx: Tx = {
+///         // ..
+/// #       Tx(vec![])
+///     };
+///
+///     let tx = send(tx, b"hello");
+///     assert_eq!(tx.0, b"\x7e\x01hello");
+/// }
+///
+/// # use hal::dma::scatter_gather::ReadSegments;
+/// # struct Tx(Vec<u8>);
+/// # impl<S: ReadSegments<Word = u8>> Gather<S> for Tx {
+/// #     const MAX_SEGMENTS: usize = 4;
+/// #     type Transfer = Done<(Tx, S)>;
+/// #     fn gather(mut self, segments: S) -> Done<(Tx, S)> {
+/// #         for i in 0..segments.segment_count() {
+/// #             let (ptr, len) = unsafe { segments.read_segment(i) };
+/// #             self.0.extend_from_slice(unsafe { std::slice::from_raw_parts(ptr, len) });
+/// #         }
+/// #         Done((self, segments))
+/// #     }
+/// # }
+/// # struct Done<P>(P);
+/// # impl<P> Transfer for Done<P> {
+/// #     type Payload = P;
+/// #     type Error = ();
+/// #     fn is_done(&self) -> bool { true }
+/// #     fn wait(self) -> Result<P, ((), P)> { Ok(self.0) }
+/// #     fn abort(self) -> P { self.0 }
+/// # }
+/// ```
+pub trait Gather<S>: Sized
+where
+    S: ReadSegments,
+{
+    /// Maximum number of segments in a chain
+    const MAX_SEGMENTS: usize;
+
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, S)>;
+
+    /// Starts sending the `segments`, in order
+    fn gather(self, segments: S) -> Self::Transfer;
+}
+
+/// Receives data into a chain of segments as a single transfer
+///
+/// # Contract
+///
+/// Chains of more than `MAX_SEGMENTS` segments MUST NOT be started; the returned transfer MUST be
+/// done right away and its `wait` MUST return an error.
+pub trait Scatter<S>: Sized
+where
+    S: WriteSegments,
+{
+    /// Maximum number of segments in a chain
+    const MAX_SEGMENTS: usize;
+
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, S)>;
+
+    /// Starts filling the `segments`, in order, each one completely before moving to the next
+    fn scatter(self, segments: S) -> Self::Transfer;
+}
+
+unsafe impl<B, const N: usize> ReadSegments for [B; N]
+where
+    B: ReadBuffer,
+{
+    type Word = B::Word;
+
+    fn segment_count(&self) -> usize {
+        N
+    }
+
+    unsafe fn read_segment(&self, index: usize) -> (*const B::Word, usize) {
+        self[index].read_buffer()
+    }
+}
+
+unsafe impl<B, const N: usize> WriteSegments for [B; N]
+where
+    B: WriteBuffer,
+{
+    type Word = B::Word;
+
+    fn segment_count(&self) -> usize {
+        N
+    }
+
+    unsafe fn write_segment(&mut self, index: usize) -> (*mut B::Word, usize) {
+        self[index].write_buffer()
+    }
+}
+
+macro_rules! tuple {
+    ($n:expr, $first:ident: $i0:tt $(, $rest:ident: $i:tt)*) => {
+        unsafe impl<$first, $($rest),*> ReadSegments for ($first, $($rest),*)
+        where
+            $first: ReadBuffer,
+            $($rest: ReadBuffer<Word = $first::Word>,)*
+        {
+            type Word = $first::Word;
+
+            fn segment_count(&self) -> usize {
+                $n
+            }
+
+            unsafe fn read_segment(&self, index: usize) -> (*const Self::Word, usize) {
+                match index {
+                    $i0 => self.$i0.read_buffer(),
+                    $($i => self.$i.read_buffer(),)*
+                    _ => panic!("segment index out of bounds"),
+                }
+            }
+        }
+
+        unsafe impl<$first, $($rest),*> WriteSegments for ($first, $($rest),*)
+        where
+            $first: WriteBuffer,
+            $($rest: WriteBuffer<Word = $first::Word>,)*
+        {
+            type Word = $first::Word;
+
+            fn segment_count(&self) -> usize {
+                $n
+            }
+
+            unsafe fn write_segment(&mut self, index: usize) -> (*mut Self::Word, usize) {
+                match index {
+                    $i0 => self.$i0.write_buffer(),
+                    $($i => self.$i.write_buffer(),)*
+                    _ => panic!("segment index out of bounds"),
+                }
+            }
+        }
+    };
+}
+
+tuple!(2, A: 0, B: 1);
+tuple!(3, A: 0, B: 1, C: 2);
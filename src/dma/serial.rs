@@ -0,0 +1,160 @@
+//! DMA transfers of serial interfaces
+//!
+//! The traits of this module start a transfer of a whole buffer and return right away with a
+//! [`Transfer`] handle, which gives the interface and the buffer back once the transfer is over,
+//! e.g. to send a log or to receive a fixed-size frame without the CPU handling each word. The
+//! errors of the transfers are the errors of the interface's [`serial::ErrorType`].
+//!
+//! [`Blocking`] implements them with the CPU, on top of the blocking and `nb` traits, so code
+//! written against them also runs on interfaces without DMA.
+//!
+//! [`Transfer`]: ../trait.Transfer.html
+//! [`serial::ErrorType`]: ../../serial/trait.ErrorType.html
+//! [`Blocking`]: struct.Blocking.html
+
+use core::{ptr, slice};
+
+use blocking;
+use dma::mem::Done;
+use dma::{ReadBuffer, Transfer, WriteBuffer};
+use serial::{self, ErrorType};
+
+/// Receives words until a buffer is full
+pub trait ReadExact<B>: ErrorType + Sized
+where
+    B: WriteBuffer,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, B), Error = Self::Error>;
+
+    /// Starts receiving words into `buffer`, until it's full
+    fn read_exact(self, buffer: B) -> Self::Transfer;
+}
+
+/// Sends all the words of a buffer
+///
+/// # Examples
+///
+/// Sending a log line while the next sample is taken
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::serial::{Blocking, WriteAll};
+/// use hal::dma::Transfer;
+///
+/// fn log<S>(tx: S, line: &'static [u8]) -> Result<S, S::Error>
+/// where
+///     S: WriteAll<&'static [u8]>,
+/// {
+///     let transfer = tx.write_all(line);
+///     // (take the next sample in the meantime)
+///     transfer.wait().map(|(tx, _)| tx).map_err(|(e, _)| e)
+/// }
+///
+/// fn main() {
+///     let usart1: Usart1 = {
+///         // ..
+/// #       Usart1(Vec::new())
+///     };
+///
+///     // no DMA channel left for this interface
+///     let usart1 = log(Blocking::new(usart1), b"t=21.5\r\n").unwrap();
+/// #   assert_eq!(usart1.into_inner().0, b"t=21.5\r\n");
+/// }
+///
+/// # use hal::serial::{ErrorKind, ErrorType};
+/// # struct Usart1(Vec<u8>);
+/// # impl ErrorType for Usart1 { type Error = ErrorKind; }
+/// # impl hal::blocking::serial::Write<u8> for Usart1 {
+/// #     fn bwrite_all(&mut self, words: &[u8]) -> Result<(), ErrorKind> {
+/// #         self.0.extend_from_slice(words);
+/// #         Ok(())
+/// #     }
+/// #     fn bflush(&mut self) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// ```
+pub trait WriteAll<B>: ErrorType + Sized
+where
+    B: ReadBuffer,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, B), Error = Self::Error>;
+
+    /// Starts sending all the words of `buffer`
+    ///
+    /// The transfer is over once the last word has been handed to the interface, which may still
+    /// be sending it.
+    fn write_all(self, buffer: B) -> Self::Transfer;
+}
+
+/// CPU implementation of the traits of this module
+///
+/// The transfers are over by the time `read_exact` or `write_all` return. Reads use the `nb`
+/// [`serial::Read`] trait, writes the [`blocking::serial::Write`] trait.
+///
+/// [`serial::Read`]: ../../serial/trait.Read.html
+/// [`blocking::serial::Write`]: ../../blocking/serial/trait.Write.html
+#[derive(Debug)]
+pub struct Blocking<S> {
+    serial: S,
+}
+
+impl<S> Blocking<S> {
+    /// Wraps `serial`
+    pub fn new(serial: S) -> Self {
+        Blocking { serial }
+    }
+
+    /// Returns the wrapped serial interface
+    pub fn into_inner(self) -> S {
+        self.serial
+    }
+}
+
+impl<S> ErrorType for Blocking<S>
+where
+    S: ErrorType,
+{
+    type Error = S::Error;
+}
+
+impl<S, B> ReadExact<B> for Blocking<S>
+where
+    S: serial::Read<B::Word>,
+    B: WriteBuffer,
+{
+    type Transfer = Done<(Self, B), S::Error>;
+
+    fn read_exact(mut self, mut buffer: B) -> Self::Transfer {
+        let (words, len) = unsafe { buffer.write_buffer() };
+        for i in 0..len {
+            match block!(self.serial.read()) {
+                Ok(word) => unsafe { ptr::write(words.add(i), word) },
+                Err(e) => return Done::new(Err((e, (self, buffer)))),
+            }
+        }
+
+        Done::new(Ok((self, buffer)))
+    }
+}
+
+impl<S, B> WriteAll<B> for Blocking<S>
+where
+    S: blocking::serial::Write<B::Word>,
+    B: ReadBuffer,
+{
+    type Transfer = Done<(Self, B), S::Error>;
+
+    fn write_all(mut self, buffer: B) -> Self::Transfer {
+        let result = unsafe {
+            let (words, len) = buffer.read_buffer();
+            self.serial.bwrite_all(slice::from_raw_parts(words, len))
+        };
+
+        match result {
+            Ok(()) => Done::new(Ok((self, buffer))),
+            Err(e) => Done::new(Err((e, (self, buffer)))),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! DMA transfers of SPI buses
+//!
+//! The traits of this module start a transfer of whole buffers and return right away with a
+//! [`Transfer`] handle, which gives the bus and the buffers back once the transfer is over, e.g.
+//! to send a frame buffer to a display while the next frame is rendered. The errors of the
+//! transfers are the errors of the bus' [`spi::ErrorType`].
+//!
+//! [`Blocking`] implements them with the CPU, on top of the blocking traits, so code written
+//! against them also runs on buses without DMA.
+//!
+//! [`Transfer`]: ../trait.Transfer.html
+//! [`spi::ErrorType`]: ../../spi/trait.ErrorType.html
+//! [`Blocking`]: struct.Blocking.html
+
+use core::{cmp, ptr, slice};
+
+use blocking;
+use dma::mem::Done;
+use dma::{ReadBuffer, Transfer, WriteBuffer};
+use spi::ErrorType;
+
+/// Receives words until a buffer is full
+pub trait ReadExact<B>: ErrorType + Sized
+where
+    B: WriteBuffer,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, B), Error = Self::Error>;
+
+    /// Starts receiving words into `buffer`, until it's full
+    ///
+    /// The words sent in the meantime are unspecified; drivers of devices that expect specific
+    /// words use [`FullDuplex`](trait.FullDuplex.html) instead.
+    fn read_exact(self, buffer: B) -> Self::Transfer;
+}
+
+/// Sends all the words of a buffer, ignoring the incoming words
+///
+/// # Examples
+///
+/// Sending a frame buffer to a display while the next frame is rendered
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::dma::spi::{Blocking, WriteAll};
+/// use hal::dma::Transfer;
+///
+/// fn show<S>(spi: S, frame: &'static mut [u16]) -> Result<(S, &'static mut [u16]), S::Error>
+/// where
+///     S: WriteAll<&'static mut [u16]>,
+/// {
+///     let transfer = spi.write_all(frame);
+///     // (render the next frame into the other buffer in the meantime)
+///     transfer.wait().map_err(|(e, _)| e)
+/// }
+///
+/// fn main() {
+///     let (spi1, frame): (Spi1, &'static mut [u16]) = {
+///         // ..
+/// #       (Spi1(Vec::new()), Box::leak(Box::new([0xf800; 4])))
+///     };
+///
+///     // no DMA channel left for this bus
+///     let (spi1, _frame) = show(Blocking::new(spi1), frame).unwrap();
+/// #   assert_eq!(spi1.into_inner().0, [0xf800; 4]);
+/// }
+///
+/// # use hal::spi::{ErrorKind, ErrorType};
+/// # struct Spi1(Vec<u16>);
+/// # impl ErrorType for Spi1 { type Error = ErrorKind; }
+/// # impl hal::blocking::spi::Write<u16> for Spi1 {
+/// #     fn write(&mut self, words: &[u16]) -> Result<(), ErrorKind> {
+/// #         self.0.extend_from_slice(words);
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait WriteAll<B>: ErrorType + Sized
+where
+    B: ReadBuffer,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, B), Error = Self::Error>;
+
+    /// Starts sending all the words of `buffer`
+    fn write_all(self, buffer: B) -> Self::Transfer;
+}
+
+/// Sends the words of a buffer while receiving into another one
+pub trait FullDuplex<T, R>: ErrorType + Sized
+where
+    T: ReadBuffer,
+    R: WriteBuffer<Word = T::Word>,
+{
+    /// Handle to the started transfer
+    type Transfer: Transfer<Payload = (Self, T, R), Error = Self::Error>;
+
+    /// Starts sending `tx` while receiving into `rx`
+    ///
+    /// If the buffers have different lengths, only the length of the shorter one is transferred.
+    fn transfer(self, tx: T, rx: R) -> Self::Transfer;
+}
+
+/// CPU implementation of the traits of this module
+///
+/// The transfers are over by the time `read_exact`, `write_all` or `transfer` return. Writes use
+/// the [`blocking::spi::Write`] trait, the other transfers the [`blocking::spi::Transfer`] trait;
+/// `read_exact` sends `Default` words, i.e. zeros.
+///
+/// [`blocking::spi::Write`]: ../../blocking/spi/trait.Write.html
+/// [`blocking::spi::Transfer`]: ../../blocking/spi/trait.Transfer.html
+#[derive(Debug)]
+pub struct Blocking<S> {
+    spi: S,
+}
+
+impl<S> Blocking<S> {
+    /// Wraps `spi`
+    pub fn new(spi: S) -> Self {
+        Blocking { spi }
+    }
+
+    /// Returns the wrapped SPI bus
+    pub fn into_inner(self) -> S {
+        self.spi
+    }
+}
+
+impl<S> ErrorType for Blocking<S>
+where
+    S: ErrorType,
+{
+    type Error = S::Error;
+}
+
+impl<S, B> ReadExact<B> for Blocking<S>
+where
+    S: blocking::spi::Transfer<B::Word>,
+    B: WriteBuffer,
+    B::Word: Default,
+{
+    type Transfer = Done<(Self, B), S::Error>;
+
+    fn read_exact(mut self, mut buffer: B) -> Self::Transfer {
+        let result = unsafe {
+            let (words, len) = buffer.write_buffer();
+            for i in 0..len {
+                ptr::write(words.add(i), B::Word::default());
+            }
+            self.spi.transfer(slice::from_raw_parts_mut(words, len))
+        };
+
+        match result {
+            Ok(_) => Done::new(Ok((self, buffer))),
+            Err(e) => Done::new(Err((e, (self, buffer)))),
+        }
+    }
+}
+
+impl<S, B> WriteAll<B> for Blocking<S>
+where
+    S: blocking::spi::Write<B::Word>,
+    B: ReadBuffer,
+{
+    type Transfer = Done<(Self, B), S::Error>;
+
+    fn write_all(mut self, buffer: B) -> Self::Transfer {
+        let result = unsafe {
+            let (words, len) = buffer.read_buffer();
+            self.spi.write(slice::from_raw_parts(words, len))
+        };
+
+        match result {
+            Ok(()) => Done::new(Ok((self, buffer))),
+            Err(e) => Done::new(Err((e, (self, buffer)))),
+        }
+    }
+}
+
+impl<S, T, R> FullDuplex<T, R> for Blocking<S>
+where
+    S: blocking::spi::Transfer<T::Word>,
+    T: ReadBuffer,
+    R: WriteBuffer<Word = T::Word>,
+{
+    type Transfer = Done<(Self, T, R), S::Error>;
+
+    fn transfer(mut self, tx: T, mut rx: R) -> Self::Transfer {
+        let result = unsafe {
+            let (from, n) = tx.read_buffer();
+            let (to, m) = rx.write_buffer();
+            let len = cmp::min(n, m);
+            // the buffer traits don't rule out overlapping buffers
+            ptr::copy(from, to, len);
+            self.spi.transfer(slice::from_raw_parts_mut(to, len))
+        };
+
+        match result {
+            Ok(_) => Done::new(Ok((self, tx, rx))),
+            Err(e) => Done::new(Err((e, (self, tx, rx)))),
+        }
+    }
+}
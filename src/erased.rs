@@ -0,0 +1,694 @@
+//! Type-erased peripherals
+//!
+//! *This module is available if embedded-hal is built with the `"erased"` feature.*
+//!
+//! Plugin architectures, and firmware that picks its devices at runtime, need trait objects, e.g.
+//! a `&mut dyn OutputPin`, rather than generics. Most traits in this crate are object safe:
+//!
+//! - the digital traits and the delay traits can be used as trait objects as they are
+//! - the SPI, serial, I2C and RNG traits, and the other traits with an associated error type, can
+//!   be used as trait objects that name their error type, e.g. `dyn Write<Error = MyError>`
+//!
+//! That leaves out the traits with generic methods, `timer::CountDown`, `Capture` and `Pwm`, and
+//! the peripherals whose error types differ, e.g. two SPI buses of different HALs. This module
+//! covers them with a pair of items per peripheral:
+//!
+//! - an object safe `Dyn*` trait, e.g. [`DynSpi`], which is implemented for all the implementers
+//!   of the traits it stands for and maps their errors to the error kind of their domain
+//! - an `Erased*` wrapper around a `&mut dyn Dyn*` trait object, e.g. [`ErasedSpi`], which
+//!   implements those traits again, so it can be handed to any driver
+//!
+//! The SPI, serial and I2C wrappers use 8-bit words.
+//!
+//! The traits of the `futures` module aren't object safe either, as their futures are generic
+//! associated types; erasing them would require boxing their futures, and they're not covered.
+//!
+//! [`DynSpi`]: trait.DynSpi.html
+//! [`ErasedSpi`]: struct.ErasedSpi.html
+//!
+//! # Examples
+//!
+//! Selecting the SPI bus a display is on at runtime
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::spi::Write;
+//! use hal::erased::{DynSpi, ErasedSpi};
+//!
+//! /// Driver that's generic over its SPI bus
+//! struct Display<SPI> {
+//!     spi: SPI,
+//! }
+//!
+//! impl<SPI> Display<SPI>
+//! where
+//!     SPI: Write<u8>,
+//! {
+//!     fn clear(&mut self) -> Result<(), SPI::Error> {
+//!         self.spi.write(&[0x01])
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let (mut spi1, mut spi2): (Spi1, Spi2) = {
+//!         // ..
+//! #       (Spi1, Spi2)
+//!     };
+//!     let on_spi2 = {
+//!         // read from the configuration
+//! #       true
+//!     };
+//!
+//!     let spi: &mut dyn DynSpi = if on_spi2 { &mut spi2 } else { &mut spi1 };
+//!     let mut display = Display { spi: ErasedSpi::new(spi) };
+//!
+//!     display.clear().unwrap();
+//! }
+//!
+//! # use hal::blocking::spi::Transfer;
+//! # use hal::spi::{ErrorKind, ErrorType};
+//! # struct Spi1;
+//! # struct Spi2;
+//! # #[derive(Debug)] struct Spi2Error;
+//! # impl hal::spi::Error for Spi2Error { fn kind(&self) -> ErrorKind { ErrorKind::Other } }
+//! # impl ErrorType for Spi1 { type Error = ErrorKind; }
+//! # impl ErrorType for Spi2 { type Error = Spi2Error; }
+//! # impl Transfer<u8> for Spi1 {
+//! #     fn transfer<'w>(&mut self, w: &'w mut [u8]) -> Result<&'w [u8], ErrorKind> { Ok(w) }
+//! # }
+//! # impl Write<u8> for Spi1 {
+//! #     fn write(&mut self, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//! # impl Transfer<u8> for Spi2 {
+//! #     fn transfer<'w>(&mut self, w: &'w mut [u8]) -> Result<&'w [u8], Spi2Error> { Ok(w) }
+//! # }
+//! # impl Write<u8> for Spi2 {
+//! #     fn write(&mut self, _: &[u8]) -> Result<(), Spi2Error> { Ok(()) }
+//! # }
+//! ```
+
+use core::fmt;
+
+use nb;
+use void::Void;
+
+use blocking;
+use i2c::{self, Error as _};
+use serial::{self, Error as _};
+use spi::{self, Error as _};
+use timer::CountDown;
+#[cfg(feature = "capture")]
+use Capture;
+#[cfg(feature = "pwm")]
+use Pwm;
+
+fn erase_nb<E, K, F>(error: nb::Error<E>, kind: F) -> nb::Error<K>
+where
+    F: FnOnce(&E) -> K,
+{
+    match error {
+        nb::Error::Other(ref e) => nb::Error::Other(kind(e)),
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+    }
+}
+
+/// Object safe version of the blocking SPI traits, for 8-bit words
+///
+/// It's implemented for all the implementers of `blocking::spi::{Transfer<u8>, Write<u8>}`, and
+/// reports the kind of their errors.
+pub trait DynSpi {
+    /// `blocking::spi::Transfer::transfer`
+    fn dyn_transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], spi::ErrorKind>;
+
+    /// `blocking::spi::Write::write`
+    fn dyn_write(&mut self, words: &[u8]) -> Result<(), spi::ErrorKind>;
+}
+
+impl<T> DynSpi for T
+where
+    T: blocking::spi::Transfer<u8> + blocking::spi::Write<u8>,
+{
+    fn dyn_transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], spi::ErrorKind> {
+        self.transfer(words).map_err(|e| e.kind())
+    }
+
+    fn dyn_write(&mut self, words: &[u8]) -> Result<(), spi::ErrorKind> {
+        self.write(words).map_err(|e| e.kind())
+    }
+}
+
+/// SPI bus behind a `DynSpi` trait object
+///
+/// It implements the blocking SPI traits for 8-bit words, with `spi::ErrorKind` as its error
+/// type.
+pub struct ErasedSpi<'a> {
+    inner: &'a mut (dyn DynSpi + 'a),
+}
+
+impl<'a> ErasedSpi<'a> {
+    /// Wraps `inner`
+    pub fn new(inner: &'a mut (dyn DynSpi + 'a)) -> Self {
+        ErasedSpi { inner }
+    }
+}
+
+impl<'a> fmt::Debug for ErasedSpi<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedSpi")
+    }
+}
+
+impl<'a> spi::ErrorType for ErasedSpi<'a> {
+    type Error = spi::ErrorKind;
+}
+
+impl<'a> blocking::spi::Transfer<u8> for ErasedSpi<'a> {
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], spi::ErrorKind> {
+        self.inner.dyn_transfer(words)
+    }
+}
+
+impl<'a> blocking::spi::Write<u8> for ErasedSpi<'a> {
+    fn write(&mut self, words: &[u8]) -> Result<(), spi::ErrorKind> {
+        self.inner.dyn_write(words)
+    }
+}
+
+/// Object safe version of the serial traits, for 8-bit words
+///
+/// It's implemented for all the implementers of `serial::{Read<u8>, Write<u8>}`, and reports the
+/// kind of their errors.
+pub trait DynSerial {
+    /// `serial::Read::read`
+    fn dyn_read(&mut self) -> nb::Result<u8, serial::ErrorKind>;
+
+    /// `serial::Write::write`
+    fn dyn_write(&mut self, word: u8) -> nb::Result<(), serial::ErrorKind>;
+
+    /// `serial::Write::flush`
+    fn dyn_flush(&mut self) -> nb::Result<(), serial::ErrorKind>;
+}
+
+impl<T> DynSerial for T
+where
+    T: serial::Read<u8> + serial::Write<u8>,
+{
+    fn dyn_read(&mut self) -> nb::Result<u8, serial::ErrorKind> {
+        self.read().map_err(|e| erase_nb(e, |e| e.kind()))
+    }
+
+    fn dyn_write(&mut self, word: u8) -> nb::Result<(), serial::ErrorKind> {
+        self.write(word).map_err(|e| erase_nb(e, |e| e.kind()))
+    }
+
+    fn dyn_flush(&mut self) -> nb::Result<(), serial::ErrorKind> {
+        self.flush().map_err(|e| erase_nb(e, |e| e.kind()))
+    }
+}
+
+/// Serial interface behind a `DynSerial` trait object
+///
+/// It implements the serial traits for 8-bit words, including the blocking
+/// `blocking::serial::Write`, with `serial::ErrorKind` as its error type.
+pub struct ErasedSerial<'a> {
+    inner: &'a mut (dyn DynSerial + 'a),
+}
+
+impl<'a> ErasedSerial<'a> {
+    /// Wraps `inner`
+    pub fn new(inner: &'a mut (dyn DynSerial + 'a)) -> Self {
+        ErasedSerial { inner }
+    }
+}
+
+impl<'a> fmt::Debug for ErasedSerial<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedSerial")
+    }
+}
+
+impl<'a> serial::ErrorType for ErasedSerial<'a> {
+    type Error = serial::ErrorKind;
+}
+
+impl<'a> serial::Read<u8> for ErasedSerial<'a> {
+    fn read(&mut self) -> nb::Result<u8, serial::ErrorKind> {
+        self.inner.dyn_read()
+    }
+}
+
+impl<'a> serial::Write<u8> for ErasedSerial<'a> {
+    fn write(&mut self, word: u8) -> nb::Result<(), serial::ErrorKind> {
+        self.inner.dyn_write(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), serial::ErrorKind> {
+        self.inner.dyn_flush()
+    }
+}
+
+impl<'a> blocking::serial::write::Default<u8> for ErasedSerial<'a> {}
+
+/// Object safe version of the blocking I2C traits
+///
+/// It's implemented for all the implementers of `blocking::i2c::{Read, Write, WriteRead}`, and
+/// reports the kind of their errors.
+pub trait DynI2c {
+    /// `blocking::i2c::Read::read`
+    fn dyn_read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), i2c::ErrorKind>;
+
+    /// `blocking::i2c::Write::write`
+    fn dyn_write(&mut self, address: u8, bytes: &[u8]) -> Result<(), i2c::ErrorKind>;
+
+    /// `blocking::i2c::WriteRead::write_read`
+    fn dyn_write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), i2c::ErrorKind>;
+}
+
+impl<T> DynI2c for T
+where
+    T: blocking::i2c::Read + blocking::i2c::Write + blocking::i2c::WriteRead,
+{
+    fn dyn_read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), i2c::ErrorKind> {
+        self.read(address, buffer).map_err(|e| e.kind())
+    }
+
+    fn dyn_write(&mut self, address: u8, bytes: &[u8]) -> Result<(), i2c::ErrorKind> {
+        self.write(address, bytes).map_err(|e| e.kind())
+    }
+
+    fn dyn_write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), i2c::ErrorKind> {
+        self.write_read(address, bytes, buffer)
+            .map_err(|e| e.kind())
+    }
+}
+
+/// I2C bus behind a `DynI2c` trait object
+///
+/// It implements the blocking I2C traits, with `i2c::ErrorKind` as its error type.
+pub struct ErasedI2c<'a> {
+    inner: &'a mut (dyn DynI2c + 'a),
+}
+
+impl<'a> ErasedI2c<'a> {
+    /// Wraps `inner`
+    pub fn new(inner: &'a mut (dyn DynI2c + 'a)) -> Self {
+        ErasedI2c { inner }
+    }
+}
+
+impl<'a> fmt::Debug for ErasedI2c<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedI2c")
+    }
+}
+
+impl<'a> i2c::ErrorType for ErasedI2c<'a> {
+    type Error = i2c::ErrorKind;
+}
+
+impl<'a> blocking::i2c::Read for ErasedI2c<'a> {
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), i2c::ErrorKind> {
+        self.inner.dyn_read(address, buffer)
+    }
+}
+
+impl<'a> blocking::i2c::Write for ErasedI2c<'a> {
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), i2c::ErrorKind> {
+        self.inner.dyn_write(address, bytes)
+    }
+}
+
+impl<'a> blocking::i2c::WriteRead for ErasedI2c<'a> {
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), i2c::ErrorKind> {
+        self.inner.dyn_write_read(address, bytes, buffer)
+    }
+}
+
+/// Object safe version of `timer::CountDown`
+///
+/// It's implemented for all the implementers of `timer::CountDown`. Its `dyn_start` method takes
+/// a `Time` rather than anything that converts into one.
+pub trait DynCountDown {
+    /// `timer::CountDown::Time`
+    type Time;
+
+    /// `timer::CountDown::start`
+    fn dyn_start(&mut self, count: Self::Time);
+
+    /// `timer::CountDown::wait`
+    fn dyn_wait(&mut self) -> nb::Result<(), Void>;
+}
+
+impl<T> DynCountDown for T
+where
+    T: CountDown,
+{
+    type Time = T::Time;
+
+    fn dyn_start(&mut self, count: T::Time) {
+        self.start(count)
+    }
+
+    fn dyn_wait(&mut self) -> nb::Result<(), Void> {
+        self.wait()
+    }
+}
+
+/// Timer behind a `DynCountDown` trait object
+///
+/// It implements `timer::CountDown`, with the same `Time` as the trait object.
+pub struct ErasedCountDown<'a, Time>
+where
+    Time: 'a,
+{
+    inner: &'a mut (dyn DynCountDown<Time = Time> + 'a),
+}
+
+impl<'a, Time> ErasedCountDown<'a, Time> {
+    /// Wraps `inner`
+    pub fn new(inner: &'a mut (dyn DynCountDown<Time = Time> + 'a)) -> Self {
+        ErasedCountDown { inner }
+    }
+}
+
+impl<'a, Time> fmt::Debug for ErasedCountDown<'a, Time> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedCountDown")
+    }
+}
+
+impl<'a, Time> CountDown for ErasedCountDown<'a, Time> {
+    type Time = Time;
+
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<Time>,
+    {
+        self.inner.dyn_start(count.into())
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        self.inner.dyn_wait()
+    }
+}
+
+/// Object safe version of `Capture`
+///
+/// *This trait is available if embedded-hal is built with the `"capture"` feature.*
+///
+/// It's implemented for all the implementers of `Capture`. Its `dyn_set_resolution` method takes a
+/// `Time` rather than anything that converts into one.
+#[cfg(feature = "capture")]
+pub trait DynCapture {
+    /// `Capture::Error`
+    type Error;
+    /// `Capture::Channel`
+    type Channel;
+    /// `Capture::Time`
+    type Time;
+    /// `Capture::Capture`
+    type Capture;
+
+    /// `Capture::capture`
+    fn dyn_capture(&mut self, channel: Self::Channel) -> nb::Result<Self::Capture, Self::Error>;
+
+    /// `Capture::disable`
+    fn dyn_disable(&mut self, channel: Self::Channel);
+
+    /// `Capture::enable`
+    fn dyn_enable(&mut self, channel: Self::Channel);
+
+    /// `Capture::get_resolution`
+    fn dyn_get_resolution(&self) -> Self::Time;
+
+    /// `Capture::set_resolution`
+    fn dyn_set_resolution(&mut self, resolution: Self::Time);
+}
+
+#[cfg(feature = "capture")]
+impl<T> DynCapture for T
+where
+    T: Capture,
+{
+    type Error = T::Error;
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Capture = T::Capture;
+
+    fn dyn_capture(&mut self, channel: T::Channel) -> nb::Result<T::Capture, T::Error> {
+        self.capture(channel)
+    }
+
+    fn dyn_disable(&mut self, channel: T::Channel) {
+        self.disable(channel)
+    }
+
+    fn dyn_enable(&mut self, channel: T::Channel) {
+        self.enable(channel)
+    }
+
+    fn dyn_get_resolution(&self) -> T::Time {
+        self.get_resolution()
+    }
+
+    fn dyn_set_resolution(&mut self, resolution: T::Time) {
+        self.set_resolution(resolution)
+    }
+}
+
+/// Input capture behind a `DynCapture` trait object
+///
+/// *This type is available if embedded-hal is built with the `"capture"` feature.*
+///
+/// It implements `Capture`, with the same associated types as the trait object.
+#[cfg(feature = "capture")]
+pub struct ErasedCapture<'a, Error, Channel, Time, Value>
+where
+    Error: 'a,
+    Channel: 'a,
+    Time: 'a,
+    Value: 'a,
+{
+    inner: &'a mut (dyn DynCapture<
+        Error = Error,
+        Channel = Channel,
+        Time = Time,
+        Capture = Value,
+    > + 'a),
+}
+
+#[cfg(feature = "capture")]
+impl<'a, Error, Channel, Time, Value> ErasedCapture<'a, Error, Channel, Time, Value> {
+    /// Wraps `inner`
+    pub fn new(
+        inner: &'a mut (dyn DynCapture<
+            Error = Error,
+            Channel = Channel,
+            Time = Time,
+            Capture = Value,
+        > + 'a),
+    ) -> Self {
+        ErasedCapture { inner }
+    }
+}
+
+#[cfg(feature = "capture")]
+impl<'a, Error, Channel, Time, Value> fmt::Debug
+    for ErasedCapture<'a, Error, Channel, Time, Value>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedCapture")
+    }
+}
+
+#[cfg(feature = "capture")]
+impl<'a, Error, Channel, Time, Value> Capture for ErasedCapture<'a, Error, Channel, Time, Value> {
+    type Error = Error;
+    type Channel = Channel;
+    type Time = Time;
+    type Capture = Value;
+
+    fn capture(&mut self, channel: Channel) -> nb::Result<Value, Error> {
+        self.inner.dyn_capture(channel)
+    }
+
+    fn disable(&mut self, channel: Channel) {
+        self.inner.dyn_disable(channel)
+    }
+
+    fn enable(&mut self, channel: Channel) {
+        self.inner.dyn_enable(channel)
+    }
+
+    fn get_resolution(&self) -> Time {
+        self.inner.dyn_get_resolution()
+    }
+
+    fn set_resolution<R>(&mut self, resolution: R)
+    where
+        R: Into<Time>,
+    {
+        self.inner.dyn_set_resolution(resolution.into())
+    }
+}
+
+/// Object safe version of `Pwm`
+///
+/// *This trait is available if embedded-hal is built with the `"pwm"` feature.*
+///
+/// It's implemented for all the implementers of `Pwm`. Its `dyn_set_period` method takes a `Time`
+/// rather than anything that converts into one.
+#[cfg(feature = "pwm")]
+pub trait DynPwm {
+    /// `Pwm::Channel`
+    type Channel;
+    /// `Pwm::Time`
+    type Time;
+    /// `Pwm::Duty`
+    type Duty;
+
+    /// `Pwm::disable`
+    fn dyn_disable(&mut self, channel: Self::Channel);
+
+    /// `Pwm::enable`
+    fn dyn_enable(&mut self, channel: Self::Channel);
+
+    /// `Pwm::get_period`
+    fn dyn_get_period(&self) -> Self::Time;
+
+    /// `Pwm::get_duty`
+    fn dyn_get_duty(&self, channel: Self::Channel) -> Self::Duty;
+
+    /// `Pwm::get_max_duty`
+    fn dyn_get_max_duty(&self) -> Self::Duty;
+
+    /// `Pwm::set_duty`
+    fn dyn_set_duty(&mut self, channel: Self::Channel, duty: Self::Duty);
+
+    /// `Pwm::set_period`
+    fn dyn_set_period(&mut self, period: Self::Time);
+}
+
+#[cfg(feature = "pwm")]
+impl<T> DynPwm for T
+where
+    T: Pwm,
+{
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Duty = T::Duty;
+
+    fn dyn_disable(&mut self, channel: T::Channel) {
+        self.disable(channel)
+    }
+
+    fn dyn_enable(&mut self, channel: T::Channel) {
+        self.enable(channel)
+    }
+
+    fn dyn_get_period(&self) -> T::Time {
+        self.get_period()
+    }
+
+    fn dyn_get_duty(&self, channel: T::Channel) -> T::Duty {
+        self.get_duty(channel)
+    }
+
+    fn dyn_get_max_duty(&self) -> T::Duty {
+        self.get_max_duty()
+    }
+
+    fn dyn_set_duty(&mut self, channel: T::Channel, duty: T::Duty) {
+        self.set_duty(channel, duty)
+    }
+
+    fn dyn_set_period(&mut self, period: T::Time) {
+        self.set_period(period)
+    }
+}
+
+/// PWM behind a `DynPwm` trait object
+///
+/// *This type is available if embedded-hal is built with the `"pwm"` feature.*
+///
+/// It implements `Pwm`, with the same associated types as the trait object.
+#[cfg(feature = "pwm")]
+pub struct ErasedPwm<'a, Channel, Time, Duty>
+where
+    Channel: 'a,
+    Time: 'a,
+    Duty: 'a,
+{
+    inner: &'a mut (dyn DynPwm<Channel = Channel, Time = Time, Duty = Duty> + 'a),
+}
+
+#[cfg(feature = "pwm")]
+impl<'a, Channel, Time, Duty> ErasedPwm<'a, Channel, Time, Duty> {
+    /// Wraps `inner`
+    pub fn new(
+        inner: &'a mut (dyn DynPwm<Channel = Channel, Time = Time, Duty = Duty> + 'a),
+    ) -> Self {
+        ErasedPwm { inner }
+    }
+}
+
+#[cfg(feature = "pwm")]
+impl<'a, Channel, Time, Duty> fmt::Debug for ErasedPwm<'a, Channel, Time, Duty> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErasedPwm")
+    }
+}
+
+#[cfg(feature = "pwm")]
+impl<'a, Channel, Time, Duty> Pwm for ErasedPwm<'a, Channel, Time, Duty> {
+    type Channel = Channel;
+    type Time = Time;
+    type Duty = Duty;
+
+    fn disable(&mut self, channel: Channel) {
+        self.inner.dyn_disable(channel)
+    }
+
+    fn enable(&mut self, channel: Channel) {
+        self.inner.dyn_enable(channel)
+    }
+
+    fn get_period(&self) -> Time {
+        self.inner.dyn_get_period()
+    }
+
+    fn get_duty(&self, channel: Channel) -> Duty {
+        self.inner.dyn_get_duty(channel)
+    }
+
+    fn get_max_duty(&self) -> Duty {
+        self.inner.dyn_get_max_duty()
+    }
+
+    fn set_duty(&mut self, channel: Channel, duty: Duty) {
+        self.inner.dyn_set_duty(channel, duty)
+    }
+
+    fn set_period<P>(&mut self, period: P)
+    where
+        P: Into<Time>,
+    {
+        self.inner.dyn_set_period(period.into())
+    }
+}
@@ -0,0 +1,198 @@
+//! Errors of all the domains
+//!
+//! Each domain has an error kind of its own, e.g. [`spi::ErrorKind`]. Applications that compose
+//! drivers of several domains can collapse their errors into a [`HalErrorKind`] instead, which
+//! the error kind of every enabled domain converts into.
+//!
+//! [`spi::ErrorKind`]: ../spi/enum.ErrorKind.html
+//! [`HalErrorKind`]: enum.HalErrorKind.html
+//!
+//! # Examples
+//!
+//! An application that reads a sensor over I2C and logs the result over serial
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::i2c::WriteRead;
+//! use hal::blocking::serial::Write;
+//! use hal::error::HalErrorKind;
+//! use hal::i2c::Error as _;
+//! use hal::i2c::NoAcknowledgeSource::Address;
+//! use hal::serial::Error as _;
+//!
+//! fn log_temperature<I, S>(i2c: &mut I, serial: &mut S) -> Result<(), HalErrorKind>
+//! where
+//!     I: WriteRead,
+//!     S: Write<u8>,
+//! {
+//!     let mut temperature = [0; 2];
+//!     i2c.write_read(0x48, &[0x00], &mut temperature).map_err(|e| e.kind())?;
+//!     serial.bwrite_all(&temperature).map_err(|e| e.kind())?;
+//!     Ok(())
+//! }
+//!
+//! fn main() {
+//!     let (mut i2c, mut serial): (I2c1, Serial1) = {
+//!         // ..
+//! #       (I2c1, Serial1)
+//!     };
+//!
+//!     assert_eq!(
+//!         log_temperature(&mut i2c, &mut serial),
+//!         Err(HalErrorKind::I2c(hal::i2c::ErrorKind::NoAcknowledge(Address))),
+//!     );
+//! }
+//!
+//! # use hal::i2c::ErrorKind;
+//! # struct I2c1;
+//! # impl hal::i2c::ErrorType for I2c1 { type Error = ErrorKind; }
+//! # impl WriteRead for I2c1 {
+//! #     fn write_read(&mut self, _: u8, _: &[u8], _: &mut [u8]) -> Result<(), ErrorKind> {
+//! #         Err(ErrorKind::NoAcknowledge(Address))
+//! #     }
+//! # }
+//! # struct Serial1;
+//! # impl hal::serial::ErrorType for Serial1 { type Error = hal::serial::ErrorKind; }
+//! # impl Write<u8> for Serial1 {
+//! #     fn bwrite_all(&mut self, _: &[u8]) -> Result<(), hal::serial::ErrorKind> { Ok(()) }
+//! #     fn bflush(&mut self) -> Result<(), hal::serial::ErrorKind> { Ok(()) }
+//! # }
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "adc")]
+use adc;
+#[cfg(feature = "calibration")]
+use calibration;
+#[cfg(feature = "can")]
+use can;
+use digital;
+use i2c;
+#[cfg(feature = "i2s")]
+use i2s;
+#[cfg(feature = "i3c")]
+use i3c;
+#[cfg(feature = "pdm")]
+use pdm;
+#[cfg(feature = "power")]
+use power;
+#[cfg(feature = "qspi")]
+use qspi;
+#[cfg(feature = "rng")]
+use rng;
+#[cfg(feature = "rtc")]
+use rtc;
+use serial;
+use spi;
+#[cfg(feature = "storage")]
+use storage;
+
+/// Error kind of any domain
+///
+/// The variants of the unproven domains are only available with their Cargo features. As any crate
+/// of the dependency graph can enable them, the enum is non-exhaustive: `match`es on it need a
+/// wildcard arm.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[non_exhaustive]
+pub enum HalErrorKind {
+    /// ADC error
+    #[cfg(feature = "adc")]
+    Adc(adc::ErrorKind),
+    /// Calibration error
+    #[cfg(feature = "calibration")]
+    Calibration(calibration::ErrorKind),
+    /// CAN error
+    #[cfg(feature = "can")]
+    Can(can::ErrorKind),
+    /// Digital I/O error
+    Digital(digital::ErrorKind),
+    /// I2C error
+    I2c(i2c::ErrorKind),
+    /// I2S error
+    #[cfg(feature = "i2s")]
+    I2s(i2s::ErrorKind),
+    /// I3C error
+    #[cfg(feature = "i3c")]
+    I3c(i3c::ErrorKind),
+    /// PDM error
+    #[cfg(feature = "pdm")]
+    Pdm(pdm::ErrorKind),
+    /// Power management error
+    #[cfg(feature = "power")]
+    Power(power::ErrorKind),
+    /// QSPI error
+    #[cfg(feature = "qspi")]
+    Qspi(qspi::ErrorKind),
+    /// RNG error
+    #[cfg(feature = "rng")]
+    Rng(rng::ErrorKind),
+    /// RTC error
+    #[cfg(feature = "rtc")]
+    Rtc(rtc::ErrorKind),
+    /// Serial error
+    Serial(serial::ErrorKind),
+    /// SPI error
+    Spi(spi::ErrorKind),
+    /// Storage error
+    #[cfg(feature = "storage")]
+    Storage(storage::ErrorKind),
+}
+
+macro_rules! domains {
+    ($($(#[$cfg:meta])* $Variant:ident($domain:ident, $name:expr),)+) => {
+        $(
+            $(#[$cfg])*
+            impl From<$domain::ErrorKind> for HalErrorKind {
+                fn from(kind: $domain::ErrorKind) -> Self {
+                    HalErrorKind::$Variant(kind)
+                }
+            }
+        )+
+
+        impl fmt::Display for HalErrorKind {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $(
+                        $(#[$cfg])*
+                        HalErrorKind::$Variant(ref kind) => write!(f, "{} error: {}", $name, kind),
+                    )+
+                }
+            }
+        }
+    }
+}
+
+domains! {
+    #[cfg(feature = "adc")]
+    Adc(adc, "ADC"),
+    #[cfg(feature = "calibration")]
+    Calibration(calibration, "calibration"),
+    #[cfg(feature = "can")]
+    Can(can, "CAN"),
+    Digital(digital, "digital I/O"),
+    I2c(i2c, "I2C"),
+    #[cfg(feature = "i2s")]
+    I2s(i2s, "I2S"),
+    #[cfg(feature = "i3c")]
+    I3c(i3c, "I3C"),
+    #[cfg(feature = "pdm")]
+    Pdm(pdm, "PDM"),
+    #[cfg(feature = "power")]
+    Power(power, "power management"),
+    #[cfg(feature = "qspi")]
+    Qspi(qspi, "QSPI"),
+    #[cfg(feature = "rng")]
+    Rng(rng, "RNG"),
+    #[cfg(feature = "rtc")]
+    Rtc(rtc, "RTC"),
+    Serial(serial, "serial"),
+    Spi(spi, "SPI"),
+    #[cfg(feature = "storage")]
+    Storage(storage, "storage"),
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for HalErrorKind {}
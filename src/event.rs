@@ -0,0 +1,151 @@
+//! Interrupt event sources
+//!
+//! *This module is available if embedded-hal is built with the `"event"` feature.*
+//!
+//! Peripherals raise events, e.g. an edge on a pin, the update of a timer or a byte received by a
+//! UART, which usually trigger an interrupt. [`EventSource`] exposes them uniformly, so that a
+//! scheduler or an async runtime has a single integration point instead of the interrupt plumbing
+//! of each peripheral.
+//!
+//! [`EventSource`]: trait.EventSource.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// Identifier of the listener of an event source, chosen by the scheduler, e.g. a task index
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Token(pub usize);
+
+/// Peripheral that raises events
+///
+/// # Contract
+///
+/// - An event MUST NOT trigger an interrupt unless it's been enabled with `listen`.
+/// - `take_pending` MUST clear the pending flag of `event`, if it was set, so that the interrupt
+///   handler can return without the interrupt firing again.
+/// - `listener` MUST return the token last passed to `register`, if any.
+///
+/// # Examples
+///
+/// An interrupt handler that readies the tasks waiting for the events of a UART
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::event::{EventSource, Token};
+///
+/// fn on_interrupt<S>(source: &mut S, events: &[S::Event], ready: &mut Vec<Token>)
+/// where
+///     S: EventSource,
+/// {
+///     for &event in events {
+///         if source.take_pending(event) {
+///             ready.extend(source.listener());
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let mut uart: Uart1 = {
+///         // ..
+/// #       Uart1 { rxne: true, token: None }
+///     };
+///
+///     uart.register(Token(3));
+///     uart.listen(Event::RxNotEmpty);
+///
+///     // USART1 interrupt
+///     let mut ready = vec![];
+///     on_interrupt(&mut uart, &[Event::RxNotEmpty, Event::TxEmpty], &mut ready);
+///     assert_eq!(ready, [Token(3)]);
+/// }
+///
+/// # #[derive(Clone, Copy, Debug, PartialEq)]
+/// # enum Event { RxNotEmpty, TxEmpty }
+/// # struct Uart1 { rxne: bool, token: Option<Token> }
+/// # impl EventSource for Uart1 {
+/// #     type Event = Event;
+/// #     fn listen(&mut self, _: Event) {}
+/// #     fn unlisten(&mut self, _: Event) {}
+/// #     fn take_pending(&mut self, event: Event) -> bool {
+/// #         event == Event::RxNotEmpty && std::mem::replace(&mut self.rxne, false)
+/// #     }
+/// #     fn register(&mut self, token: Token) { self.token = Some(token) }
+/// #     fn listener(&self) -> Option<Token> { self.token }
+/// # }
+/// ```
+pub trait EventSource {
+    /// Events the peripheral raises, usually an enum, e.g. `RxNotEmpty` or `TxEmpty`
+    type Event: Copy;
+
+    /// Enables `event`, so that it triggers an interrupt
+    fn listen(&mut self, event: Self::Event);
+
+    /// Disables `event`
+    fn unlisten(&mut self, event: Self::Event);
+
+    /// Checks whether `event` is pending, and clears it
+    fn take_pending(&mut self, event: Self::Event) -> bool;
+
+    /// Registers the listener of the events, replacing the previous one
+    fn register(&mut self, token: Token);
+
+    /// Returns the listener of the events
+    fn listener(&self) -> Option<Token>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> EventSource for Box<T>
+where
+    T: EventSource + ?Sized,
+{
+    type Event = T::Event;
+
+    fn listen(&mut self, event: Self::Event) {
+        (**self).listen(event)
+    }
+
+    fn unlisten(&mut self, event: Self::Event) {
+        (**self).unlisten(event)
+    }
+
+    fn take_pending(&mut self, event: Self::Event) -> bool {
+        (**self).take_pending(event)
+    }
+
+    fn register(&mut self, token: Token) {
+        (**self).register(token)
+    }
+
+    fn listener(&self) -> Option<Token> {
+        (**self).listener()
+    }
+}
+
+impl<T> EventSource for &mut T
+where
+    T: EventSource + ?Sized,
+{
+    type Event = T::Event;
+
+    fn listen(&mut self, event: Self::Event) {
+        (**self).listen(event)
+    }
+
+    fn unlisten(&mut self, event: Self::Event) {
+        (**self).unlisten(event)
+    }
+
+    fn take_pending(&mut self, event: Self::Event) -> bool {
+        (**self).take_pending(event)
+    }
+
+    fn register(&mut self, token: Token) {
+        (**self).register(token)
+    }
+
+    fn listener(&self) -> Option<Token> {
+        (**self).listener()
+    }
+}
@@ -0,0 +1,284 @@
+//! Integration with the [`fugit`] time library
+//!
+//! *This module is available if embedded-hal is built with the `"fugit"` feature.*
+//!
+//! The timing traits of this crate leave the units to the implementation: [`DelayUs`] takes a
+//! number of microseconds, and the `Time` of [`CountDown`] or [`Pwm`] is whatever type the HAL
+//! picks. This module maps them onto the typed durations and rates of `fugit`:
+//!
+//! - HALs SHOULD use [`Duration`] as the `Time` of their timers, watchdogs and input captures, and
+//!   [`Rate`] as the `Time` of their PWM peripherals. The units of the [`time`] module convert
+//!   into them, so these HALs also accept the durations and frequencies of drivers that don't
+//!   depend on `fugit`.
+//! - Drivers can then use the extension traits, [`DelayExt`], [`CountDownExt`], [`CaptureExt`],
+//!   [`PwmExt`] and [`WatchdogEnableExt`], which accept anything that implements the conversion
+//!   traits [`IntoDuration`] or [`IntoRate`]: any `fugit` duration or rate, any unit of the `time`
+//!   module, or a raw integer of microseconds or hertz.
+//!
+//! [`fugit`]: https://docs.rs/fugit/0.3
+//! [`DelayUs`]: ../blocking/delay/trait.DelayUs.html
+//! [`CountDown`]: ../timer/trait.CountDown.html
+//! [`Pwm`]: ../trait.Pwm.html
+//! [`Duration`]: type.Duration.html
+//! [`Rate`]: type.Rate.html
+//! [`time`]: ../time/index.html
+//! [`DelayExt`]: trait.DelayExt.html
+//! [`CountDownExt`]: trait.CountDownExt.html
+//! [`CaptureExt`]: trait.CaptureExt.html
+//! [`PwmExt`]: trait.PwmExt.html
+//! [`WatchdogEnableExt`]: trait.WatchdogEnableExt.html
+//! [`IntoDuration`]: trait.IntoDuration.html
+//! [`IntoRate`]: trait.IntoRate.html
+//!
+//! # Examples
+//!
+//! A driver that takes its timings as typed durations
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//! extern crate fugit;
+//!
+//! use fugit::ExtU32;
+//! use hal::blocking::delay::DelayUs;
+//! use hal::digital::OutputPin;
+//! use hal::fugit::{DelayExt, IntoDuration};
+//!
+//! /// Pulses the reset line of a display
+//! fn reset<P, D, T>(rst: &mut P, delay: &mut D, pulse: T)
+//! where
+//!     P: OutputPin,
+//!     D: DelayUs<u32>,
+//!     T: IntoDuration,
+//! {
+//!     rst.set_low();
+//!     delay.delay(pulse);
+//!     rst.set_high();
+//! }
+//!
+//! fn main() {
+//!     let (mut rst, mut delay): (Pa1, Delay) = {
+//!         // ..
+//! #       (Pa1, Delay(0))
+//!     };
+//!
+//!     reset(&mut rst, &mut delay, 10.millis::<1, 1_000>());
+//!     // or, in microseconds
+//!     reset(&mut rst, &mut delay, 10_000);
+//! #   assert_eq!(delay.0, 20_000);
+//! }
+//!
+//! # struct Pa1;
+//! # impl OutputPin for Pa1 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+//! # struct Delay(u32);
+//! # impl DelayUs<u32> for Delay { fn delay_us(&mut self, us: u32) { self.0 += us } }
+//! ```
+
+use fugit_crate;
+
+use blocking::delay::DelayUs;
+use time::{Hertz, MicroSeconds, MilliSeconds};
+use timer::CountDown;
+#[cfg(feature = "watchdog")]
+use watchdog::WatchdogEnable;
+#[cfg(feature = "capture")]
+use Capture;
+#[cfg(feature = "pwm")]
+use Pwm;
+
+/// Duration in microseconds, the recommended `Time` of timers and input captures
+pub type Duration = fugit_crate::MicrosDurationU32;
+
+/// Rate in hertz, the recommended `Time` of PWM peripherals
+pub type Rate = fugit_crate::HertzU32;
+
+/// Conversion into a [`Duration`](type.Duration.html)
+///
+/// It's implemented for every `fugit` duration with a `u32` tick count, for the durations of the
+/// [`time`](../time/index.html) module, and for `u32` as a number of microseconds.
+pub trait IntoDuration {
+    /// Converts `self` into a duration in microseconds
+    fn into_duration(self) -> Duration;
+}
+
+impl IntoDuration for u32 {
+    fn into_duration(self) -> Duration {
+        Duration::from_ticks(self)
+    }
+}
+
+impl<const NOM: u32, const DENOM: u32> IntoDuration for fugit_crate::Duration<u32, NOM, DENOM> {
+    fn into_duration(self) -> Duration {
+        Duration::from_ticks(self.to_micros())
+    }
+}
+
+impl IntoDuration for MicroSeconds {
+    fn into_duration(self) -> Duration {
+        Duration::from_ticks(self.0)
+    }
+}
+
+impl IntoDuration for MilliSeconds {
+    fn into_duration(self) -> Duration {
+        MicroSeconds::from(self).into_duration()
+    }
+}
+
+impl From<MicroSeconds> for Duration {
+    fn from(duration: MicroSeconds) -> Self {
+        duration.into_duration()
+    }
+}
+
+impl From<MilliSeconds> for Duration {
+    fn from(duration: MilliSeconds) -> Self {
+        duration.into_duration()
+    }
+}
+
+/// Conversion into a [`Rate`](type.Rate.html)
+///
+/// It's implemented for every `fugit` rate with a `u32` raw value, for
+/// [`time::Hertz`](../time/struct.Hertz.html), and for `u32` as a number of hertz.
+pub trait IntoRate {
+    /// Converts `self` into a rate in hertz
+    fn into_rate(self) -> Rate;
+}
+
+impl IntoRate for u32 {
+    fn into_rate(self) -> Rate {
+        Rate::from_raw(self)
+    }
+}
+
+impl<const NOM: u32, const DENOM: u32> IntoRate for fugit_crate::Rate<u32, NOM, DENOM> {
+    fn into_rate(self) -> Rate {
+        Rate::from_raw(self.to_Hz())
+    }
+}
+
+impl IntoRate for Hertz {
+    fn into_rate(self) -> Rate {
+        Rate::from_raw(self.0)
+    }
+}
+
+impl From<Hertz> for Rate {
+    fn from(frequency: Hertz) -> Self {
+        frequency.into_rate()
+    }
+}
+
+/// Typed delays, for implementers of [`DelayUs<u32>`](../blocking/delay/trait.DelayUs.html)
+pub trait DelayExt: DelayUs<u32> {
+    /// Pauses execution for `duration`
+    fn delay<T>(&mut self, duration: T)
+    where
+        T: IntoDuration,
+    {
+        self.delay_us(duration.into_duration().ticks())
+    }
+}
+
+impl<T> DelayExt for T where T: DelayUs<u32> + ?Sized {}
+
+/// Typed timeouts, for implementers of [`CountDown`](../timer/trait.CountDown.html) whose `Time`
+/// can be made from a [`Duration`](type.Duration.html)
+pub trait CountDownExt: CountDown
+where
+    Duration: Into<Self::Time>,
+{
+    /// Starts a new count down of `duration`
+    fn start_duration<T>(&mut self, duration: T)
+    where
+        T: IntoDuration,
+    {
+        self.start(duration.into_duration())
+    }
+}
+
+impl<T> CountDownExt for T
+where
+    T: CountDown + ?Sized,
+    Duration: Into<T::Time>,
+{
+}
+
+/// Typed capture resolutions, for implementers of [`Capture`](../trait.Capture.html) whose `Time`
+/// can be made from a [`Duration`](type.Duration.html)
+///
+/// *This trait is available if embedded-hal is built with the `"capture"` feature.*
+#[cfg(feature = "capture")]
+pub trait CaptureExt: Capture
+where
+    Duration: Into<Self::Time>,
+{
+    /// Sets the resolution of the capture timer, the duration of one count
+    fn set_resolution_duration<T>(&mut self, resolution: T)
+    where
+        T: IntoDuration,
+    {
+        self.set_resolution(resolution.into_duration())
+    }
+}
+
+#[cfg(feature = "capture")]
+impl<T> CaptureExt for T
+where
+    T: Capture + ?Sized,
+    Duration: Into<T::Time>,
+{
+}
+
+/// Typed PWM frequencies, for implementers of [`Pwm`](../trait.Pwm.html) whose `Time` can be
+/// made from a [`Rate`](type.Rate.html)
+///
+/// *This trait is available if embedded-hal is built with the `"pwm"` feature.*
+#[cfg(feature = "pwm")]
+pub trait PwmExt: Pwm
+where
+    Rate: Into<Self::Time>,
+{
+    /// Sets the frequency of the PWM signal, the inverse of its period
+    fn set_frequency<T>(&mut self, frequency: T)
+    where
+        T: IntoRate,
+    {
+        self.set_period(frequency.into_rate())
+    }
+}
+
+#[cfg(feature = "pwm")]
+impl<T> PwmExt for T
+where
+    T: Pwm + ?Sized,
+    Rate: Into<T::Time>,
+{
+}
+
+/// Typed watchdog timeouts, for implementers of
+/// [`WatchdogEnable`](../watchdog/trait.WatchdogEnable.html) whose `Time` can be made from a
+/// [`Duration`](type.Duration.html)
+///
+/// *This trait is available if embedded-hal is built with the `"watchdog"` feature.*
+#[cfg(feature = "watchdog")]
+pub trait WatchdogEnableExt: WatchdogEnable
+where
+    Duration: Into<Self::Time>,
+{
+    /// Starts the watchdog with a timeout of `period`
+    fn start_duration<T>(&mut self, period: T)
+    where
+        T: IntoDuration,
+    {
+        self.start(period.into_duration())
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<T> WatchdogEnableExt for T
+where
+    T: WatchdogEnable + ?Sized,
+    Duration: Into<T::Time>,
+{
+}
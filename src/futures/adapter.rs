@@ -0,0 +1,692 @@
+//! Adapter from the `nb` traits to the asynchronous traits
+//!
+//! HALs that implement the `nb` traits on top of interrupts only need to implement
+//! [`RegisterWaker`] as well to be usable from async code: wrapping the peripheral in [`FromNb`]
+//! provides the asynchronous traits:
+//!
+//! - `futures::serial::Read<Word>` for implementers of `serial::Read<Word>`
+//! - `futures::serial::Write<Word>` for implementers of `serial::Write<Word>`
+//! - `futures::spi::{Transfer<Word>, Write<Word>}` for implementers of `spi::FullDuplex<Word>`
+//! - `futures::digital::Wait` for implementers of `digital::Wait`, with the `"digital-unproven"`
+//!   feature
+//!
+//! `blocking::spi::ExclusiveDevice` also implements `futures::spi::SpiDevice<Word>` if its bus
+//! implements `spi::FullDuplex<Word>` and `RegisterWaker`, without being wrapped.
+//!
+//! The futures returned by the adapter retry the `nb` operation whenever they are polled, and
+//! register the task's waker with the peripheral when it would block, so the task is only polled
+//! again once the interrupt fired.
+//!
+//! [`RegisterWaker`]: ../trait.RegisterWaker.html
+//! [`FromNb`]: struct.FromNb.html
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use nb;
+#[cfg(feature = "digital-unproven")]
+use void::Void;
+
+#[cfg(feature = "digital-unproven")]
+use digital;
+#[cfg(feature = "digital-unproven")]
+use futures::digital as adigital;
+use blocking::spi::{ExclusiveDevice, Operation};
+use digital::OutputPin;
+use futures::RegisterWaker;
+use futures::{serial as aserial, spi as aspi};
+use serial;
+use spi;
+
+/// Asynchronous wrapper around an implementation of the `nb` traits
+///
+/// # Examples
+///
+/// Echoing the bytes received by a serial interface
+///
+/// ```edition2018
+/// use core::task::Waker;
+/// use embedded_hal::futures::adapter::FromNb;
+/// use embedded_hal::futures::serial::{Read, Write};
+/// use embedded_hal::futures::RegisterWaker;
+///
+/// /// A serial interface that implements the `nb` traits, and `RegisterWaker`
+/// struct Serial1 {
+///     // ..
+/// #   rx: &'static [u8],
+/// #   tx: Vec<u8>,
+/// #   waker: Option<Waker>,
+/// }
+///
+/// impl RegisterWaker for Serial1 {
+///     fn register_waker(&mut self, waker: &Waker) {
+///         // (store `waker` for the interrupt handler and enable the interrupts)
+/// #       self.waker = Some(waker.clone());
+///     }
+/// }
+///
+/// async fn echo<S>(serial: &mut S) -> Result<(), S::Error>
+/// where
+///     S: Read<u8> + Write<u8>,
+/// {
+///     let mut buffer = [0; 4];
+///     serial.read(&mut buffer).await?;
+///     serial.write(&buffer).await?;
+///     serial.flush().await
+/// }
+///
+/// fn main() {
+///     let serial: Serial1 = {
+///         // ..
+/// #       Serial1 { rx: b"ping", tx: vec![], waker: None }
+///     };
+///
+///     let mut serial = FromNb::new(serial);
+///     block_on(echo(&mut serial)).unwrap();
+///     assert_eq!(serial.into_inner().tx, b"ping");
+/// }
+///
+/// # use core::future::Future;
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # use embedded_hal::serial::{self, ErrorKind, ErrorType};
+/// # impl ErrorType for Serial1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl serial::Read<u8> for Serial1 {
+/// #     fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+/// #         // a byte arrives every time the waker is registered
+/// #         if self.waker.take().is_none() { return Err(nb::Error::WouldBlock) }
+/// #         let (byte, rest) = self.rx.split_first().ok_or(nb::Error::WouldBlock)?;
+/// #         self.rx = rest;
+/// #         Ok(*byte)
+/// #     }
+/// # }
+/// # impl serial::Write<u8> for Serial1 {
+/// #     fn write(&mut self, byte: u8) -> nb::Result<(), ErrorKind> {
+/// #         self.tx.push(byte);
+/// #         Ok(())
+/// #     }
+/// #     fn flush(&mut self) -> nb::Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// ```
+pub struct FromNb<T> {
+    inner: T,
+}
+
+impl<T> FromNb<T> {
+    /// Wraps `inner`
+    pub fn new(inner: T) -> Self {
+        FromNb { inner }
+    }
+
+    /// Returns a reference to the wrapped implementation
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped implementation
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps the implementation
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Polls the `nb` operation `f`, registering the task's waker if it would block
+fn poll_nb<T, R, E, F>(inner: &mut T, cx: &mut Context, mut f: F) -> Poll<Result<R, E>>
+where
+    T: RegisterWaker,
+    F: FnMut(&mut T) -> nb::Result<R, E>,
+{
+    match f(inner) {
+        Ok(r) => return Poll::Ready(Ok(r)),
+        Err(nb::Error::Other(e)) => return Poll::Ready(Err(e)),
+        Err(nb::Error::WouldBlock) => {}
+    }
+
+    inner.register_waker(cx.waker());
+
+    // the event may have occurred before the waker was registered
+    match f(inner) {
+        Ok(r) => Poll::Ready(Ok(r)),
+        Err(nb::Error::Other(e)) => Poll::Ready(Err(e)),
+        Err(nb::Error::WouldBlock) => Poll::Pending,
+    }
+}
+
+impl<T> serial::ErrorType for FromNb<T>
+where
+    T: serial::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T> spi::ErrorType for FromNb<T>
+where
+    T: spi::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T> RegisterWaker for FromNb<T>
+where
+    T: RegisterWaker,
+{
+    fn register_waker(&mut self, waker: &Waker) {
+        self.inner.register_waker(waker)
+    }
+}
+
+/// Future returned by the `futures::serial::Read` implementation of `FromNb`
+pub struct SerialReadFuture<'a, T, Word>
+where
+    T: 'a,
+    Word: 'a,
+{
+    inner: &'a mut T,
+    buffer: &'a mut [Word],
+    pos: usize,
+}
+
+impl<'a, T, Word> Future for SerialReadFuture<'a, T, Word>
+where
+    T: serial::Read<Word> + RegisterWaker,
+{
+    type Output = Result<(), T::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while this.pos < this.buffer.len() {
+            match poll_nb(this.inner, cx, |inner| inner.read()) {
+                Poll::Ready(Ok(word)) => {
+                    this.buffer[this.pos] = word;
+                    this.pos += 1;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T, Word> aserial::Read<Word> for FromNb<T>
+where
+    T: serial::Read<Word> + RegisterWaker,
+{
+    type ReadFuture<'a>
+        = SerialReadFuture<'a, T, Word>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn read<'a>(&'a mut self, buffer: &'a mut [Word]) -> Self::ReadFuture<'a> {
+        SerialReadFuture {
+            inner: &mut self.inner,
+            buffer,
+            pos: 0,
+        }
+    }
+}
+
+/// Future returned by the `write` method of the `futures::serial::Write` implementation of
+/// `FromNb`
+pub struct SerialWriteFuture<'a, T, Word>
+where
+    T: 'a,
+    Word: 'a,
+{
+    inner: &'a mut T,
+    words: &'a [Word],
+}
+
+impl<'a, T, Word> Future for SerialWriteFuture<'a, T, Word>
+where
+    T: serial::Write<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type Output = Result<(), T::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while let Some((word, rest)) = this.words.split_first() {
+            match poll_nb(this.inner, cx, |inner| inner.write(word.clone())) {
+                Poll::Ready(Ok(())) => this.words = rest,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by the `flush` method of the `futures::serial::Write` implementation of
+/// `FromNb`
+pub struct SerialFlushFuture<'a, T>
+where
+    T: serial::ErrorType + 'a,
+{
+    inner: &'a mut T,
+    // `serial::Write<Word>::flush`; this future doesn't depend on `Word`
+    flush: fn(&mut T) -> nb::Result<(), T::Error>,
+}
+
+impl<'a, T> Future for SerialFlushFuture<'a, T>
+where
+    T: serial::ErrorType + RegisterWaker,
+{
+    type Output = Result<(), T::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        poll_nb(this.inner, cx, this.flush)
+    }
+}
+
+impl<T, Word> aserial::Write<Word> for FromNb<T>
+where
+    T: serial::Write<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type WriteFuture<'a>
+        = SerialWriteFuture<'a, T, Word>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    type FlushFuture<'a>
+        = SerialFlushFuture<'a, T>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [Word]) -> Self::WriteFuture<'a> {
+        SerialWriteFuture {
+            inner: &mut self.inner,
+            words,
+        }
+    }
+
+    fn flush(&mut self) -> Self::FlushFuture<'_> {
+        SerialFlushFuture {
+            inner: &mut self.inner,
+            flush: <T as serial::Write<Word>>::flush,
+        }
+    }
+}
+
+/// Future returned by the `futures::spi::Transfer` implementation of `FromNb`
+pub struct SpiTransferFuture<'a, T, Word>
+where
+    T: 'a,
+    Word: 'a,
+{
+    inner: &'a mut T,
+    words: Option<&'a mut [Word]>,
+    pos: usize,
+    sent: bool,
+}
+
+impl<'a, T, Word> Future for SpiTransferFuture<'a, T, Word>
+where
+    T: spi::FullDuplex<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type Output = Result<&'a [Word], T::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = this.words.as_ref().map_or(0, |words| words.len());
+
+        while this.pos < len {
+            let words = this.words.as_mut().expect("future polled after completion");
+
+            if !this.sent {
+                let word = &words[this.pos];
+                match poll_nb(this.inner, cx, |inner| inner.send(word.clone())) {
+                    Poll::Ready(Ok(())) => this.sent = true,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            match poll_nb(this.inner, cx, |inner| inner.read()) {
+                Poll::Ready(Ok(word)) => {
+                    words[this.pos] = word;
+                    this.pos += 1;
+                    this.sent = false;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let words = this.words.take().expect("future polled after completion");
+        Poll::Ready(Ok(words))
+    }
+}
+
+impl<T, Word> aspi::Transfer<Word> for FromNb<T>
+where
+    T: spi::FullDuplex<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type TransferFuture<'a>
+        = SpiTransferFuture<'a, T, Word>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn transfer<'a>(&'a mut self, words: &'a mut [Word]) -> Self::TransferFuture<'a> {
+        SpiTransferFuture {
+            inner: &mut self.inner,
+            words: Some(words),
+            pos: 0,
+            sent: false,
+        }
+    }
+}
+
+/// Future returned by the `futures::spi::Write` implementation of `FromNb`
+pub struct SpiWriteFuture<'a, T, Word>
+where
+    T: 'a,
+    Word: 'a,
+{
+    inner: &'a mut T,
+    words: &'a [Word],
+    sent: bool,
+}
+
+impl<'a, T, Word> Future for SpiWriteFuture<'a, T, Word>
+where
+    T: spi::FullDuplex<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type Output = Result<(), T::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while let Some((word, rest)) = this.words.split_first() {
+            if !this.sent {
+                match poll_nb(this.inner, cx, |inner| inner.send(word.clone())) {
+                    Poll::Ready(Ok(())) => this.sent = true,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            // each `send` must be followed by a `read`; the received word is discarded
+            match poll_nb(this.inner, cx, |inner| inner.read()) {
+                Poll::Ready(Ok(_)) => {
+                    this.words = rest;
+                    this.sent = false;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T, Word> aspi::Write<Word> for FromNb<T>
+where
+    T: spi::FullDuplex<Word> + RegisterWaker,
+    Word: Clone,
+{
+    type WriteFuture<'a>
+        = SpiWriteFuture<'a, T, Word>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [Word]) -> Self::WriteFuture<'a> {
+        SpiWriteFuture {
+            inner: &mut self.inner,
+            words,
+            sent: false,
+        }
+    }
+}
+
+/// Future returned by the `futures::spi::SpiDevice` implementation of `ExclusiveDevice`
+///
+/// Dropping it before it resolves deasserts the chip select.
+pub struct SpiDeviceTransactionFuture<'a, BUS, CS, Word>
+where
+    BUS: 'a,
+    CS: 'a + OutputPin,
+    Word: 'a,
+{
+    device: &'a mut ExclusiveDevice<BUS, CS>,
+    operations: &'a mut [Operation<'a, Word>],
+    operation: usize,
+    pos: usize,
+    sent: bool,
+    selected: bool,
+}
+
+impl<'a, BUS, CS, Word> SpiDeviceTransactionFuture<'a, BUS, CS, Word>
+where
+    BUS: spi::FullDuplex<Word> + RegisterWaker,
+    CS: OutputPin,
+    Word: Clone,
+{
+    /// Executes the operations until one would block or all are done
+    fn poll_operations(&mut self, cx: &mut Context) -> Poll<Result<(), BUS::Error>> {
+        let (bus, _) = self.device.parts_mut();
+
+        while let Some(operation) = self.operations.get_mut(self.operation) {
+            let words: &[Word] = match *operation {
+                Operation::Write(words) => words,
+                Operation::Transfer(ref words) => words,
+            };
+
+            if self.pos == words.len() {
+                self.operation += 1;
+                self.pos = 0;
+                continue;
+            }
+
+            if !self.sent {
+                let word = &words[self.pos];
+                match poll_nb(bus, cx, |bus| bus.send(word.clone())) {
+                    Poll::Ready(Ok(())) => self.sent = true,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            match poll_nb(bus, cx, |bus| bus.read()) {
+                Poll::Ready(Ok(word)) => {
+                    if let Operation::Transfer(ref mut words) = *operation {
+                        words[self.pos] = word;
+                    }
+                    self.pos += 1;
+                    self.sent = false;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, BUS, CS, Word> Future for SpiDeviceTransactionFuture<'a, BUS, CS, Word>
+where
+    BUS: spi::FullDuplex<Word> + RegisterWaker,
+    CS: OutputPin,
+    Word: Clone,
+{
+    type Output = Result<(), BUS::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if !this.selected {
+            this.device.parts_mut().1.set_low();
+            this.selected = true;
+        }
+
+        let result = match this.poll_operations(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        this.device.parts_mut().1.set_high();
+        // don't deassert the chip select again on drop
+        this.selected = false;
+        Poll::Ready(result)
+    }
+}
+
+impl<'a, BUS, CS, Word> Drop for SpiDeviceTransactionFuture<'a, BUS, CS, Word>
+where
+    CS: OutputPin,
+{
+    fn drop(&mut self) {
+        if self.selected {
+            self.device.parts_mut().1.set_high();
+        }
+    }
+}
+
+impl<BUS, CS, Word> aspi::SpiDevice<Word> for ExclusiveDevice<BUS, CS>
+where
+    BUS: spi::FullDuplex<Word> + RegisterWaker,
+    CS: OutputPin,
+    Word: Clone,
+{
+    type TransactionFuture<'a>
+        = SpiDeviceTransactionFuture<'a, BUS, CS, Word>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn transaction<'a>(
+        &'a mut self,
+        operations: &'a mut [Operation<'a, Word>],
+    ) -> Self::TransactionFuture<'a> {
+        SpiDeviceTransactionFuture {
+            device: self,
+            operations,
+            operation: 0,
+            pos: 0,
+            sent: false,
+            selected: false,
+        }
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> digital::InputPin for FromNb<T>
+where
+    T: digital::InputPin,
+{
+    fn is_high(&self) -> bool {
+        self.inner.is_high()
+    }
+
+    fn is_low(&self) -> bool {
+        self.inner.is_low()
+    }
+}
+
+/// Future returned by the `futures::digital::Wait` implementation of `FromNb`
+///
+/// *This type is available if embedded-hal is built with the `"digital-unproven"` feature.*
+#[cfg(feature = "digital-unproven")]
+pub struct DigitalWaitFuture<'a, T>
+where
+    T: 'a,
+{
+    inner: &'a mut T,
+    // the `digital::Wait` method to retry
+    wait: fn(&mut T) -> nb::Result<(), Void>,
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<'a, T> Future for DigitalWaitFuture<'a, T>
+where
+    T: RegisterWaker,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let this = self.get_mut();
+
+        match poll_nb(this.inner, cx, this.wait) {
+            Poll::Ready(Ok(())) => Poll::Ready(()),
+            Poll::Ready(Err(e)) => match e {},
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> FromNb<T>
+where
+    T: digital::Wait + RegisterWaker,
+{
+    fn wait_future(
+        &mut self,
+        wait: fn(&mut T) -> nb::Result<(), Void>,
+    ) -> DigitalWaitFuture<'_, T> {
+        DigitalWaitFuture {
+            inner: &mut self.inner,
+            wait,
+        }
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl<T> adigital::Wait for FromNb<T>
+where
+    T: digital::Wait + RegisterWaker,
+{
+    type WaitFuture<'a>
+        = DigitalWaitFuture<'a, T>
+    where
+        Self: 'a;
+
+    fn wait_for_high(&mut self) -> Self::WaitFuture<'_> {
+        self.wait_future(T::wait_for_high)
+    }
+
+    fn wait_for_low(&mut self) -> Self::WaitFuture<'_> {
+        self.wait_future(T::wait_for_low)
+    }
+
+    fn wait_for_rising_edge(&mut self) -> Self::WaitFuture<'_> {
+        self.wait_future(T::wait_for_rising_edge)
+    }
+
+    fn wait_for_falling_edge(&mut self) -> Self::WaitFuture<'_> {
+        self.wait_future(T::wait_for_falling_edge)
+    }
+
+    fn wait_for_any_edge(&mut self) -> Self::WaitFuture<'_> {
+        self.wait_future(T::wait_for_any_edge)
+    }
+}
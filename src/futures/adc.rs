@@ -0,0 +1,122 @@
+//! Asynchronous analog-digital conversion
+//!
+//! *This module is available if embedded-hal is built with the `"async"` and `"adc"` features.*
+//!
+//! The traits in this module share the [`adc::ErrorType`] and the [`adc::Channel`] mapping of
+//! pins to channels with the other ADC traits.
+//!
+//! [`adc::ErrorType`]: ../../adc/trait.ErrorType.html
+//! [`adc::Channel`]: ../../adc/trait.Channel.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+use adc::{Channel, ErrorType};
+
+/// Single conversion on a channel
+///
+/// `ADC` is the ADC peripheral the `Pin` is sampled with, and `Word` the type of the conversion
+/// result, e.g. `u16` for a 12-bit ADC.
+///
+/// # Examples
+///
+/// Measuring the voltage of a battery behind a 1:2 voltage divider
+///
+/// ```edition2018
+/// use embedded_hal::adc::Channel;
+/// use embedded_hal::futures::adc::OneShot;
+///
+/// async fn battery_mv<A, P>(adc: &mut A, pin: &mut P) -> Result<u32, A::Error>
+/// where
+///     A: OneShot<Adc1, u16, P>,
+///     P: Channel<Adc1>,
+/// {
+///     let sample = adc.read(pin).await?;
+///
+///     // 12-bit conversion, 3.3 V reference
+///     Ok(u32::from(sample) * 3300 / 4095 * 2)
+/// }
+///
+/// fn main() {
+///     let (mut adc, mut pin): (Adc1, Gpio1Pin1) = {
+///         // ..
+/// #       (Adc1, Gpio1Pin1)
+///     };
+///
+///     assert_eq!(block_on(battery_mv(&mut adc, &mut pin)), Ok(3700));
+/// }
+///
+/// # use core::future::{ready, Future, Ready};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # use embedded_hal::adc::{ErrorKind, ErrorType};
+/// # struct Adc1;
+/// # struct Gpio1Pin1;
+/// # impl Channel<Adc1> for Gpio1Pin1 {
+/// #     type ID = u8;
+/// #     fn channel() -> u8 { 1 }
+/// # }
+/// # impl ErrorType for Adc1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl<P: Channel<Adc1, ID = u8>> OneShot<Adc1, u16, P> for Adc1 {
+/// #     type ReadFuture<'a> = Ready<Result<u16, ErrorKind>> where P: 'a;
+/// #     fn read<'a>(&'a mut self, _: &'a mut P) -> Self::ReadFuture<'a> {
+/// #         ready(if P::channel() == 1 { Ok(2296) } else { Err(ErrorKind::Other) })
+/// #     }
+/// # }
+/// ```
+pub trait OneShot<ADC, Word, Pin>: ErrorType
+where
+    Pin: Channel<ADC>,
+{
+    /// Future returned by `read`
+    type ReadFuture<'a>: Future<Output = Result<Word, Self::Error>> + 'a
+    where
+        Self: 'a,
+        Pin: 'a;
+
+    /// Samples `pin` once, resolving to the conversion result
+    fn read<'a>(&'a mut self, pin: &'a mut Pin) -> Self::ReadFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, ADC, Word, Pin> OneShot<ADC, Word, Pin> for Box<T>
+where
+    T: OneShot<ADC, Word, Pin> + ?Sized,
+    Pin: Channel<ADC>,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a,
+        Pin: 'a;
+
+    fn read<'a>(&'a mut self, pin: &'a mut Pin) -> Self::ReadFuture<'a> {
+        (**self).read(pin)
+    }
+}
+
+impl<T, ADC, Word, Pin> OneShot<ADC, Word, Pin> for &mut T
+where
+    T: OneShot<ADC, Word, Pin> + ?Sized,
+    Pin: Channel<ADC>,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a,
+        Pin: 'a;
+
+    fn read<'a>(&'a mut self, pin: &'a mut Pin) -> Self::ReadFuture<'a> {
+        (**self).read(pin)
+    }
+}
@@ -0,0 +1,96 @@
+//! Asynchronous delays
+//!
+//! Unlike the [`blocking::delay`] traits, these let other tasks run, or the core sleep, during the
+//! delay.
+//!
+//! [`blocking::delay`]: ../../blocking/delay/index.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+/// Millisecond delay
+///
+/// `UXX` denotes the range type of the delay time. `UXX` can be `u8`, `u16`, etc. A single type can
+/// implement this trait for different types of `UXX`.
+pub trait DelayMs<UXX> {
+    /// Future returned by `delay_ms`
+    type DelayFuture<'a>: Future<Output = ()> + 'a
+    where
+        Self: 'a;
+
+    /// Resolves after `ms` milliseconds
+    fn delay_ms(&mut self, ms: UXX) -> Self::DelayFuture<'_>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, UXX> DelayMs<UXX> for Box<T>
+where
+    T: DelayMs<UXX> + ?Sized,
+{
+    type DelayFuture<'a>
+        = T::DelayFuture<'a>
+    where
+        Self: 'a;
+
+    fn delay_ms(&mut self, ms: UXX) -> Self::DelayFuture<'_> {
+        (**self).delay_ms(ms)
+    }
+}
+
+impl<T, UXX> DelayMs<UXX> for &mut T
+where
+    T: DelayMs<UXX> + ?Sized,
+{
+    type DelayFuture<'a>
+        = T::DelayFuture<'a>
+    where
+        Self: 'a;
+
+    fn delay_ms(&mut self, ms: UXX) -> Self::DelayFuture<'_> {
+        (**self).delay_ms(ms)
+    }
+}
+
+/// Microsecond delay
+///
+/// `UXX` denotes the range type of the delay time. `UXX` can be `u8`, `u16`, etc. A single type can
+/// implement this trait for different types of `UXX`.
+pub trait DelayUs<UXX> {
+    /// Future returned by `delay_us`
+    type DelayFuture<'a>: Future<Output = ()> + 'a
+    where
+        Self: 'a;
+
+    /// Resolves after `us` microseconds
+    fn delay_us(&mut self, us: UXX) -> Self::DelayFuture<'_>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, UXX> DelayUs<UXX> for Box<T>
+where
+    T: DelayUs<UXX> + ?Sized,
+{
+    type DelayFuture<'a>
+        = T::DelayFuture<'a>
+    where
+        Self: 'a;
+
+    fn delay_us(&mut self, us: UXX) -> Self::DelayFuture<'_> {
+        (**self).delay_us(us)
+    }
+}
+
+impl<T, UXX> DelayUs<UXX> for &mut T
+where
+    T: DelayUs<UXX> + ?Sized,
+{
+    type DelayFuture<'a>
+        = T::DelayFuture<'a>
+    where
+        Self: 'a;
+
+    fn delay_us(&mut self, us: UXX) -> Self::DelayFuture<'_> {
+        (**self).delay_us(us)
+    }
+}
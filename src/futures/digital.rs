@@ -0,0 +1,167 @@
+//! Asynchronous digital I/O
+//!
+//! *This module is available if embedded-hal is built with the `"async"` and `"digital-unproven"`
+//! features.*
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+use digital::InputPin;
+
+/// Waiting for the level of an input pin, or for edges on it
+///
+/// This lets drivers wait for e.g. a data-ready or interrupt line without polling `is_high`.
+/// Implementations are usually backed by the external interrupt controller of the device. HALs
+/// that implement [`digital::Wait`] and `RegisterWaker` get this trait from
+/// [`FromNb`](../adapter/struct.FromNb.html).
+///
+/// [`digital::Wait`]: ../../digital/trait.Wait.html
+///
+/// # Contract
+///
+/// - `wait_for_high` and `wait_for_low` resolve immediately if the pin already is at the awaited
+///   level.
+/// - The edge futures only resolve on edges that occur after they were first polled.
+///
+/// # Examples
+///
+/// Reading a sample when a sensor signals that it's ready
+///
+/// ```edition2018
+/// use embedded_hal::digital::InputPin;
+/// use embedded_hal::futures::digital::Wait;
+///
+/// async fn wait_until_ready<P>(data_ready: &mut P)
+/// where
+///     P: Wait,
+/// {
+///     // the sensor pulls the line low while a sample is available
+///     data_ready.wait_for_low().await;
+/// }
+///
+/// fn main() {
+///     let mut data_ready: Pin = {
+///         // ..
+/// #       Pin { high: false }
+///     };
+///
+///     block_on(wait_until_ready(&mut data_ready));
+///     assert!(data_ready.is_low());
+/// }
+///
+/// # use core::future::Future;
+/// # use core::pin::Pin as Pinned;
+/// # use core::task::{Context, Poll};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # struct Pin { high: bool }
+/// # impl InputPin for Pin {
+/// #     fn is_high(&self) -> bool { self.high }
+/// #     fn is_low(&self) -> bool { !self.high }
+/// # }
+/// # struct Level(bool, bool);
+/// # impl Future for Level {
+/// #     type Output = ();
+/// #     fn poll(self: Pinned<&mut Self>, _: &mut Context) -> Poll<()> {
+/// #         if self.0 == self.1 { Poll::Ready(()) } else { Poll::Pending }
+/// #     }
+/// # }
+/// # impl Wait for Pin {
+/// #     type WaitFuture<'a> = Level;
+/// #     fn wait_for_high(&mut self) -> Level { Level(self.high, true) }
+/// #     fn wait_for_low(&mut self) -> Level { Level(self.high, false) }
+/// #     fn wait_for_rising_edge(&mut self) -> Level { unimplemented!() }
+/// #     fn wait_for_falling_edge(&mut self) -> Level { unimplemented!() }
+/// #     fn wait_for_any_edge(&mut self) -> Level { unimplemented!() }
+/// # }
+/// ```
+pub trait Wait: InputPin {
+    /// Future returned by the `wait_for_*` methods
+    type WaitFuture<'a>: Future<Output = ()> + 'a
+    where
+        Self: 'a;
+
+    /// Resolves once the pin is high
+    fn wait_for_high(&mut self) -> Self::WaitFuture<'_>;
+
+    /// Resolves once the pin is low
+    fn wait_for_low(&mut self) -> Self::WaitFuture<'_>;
+
+    /// Resolves on the next transition from low to high
+    fn wait_for_rising_edge(&mut self) -> Self::WaitFuture<'_>;
+
+    /// Resolves on the next transition from high to low
+    fn wait_for_falling_edge(&mut self) -> Self::WaitFuture<'_>;
+
+    /// Resolves on the next transition, in either direction
+    fn wait_for_any_edge(&mut self) -> Self::WaitFuture<'_>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Wait for Box<T>
+where
+    T: Wait + ?Sized,
+{
+    type WaitFuture<'a>
+        = T::WaitFuture<'a>
+    where
+        Self: 'a;
+
+    fn wait_for_high(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_high()
+    }
+
+    fn wait_for_low(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_low()
+    }
+
+    fn wait_for_rising_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_rising_edge()
+    }
+
+    fn wait_for_falling_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_falling_edge()
+    }
+
+    fn wait_for_any_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_any_edge()
+    }
+}
+
+impl<T> Wait for &mut T
+where
+    T: Wait + ?Sized,
+{
+    type WaitFuture<'a>
+        = T::WaitFuture<'a>
+    where
+        Self: 'a;
+
+    fn wait_for_high(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_high()
+    }
+
+    fn wait_for_low(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_low()
+    }
+
+    fn wait_for_rising_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_rising_edge()
+    }
+
+    fn wait_for_falling_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_falling_edge()
+    }
+
+    fn wait_for_any_edge(&mut self) -> Self::WaitFuture<'_> {
+        (**self).wait_for_any_edge()
+    }
+}
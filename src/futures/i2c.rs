@@ -0,0 +1,220 @@
+//! Asynchronous I2C
+//!
+//! These traits mirror the ones in the [`blocking::i2c`] module, whose documentation describes the
+//! bus events of each operation, but return futures instead of blocking. They share the
+//! [`i2c::ErrorType`] with the blocking traits, so a bus that implements both defines its error
+//! type once.
+//!
+//! [`blocking::i2c`]: ../../blocking/i2c/index.html
+//! [`i2c::ErrorType`]: ../../i2c/trait.ErrorType.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+use i2c::ErrorType;
+
+/// Read
+///
+/// See [`blocking::i2c::Read`](../../blocking/i2c/trait.Read.html)
+pub trait Read: ErrorType {
+    /// Future returned by `read`
+    type ReadFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Reads enough bytes from slave with `address` to fill `buffer`
+    fn read<'a>(&'a mut self, address: u8, buffer: &'a mut [u8]) -> Self::ReadFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Read for Box<T>
+where
+    T: Read + ?Sized,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn read<'a>(&'a mut self, address: u8, buffer: &'a mut [u8]) -> Self::ReadFuture<'a> {
+        (**self).read(address, buffer)
+    }
+}
+
+impl<T> Read for &mut T
+where
+    T: Read + ?Sized,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn read<'a>(&'a mut self, address: u8, buffer: &'a mut [u8]) -> Self::ReadFuture<'a> {
+        (**self).read(address, buffer)
+    }
+}
+
+/// Write
+///
+/// See [`blocking::i2c::Write`](../../blocking/i2c/trait.Write.html)
+pub trait Write: ErrorType {
+    /// Future returned by `write`
+    type WriteFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Sends `bytes` to slave with address `address`
+    fn write<'a>(&'a mut self, address: u8, bytes: &'a [u8]) -> Self::WriteFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Write for Box<T>
+where
+    T: Write + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, address: u8, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(address, bytes)
+    }
+}
+
+impl<T> Write for &mut T
+where
+    T: Write + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, address: u8, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(address, bytes)
+    }
+}
+
+/// Write + read
+///
+/// See [`blocking::i2c::WriteRead`](../../blocking/i2c/trait.WriteRead.html)
+///
+/// # Examples
+///
+/// Reading a register of a sensor
+///
+/// ```edition2018
+/// use embedded_hal::futures::i2c::WriteRead;
+///
+/// const ADDRESS: u8 = 0x48;
+/// const TEMPERATURE: u8 = 0x00;
+///
+/// async fn temperature<I>(i2c: &mut I) -> Result<i16, I::Error>
+/// where
+///     I: WriteRead,
+/// {
+///     let mut buffer = [0; 2];
+///     // other tasks run while the bytes are being transferred
+///     i2c.write_read(ADDRESS, &[TEMPERATURE], &mut buffer).await?;
+///
+///     Ok(i16::from_be_bytes(buffer) >> 4)
+/// }
+///
+/// fn main() {
+///     let mut i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     assert_eq!(block_on(temperature(&mut i2c)), Ok(0x190));
+/// }
+///
+/// # use core::future::{ready, Future, Ready};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource};
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl WriteRead for I2c1 {
+/// #     type WriteReadFuture<'a> = Ready<Result<(), ErrorKind>>;
+/// #     fn write_read<'a>(
+/// #         &'a mut self,
+/// #         address: u8,
+/// #         bytes: &'a [u8],
+/// #         buffer: &'a mut [u8],
+/// #     ) -> Self::WriteReadFuture<'a> {
+/// #         if address != ADDRESS {
+/// #             return ready(Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)));
+/// #         }
+/// #         assert_eq!(bytes, [TEMPERATURE]);
+/// #         buffer.copy_from_slice(&[0x19, 0x00]);
+/// #         ready(Ok(()))
+/// #     }
+/// # }
+/// ```
+pub trait WriteRead: ErrorType {
+    /// Future returned by `write_read`
+    type WriteReadFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Sends `bytes` and then reads enough bytes to fill `buffer`, with a repeated start condition
+    /// in between, in a single transaction with slave `address`
+    fn write_read<'a>(
+        &'a mut self,
+        address: u8,
+        bytes: &'a [u8],
+        buffer: &'a mut [u8],
+    ) -> Self::WriteReadFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WriteRead for Box<T>
+where
+    T: WriteRead + ?Sized,
+{
+    type WriteReadFuture<'a>
+        = T::WriteReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn write_read<'a>(
+        &'a mut self,
+        address: u8,
+        bytes: &'a [u8],
+        buffer: &'a mut [u8],
+    ) -> Self::WriteReadFuture<'a> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
+
+impl<T> WriteRead for &mut T
+where
+    T: WriteRead + ?Sized,
+{
+    type WriteReadFuture<'a>
+        = T::WriteReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn write_read<'a>(
+        &'a mut self,
+        address: u8,
+        bytes: &'a [u8],
+        buffer: &'a mut [u8],
+    ) -> Self::WriteReadFuture<'a> {
+        (**self).write_read(address, bytes, buffer)
+    }
+}
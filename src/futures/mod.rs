@@ -0,0 +1,115 @@
+//! Asynchronous API
+//!
+//! *This module is available if embedded-hal is built with the `"async"` feature.*
+//!
+//! The traits in this module return `core::future::Future`s that resolve once the operation has
+//! completed. They are meant to be implemented on top of completion interrupts (or DMA), so that
+//! executors can run other tasks, or put the core to sleep, while the hardware is busy.
+//!
+//! Each trait exposes the futures it returns as generic associated types. Implementations can name
+//! a hand-written future, or any other `Future` implementation, there.
+//!
+//! The submodules mirror the blocking and `nb` APIs, and their traits use the same error types,
+//! e.g. [`futures::spi`] traits inherit from [`spi::ErrorType`]. A peripheral can then implement
+//! the asynchronous traits next to the blocking ones without defining a new error type.
+//!
+//! [`futures::spi`]: spi/index.html
+//! [`spi::ErrorType`]: ../spi/trait.ErrorType.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::task::Waker;
+
+pub mod adapter;
+#[cfg(feature = "adc")]
+pub mod adc;
+pub mod delay;
+#[cfg(feature = "digital-unproven")]
+pub mod digital;
+pub mod i2c;
+pub mod serial;
+pub mod spi;
+#[cfg(feature = "storage")]
+pub mod storage;
+
+/// Registration of a waker to be notified of progress
+///
+/// This trait bridges interrupt-driven implementations of the `nb` traits, or of `dma::Transfer`
+/// (with the `"dma"` feature), and async executors: a future that got `WouldBlock`, or a transfer
+/// that's not done, registers the waker of its task and is polled again once the hardware has
+/// made progress. `Waker`s are not tied to any executor; code that doesn't use one can build a
+/// `Waker` that runs a callback with `core::task::RawWaker`.
+///
+/// # Contract
+///
+/// - Once registered, the waker MUST be woken when an event that may let a pending operation make
+///   progress occurs, e.g. from the interrupt handler. Spurious wake-ups are allowed.
+/// - Only the most recently registered waker needs to be woken.
+///
+/// Users MUST retry the operation *after* registering the waker, as the event may have occurred
+/// in between.
+///
+/// # Examples
+///
+/// A serial receiver that wakes the task waiting for data from its interrupt handler
+///
+/// ```edition2018
+/// use core::task::Waker;
+/// use embedded_hal::futures::RegisterWaker;
+///
+/// struct Rx {
+///     waker: Option<Waker>,
+///     // ..
+/// }
+///
+/// impl RegisterWaker for Rx {
+///     fn register_waker(&mut self, waker: &Waker) {
+///         match self.waker {
+///             Some(ref old) if old.will_wake(waker) => {}
+///             _ => self.waker = Some(waker.clone()),
+///         }
+///         // (enable the RX-not-empty interrupt)
+///     }
+/// }
+///
+/// impl Rx {
+///     /// Called from the USART interrupt handler
+///     fn on_interrupt(&mut self) {
+///         // (disable the interrupt)
+///         if let Some(waker) = self.waker.take() {
+///             waker.wake();
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let mut rx = Rx { waker: None };
+///
+///     rx.register_waker(Waker::noop());
+///     rx.on_interrupt();
+///     assert!(rx.waker.is_none());
+/// }
+/// ```
+pub trait RegisterWaker {
+    /// Registers `waker` to be woken when the hardware makes progress
+    fn register_waker(&mut self, waker: &Waker);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RegisterWaker for Box<T>
+where
+    T: RegisterWaker + ?Sized,
+{
+    fn register_waker(&mut self, waker: &Waker) {
+        (**self).register_waker(waker)
+    }
+}
+
+impl<T> RegisterWaker for &mut T
+where
+    T: RegisterWaker + ?Sized,
+{
+    fn register_waker(&mut self, waker: &Waker) {
+        (**self).register_waker(waker)
+    }
+}
@@ -0,0 +1,132 @@
+//! Asynchronous serial interface
+//!
+//! These traits read and write whole buffers, and resolve once they are done, instead of a word
+//! at a time like the [`serial`] traits. They share the [`serial::ErrorType`] with the blocking
+//! and `nb` traits, so a serial interface that implements several of them defines its error type
+//! once.
+//!
+//! [`serial`]: ../../serial/index.html
+//! [`serial::ErrorType`]: ../../serial/trait.ErrorType.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+use serial::ErrorType;
+
+/// Read half of a serial interface
+pub trait Read<Word>: ErrorType {
+    /// Future returned by `read`
+    type ReadFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a,
+        Word: 'a;
+
+    /// Reads enough words to fill `buffer`
+    fn read<'a>(&'a mut self, buffer: &'a mut [Word]) -> Self::ReadFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, Word> Read<Word> for Box<T>
+where
+    T: Read<Word> + ?Sized,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn read<'a>(&'a mut self, buffer: &'a mut [Word]) -> Self::ReadFuture<'a> {
+        (**self).read(buffer)
+    }
+}
+
+impl<T, Word> Read<Word> for &mut T
+where
+    T: Read<Word> + ?Sized,
+{
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    fn read<'a>(&'a mut self, buffer: &'a mut [Word]) -> Self::ReadFuture<'a> {
+        (**self).read(buffer)
+    }
+}
+
+/// Write half of a serial interface
+pub trait Write<Word>: ErrorType {
+    /// Future returned by `write`
+    type WriteFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a,
+        Word: 'a;
+
+    /// Future returned by `flush`
+    type FlushFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Writes all the `words`
+    ///
+    /// Like the blocking [`bwrite_all`], this may resolve once the words have been buffered but
+    /// before they have all been sent; use `flush` to wait for that.
+    ///
+    /// [`bwrite_all`]: ../../blocking/serial/trait.Write.html#tymethod.bwrite_all
+    fn write<'a>(&'a mut self, words: &'a [Word]) -> Self::WriteFuture<'a>;
+
+    /// Ensures that none of the previously written words are still buffered
+    fn flush(&mut self) -> Self::FlushFuture<'_>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, Word> Write<Word> for Box<T>
+where
+    T: Write<Word> + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    type FlushFuture<'a>
+        = T::FlushFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [Word]) -> Self::WriteFuture<'a> {
+        (**self).write(words)
+    }
+
+    fn flush(&mut self) -> Self::FlushFuture<'_> {
+        (**self).flush()
+    }
+}
+
+impl<T, Word> Write<Word> for &mut T
+where
+    T: Write<Word> + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a,
+        Word: 'a;
+
+    type FlushFuture<'a>
+        = T::FlushFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [Word]) -> Self::WriteFuture<'a> {
+        (**self).write(words)
+    }
+
+    fn flush(&mut self) -> Self::FlushFuture<'_> {
+        (**self).flush()
+    }
+}
@@ -0,0 +1,237 @@
+//! Asynchronous SPI
+//!
+//! These traits mirror the ones in the [`blocking::spi`] module but return futures instead of
+//! blocking, e.g. while a DMA transfer is in progress. They share the [`spi::ErrorType`] with the
+//! blocking and `nb` traits, so a bus that implements several of them defines its error type
+//! once.
+//!
+//! [`blocking::spi`]: ../../blocking/spi/index.html
+//! [`spi::ErrorType`]: ../../spi/trait.ErrorType.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+pub use blocking::spi::Operation;
+use spi::ErrorType;
+
+/// Transfer
+///
+/// See [`blocking::spi::Transfer`](../../blocking/spi/trait.Transfer.html)
+pub trait Transfer<W>: ErrorType {
+    /// Future returned by `transfer`
+    type TransferFuture<'a>: Future<Output = Result<&'a [W], Self::Error>> + 'a
+    where
+        Self: 'a,
+        W: 'a;
+
+    /// Sends `words` to the slave. Resolves to the `words` received from the slave
+    fn transfer<'a>(&'a mut self, words: &'a mut [W]) -> Self::TransferFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, W> Transfer<W> for Box<T>
+where
+    T: Transfer<W> + ?Sized,
+{
+    type TransferFuture<'a>
+        = T::TransferFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn transfer<'a>(&'a mut self, words: &'a mut [W]) -> Self::TransferFuture<'a> {
+        (**self).transfer(words)
+    }
+}
+
+impl<T, W> Transfer<W> for &mut T
+where
+    T: Transfer<W> + ?Sized,
+{
+    type TransferFuture<'a>
+        = T::TransferFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn transfer<'a>(&'a mut self, words: &'a mut [W]) -> Self::TransferFuture<'a> {
+        (**self).transfer(words)
+    }
+}
+
+/// Write
+///
+/// See [`blocking::spi::Write`](../../blocking/spi/trait.Write.html)
+pub trait Write<W>: ErrorType {
+    /// Future returned by `write`
+    type WriteFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a,
+        W: 'a;
+
+    /// Sends `words` to the slave, ignoring all the incoming words
+    fn write<'a>(&'a mut self, words: &'a [W]) -> Self::WriteFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, W> Write<W> for Box<T>
+where
+    T: Write<W> + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [W]) -> Self::WriteFuture<'a> {
+        (**self).write(words)
+    }
+}
+
+impl<T, W> Write<W> for &mut T
+where
+    T: Write<W> + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn write<'a>(&'a mut self, words: &'a [W]) -> Self::WriteFuture<'a> {
+        (**self).write(words)
+    }
+}
+
+/// SPI device, which manages its chip select
+///
+/// See [`blocking::spi::SpiDevice`](../../blocking/spi/trait.SpiDevice.html), whose contract
+/// applies. [`ExclusiveDevice`] implements this trait for buses that implement the `nb`
+/// [`spi::FullDuplex`] trait and [`RegisterWaker`].
+///
+/// [`ExclusiveDevice`]: ../../blocking/spi/struct.ExclusiveDevice.html
+/// [`spi::FullDuplex`]: ../../spi/trait.FullDuplex.html
+/// [`RegisterWaker`]: ../trait.RegisterWaker.html
+///
+/// # Examples
+///
+/// An asynchronous driver, used with an interrupt-driven bus
+///
+/// ```edition2018
+/// use embedded_hal::blocking::spi::ExclusiveDevice;
+/// use embedded_hal::futures::spi::{Operation, SpiDevice};
+///
+/// struct Bme280<SPI> {
+///     spi: SPI,
+/// }
+///
+/// impl<SPI> Bme280<SPI>
+/// where
+///     SPI: SpiDevice<u8>,
+/// {
+///     async fn id(&mut self) -> Result<u8, SPI::Error> {
+///         let mut id = [0];
+///         self.spi
+///             .transaction(&mut [Operation::Write(&[0xd0]), Operation::Transfer(&mut id)])
+///             .await?;
+///         Ok(id[0])
+///     }
+/// }
+///
+/// fn main() {
+///     let (spi, cs): (Spi1, Pa4) = {
+///         // ..
+/// #       (Spi1 { rx: None }, Pa4 { high: false })
+///     };
+///
+///     let mut bme280 = Bme280 {
+///         spi: ExclusiveDevice::new(spi, cs),
+///     };
+///     assert_eq!(block_on(bme280.id()), Ok(0x60));
+/// #   assert!(bme280.spi.into_inner().1.high);
+/// }
+///
+/// # use core::future::Future;
+/// # use core::task::Waker;
+/// # use embedded_hal::digital::OutputPin;
+/// # use embedded_hal::futures::RegisterWaker;
+/// # use embedded_hal::spi::{ErrorKind, ErrorType, FullDuplex};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # struct Spi1 { rx: Option<u8> }
+/// # impl ErrorType for Spi1 { type Error = ErrorKind; }
+/// # impl FullDuplex<u8> for Spi1 {
+/// #     fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+/// #         self.rx.take().ok_or(nb::Error::WouldBlock)
+/// #     }
+/// #     fn send(&mut self, word: u8) -> nb::Result<(), ErrorKind> {
+/// #         self.rx = Some(if word == 0xd0 { 0 } else { 0x60 });
+/// #         Ok(())
+/// #     }
+/// # }
+/// # impl RegisterWaker for Spi1 { fn register_waker(&mut self, _: &Waker) {} }
+/// # struct Pa4 { high: bool }
+/// # impl OutputPin for Pa4 {
+/// #     fn set_low(&mut self) { self.high = false }
+/// #     fn set_high(&mut self) { self.high = true }
+/// # }
+/// ```
+pub trait SpiDevice<W>: ErrorType {
+    /// Future returned by `transaction`
+    type TransactionFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a,
+        W: 'a;
+
+    /// Executes `operations` in a single transaction, with the device selected
+    fn transaction<'a>(
+        &'a mut self,
+        operations: &'a mut [Operation<'a, W>],
+    ) -> Self::TransactionFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, W> SpiDevice<W> for Box<T>
+where
+    T: SpiDevice<W> + ?Sized,
+{
+    type TransactionFuture<'a>
+        = T::TransactionFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn transaction<'a>(
+        &'a mut self,
+        operations: &'a mut [Operation<'a, W>],
+    ) -> Self::TransactionFuture<'a> {
+        (**self).transaction(operations)
+    }
+}
+
+impl<T, W> SpiDevice<W> for &mut T
+where
+    T: SpiDevice<W> + ?Sized,
+{
+    type TransactionFuture<'a>
+        = T::TransactionFuture<'a>
+    where
+        Self: 'a,
+        W: 'a;
+
+    fn transaction<'a>(
+        &'a mut self,
+        operations: &'a mut [Operation<'a, W>],
+    ) -> Self::TransactionFuture<'a> {
+        (**self).transaction(operations)
+    }
+}
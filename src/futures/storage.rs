@@ -0,0 +1,295 @@
+//! Asynchronous non-volatile storage
+//!
+//! *This module is available if embedded-hal is built with the `"async"` and `"storage"` features.*
+//!
+//! These traits mirror the ones in the [`storage`] module, whose documentation describes the
+//! semantics of each operation, but return futures instead of blocking. Erasing NOR flash takes
+//! several milliseconds, and so do EEPROM writes; these are the operations that benefit the most
+//! from being awaited.
+//!
+//! [`storage`]: ../../storage/index.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::future::Future;
+
+use storage::ErrorType;
+
+/// Read-only NOR flash
+///
+/// See [`storage::nor_flash::ReadNorFlash`](../../storage/nor_flash/trait.ReadNorFlash.html)
+pub trait ReadNorFlash: ErrorType {
+    /// The minimum number of bytes the device can read; `offset` and the length of the buffer
+    /// passed to `read` must be multiples of it
+    const READ_SIZE: usize;
+
+    /// Future returned by `read`
+    type ReadFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Reads enough bytes, starting at `offset`, to fill `bytes`
+    fn read<'a>(&'a mut self, offset: u32, bytes: &'a mut [u8]) -> Self::ReadFuture<'a>;
+
+    /// Returns the capacity of the device, in bytes
+    fn capacity(&self) -> usize;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ReadNorFlash for Box<T>
+where
+    T: ReadNorFlash + ?Sized,
+{
+    const READ_SIZE: usize = T::READ_SIZE;
+
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn read<'a>(&'a mut self, offset: u32, bytes: &'a mut [u8]) -> Self::ReadFuture<'a> {
+        (**self).read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+impl<T> ReadNorFlash for &mut T
+where
+    T: ReadNorFlash + ?Sized,
+{
+    const READ_SIZE: usize = T::READ_SIZE;
+
+    type ReadFuture<'a>
+        = T::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    fn read<'a>(&'a mut self, offset: u32, bytes: &'a mut [u8]) -> Self::ReadFuture<'a> {
+        (**self).read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+/// NOR flash that can be erased and programmed
+///
+/// See [`storage::nor_flash::NorFlash`](../../storage/nor_flash/trait.NorFlash.html)
+///
+/// # Examples
+///
+/// Persisting a configuration record in the last block of a device
+///
+/// ```edition2018
+/// use embedded_hal::futures::storage::{NorFlash, ReadNorFlash};
+///
+/// async fn save<F>(flash: &mut F, record: &[u8]) -> Result<(), F::Error>
+/// where
+///     F: NorFlash,
+/// {
+///     let to = flash.capacity() as u32;
+///     let from = to - F::ERASE_SIZE as u32;
+///
+///     // other tasks run while the block is being erased
+///     flash.erase(from, to).await?;
+///     flash.write(from, record).await
+/// }
+///
+/// fn main() {
+///     let mut flash: Flash = {
+///         // ..
+/// #       Flash([0; 8192])
+///     };
+///
+///     block_on(save(&mut flash, b"config-v1\0\0\0")).unwrap();
+///
+///     let mut record = [0; 12];
+///     block_on(flash.read(8192 - 4096, &mut record)).unwrap();
+///     assert_eq!(&record, b"config-v1\0\0\0");
+/// }
+///
+/// # use core::future::{ready, Future, Ready};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = core::pin::pin!(future);
+/// #     let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// #     loop {
+/// #         if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # use embedded_hal::storage::{ErrorKind, ErrorType};
+/// # struct Flash([u8; 8192]);
+/// # impl ErrorType for Flash {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl ReadNorFlash for Flash {
+/// #     const READ_SIZE: usize = 1;
+/// #     type ReadFuture<'a> = Ready<Result<(), ErrorKind>>;
+/// #     fn read<'a>(&'a mut self, offset: u32, bytes: &'a mut [u8]) -> Self::ReadFuture<'a> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         ready(Ok(()))
+/// #     }
+/// #     fn capacity(&self) -> usize { self.0.len() }
+/// # }
+/// # impl NorFlash for Flash {
+/// #     const WRITE_SIZE: usize = 4;
+/// #     const ERASE_SIZE: usize = 4096;
+/// #     type EraseFuture<'a> = Ready<Result<(), ErrorKind>>;
+/// #     type WriteFuture<'a> = Ready<Result<(), ErrorKind>>;
+/// #     fn erase(&mut self, from: u32, to: u32) -> Self::EraseFuture<'_> {
+/// #         for byte in &mut self.0[from as usize..to as usize] { *byte = 0xff }
+/// #         ready(Ok(()))
+/// #     }
+/// #     fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+/// #         let offset = offset as usize;
+/// #         for (cell, byte) in self.0[offset..offset + bytes.len()].iter_mut().zip(bytes) {
+/// #             *cell &= *byte;
+/// #         }
+/// #         ready(Ok(()))
+/// #     }
+/// # }
+/// ```
+pub trait NorFlash: ReadNorFlash {
+    /// The minimum number of bytes the device can program; `offset` and the length of the buffer
+    /// passed to `write` must be multiples of it
+    const WRITE_SIZE: usize;
+
+    /// The minimum number of bytes the device can erase; `from` and `to` passed to `erase` must be
+    /// multiples of it
+    const ERASE_SIZE: usize;
+
+    /// Future returned by `erase`
+    type EraseFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Future returned by `write`
+    type WriteFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Erases the bytes in the range `from .. to`
+    fn erase(&mut self, from: u32, to: u32) -> Self::EraseFuture<'_>;
+
+    /// Programs `bytes` starting at `offset`
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NorFlash for Box<T>
+where
+    T: NorFlash + ?Sized,
+{
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+    const ERASE_SIZE: usize = T::ERASE_SIZE;
+
+    type EraseFuture<'a>
+        = T::EraseFuture<'a>
+    where
+        Self: 'a;
+
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn erase(&mut self, from: u32, to: u32) -> Self::EraseFuture<'_> {
+        (**self).erase(from, to)
+    }
+
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(offset, bytes)
+    }
+}
+
+impl<T> NorFlash for &mut T
+where
+    T: NorFlash + ?Sized,
+{
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+    const ERASE_SIZE: usize = T::ERASE_SIZE;
+
+    type EraseFuture<'a>
+        = T::EraseFuture<'a>
+    where
+        Self: 'a;
+
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn erase(&mut self, from: u32, to: u32) -> Self::EraseFuture<'_> {
+        (**self).erase(from, to)
+    }
+
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(offset, bytes)
+    }
+}
+
+/// Marker trait that indicates that a NOR flash allows programming the same location several times
+/// between erases
+///
+/// See [`storage::nor_flash::MultiwriteNorFlash`][multiwrite]
+///
+/// [multiwrite]: ../../storage/nor_flash/trait.MultiwriteNorFlash.html
+pub trait MultiwriteNorFlash: NorFlash {}
+
+#[cfg(feature = "alloc")]
+impl<T: MultiwriteNorFlash + ?Sized> MultiwriteNorFlash for Box<T> {}
+
+impl<T: MultiwriteNorFlash + ?Sized> MultiwriteNorFlash for &mut T {}
+
+/// Byte-addressable EEPROM
+///
+/// Reads are provided by the [`ReadNorFlash`] supertrait, which EEPROMs implement with a
+/// `READ_SIZE` of 1.
+///
+/// See [`storage::eeprom::Eeprom`](../../storage/eeprom/trait.Eeprom.html)
+///
+/// [`ReadNorFlash`]: trait.ReadNorFlash.html
+pub trait Eeprom: ReadNorFlash {
+    /// Future returned by `write`
+    type WriteFuture<'a>: Future<Output = Result<(), Self::Error>> + 'a
+    where
+        Self: 'a;
+
+    /// Writes `bytes` starting at `offset`
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Eeprom for Box<T>
+where
+    T: Eeprom + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(offset, bytes)
+    }
+}
+
+impl<T> Eeprom for &mut T
+where
+    T: Eeprom + ?Sized,
+{
+    type WriteFuture<'a>
+        = T::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn write<'a>(&'a mut self, offset: u32, bytes: &'a [u8]) -> Self::WriteFuture<'a> {
+        (**self).write(offset, bytes)
+    }
+}
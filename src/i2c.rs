@@ -0,0 +1,253 @@
+//! Inter-Integrated Circuit
+//!
+//! The I2C traits live in the [`blocking::i2c`] module; this module contains what they share.
+//!
+//! [`blocking::i2c`]: ../blocking/i2c/index.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use void::Void;
+
+/// I2C error kind
+///
+/// This represents a common set of I2C operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// Bus error occurred, e.g. a misplaced start or stop condition
+    Bus,
+    /// The arbitration was lost, e.g. another master took over the bus
+    ArbitrationLoss,
+    /// The slave did not acknowledge its address or a data byte
+    NoAcknowledge(NoAcknowledgeSource),
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::Bus => f.write_str("bus error"),
+            ErrorKind::ArbitrationLoss => f.write_str("the arbitration was lost"),
+            ErrorKind::NoAcknowledge(source) => fmt::Display::fmt(&source, f),
+            ErrorKind::Overrun => f.write_str("the peripheral receive buffer was overrun"),
+            ErrorKind::Other => f.write_str("a different error occurred"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// What the slave did not acknowledge, in an [`ErrorKind::NoAcknowledge`] error
+///
+/// A missing address acknowledge usually means that there's no device at that address, or that
+/// it's busy, e.g. an EEPROM completing a write, whereas a missing data acknowledge usually means
+/// that the device rejected the command or the data.
+///
+/// [`ErrorKind::NoAcknowledge`]: enum.ErrorKind.html#variant.NoAcknowledge
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum NoAcknowledgeSource {
+    /// The slave did not acknowledge its address
+    Address,
+    /// The slave did not acknowledge a data byte
+    Data,
+    /// The peripheral can't tell whether the address or a data byte was not acknowledged
+    Unknown,
+}
+
+impl fmt::Display for NoAcknowledgeSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            NoAcknowledgeSource::Address => "the slave did not acknowledge its address",
+            NoAcknowledgeSource::Data => "the slave did not acknowledge a data byte",
+            NoAcknowledgeSource::Unknown => {
+                "the slave did not acknowledge its address or a data byte"
+            }
+        })
+    }
+}
+
+/// I2C error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+///
+/// # Examples
+///
+/// Telling a missing device apart from a bus fault
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::i2c::Read;
+/// use hal::i2c::{Error, ErrorKind, NoAcknowledgeSource};
+///
+/// /// Returns `Ok(None)` if no device answers at `address`
+/// fn probe<I>(i2c: &mut I, address: u8) -> Result<Option<u8>, I::Error>
+/// where
+///     I: Read,
+/// {
+///     let mut byte = [0];
+///     match i2c.read(address, &mut byte) {
+///         Ok(()) => Ok(Some(byte[0])),
+///         Err(ref e) if e.kind() == ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => {
+///             Ok(None)
+///         }
+///         Err(e) => Err(e),
+///     }
+/// }
+///
+/// fn main() {
+///     let mut i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     assert_eq!(probe(&mut i2c, 0x48).unwrap(), Some(0x2a));
+///     assert_eq!(probe(&mut i2c, 0x50).unwrap(), None);
+/// }
+///
+/// # use hal::i2c::ErrorType;
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl Read for I2c1 {
+/// #     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         if address != 0x48 {
+/// #             return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+/// #         }
+/// #         buffer[0] = 0x2a;
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// I2C error type
+///
+/// All the I2C traits inherit from this trait so that an I2C interface defines its error type
+/// only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Address mode: 7-bit or 10-bit addresses
+///
+/// The blocking I2C traits are generic over it, with 7-bit addresses as the default. It's
+/// implemented for [`SevenBitAddress`] and [`TenBitAddress`]. Implementations that support both
+/// implement the traits twice; the address of a call then needs a type, e.g. `0x48u8`.
+///
+/// [`SevenBitAddress`]: type.SevenBitAddress.html
+/// [`TenBitAddress`]: type.TenBitAddress.html
+///
+/// # Examples
+///
+/// A driver of a device with a 10-bit address
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::i2c::Write;
+/// use hal::i2c::TenBitAddress;
+///
+/// const ADDRESS: TenBitAddress = 0x2a5;
+///
+/// fn reset<I>(i2c: &mut I) -> Result<(), I::Error>
+/// where
+///     I: Write<TenBitAddress>,
+/// {
+///     i2c.write(ADDRESS, &[0x01])
+/// }
+///
+/// fn main() {
+///     let mut i2c: I2c1 = {
+///         // ..
+/// #       I2c1
+///     };
+///
+///     reset(&mut i2c).unwrap();
+///     // a 7-bit address needs a type, as `I2c1` supports both modes
+///     i2c.write(0x48u8, &[0x00]).unwrap();
+/// }
+///
+/// # use hal::i2c::{ErrorKind, ErrorType, SevenBitAddress};
+/// # struct I2c1;
+/// # impl ErrorType for I2c1 { type Error = ErrorKind; }
+/// # impl Write for I2c1 {
+/// #     fn write(&mut self, _: SevenBitAddress, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// # impl Write<TenBitAddress> for I2c1 {
+/// #     fn write(&mut self, _: TenBitAddress, _: &[u8]) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+/// ```
+pub trait AddressMode: Copy {}
+
+/// 7-bit address, right-aligned, without the R/W bit
+pub type SevenBitAddress = u8;
+
+/// 10-bit address, right-aligned, without the R/W bit
+pub type TenBitAddress = u16;
+
+impl AddressMode for SevenBitAddress {}
+
+impl AddressMode for TenBitAddress {}
@@ -0,0 +1,248 @@
+//! Inter-IC Sound (I2S) and other digital audio interfaces
+//!
+//! *This module is available if embedded-hal is built with the `"i2s"` feature.*
+//!
+//! Digital audio interfaces stream PCM samples to audio codecs, e.g. the SGTL5000 or the WM8731,
+//! and DACs, and from codecs and MEMS microphones, e.g. the INMP441. The samples are transferred
+//! in stereo frames, `[left, right]`; a mono device only uses one of the channels, the other one
+//! being ignored when writing and unspecified when reading.
+//!
+//! `Word` is the sample type, usually `i16` or `i32`. Samples narrower than `Word`, e.g. 24-bit
+//! samples in an `i32`, are left-aligned: their most significant bit is the one of `Word`, so
+//! that the scale of the samples doesn't depend on their width.
+//!
+//! The traits of this module are non-blocking and transfer one frame at a time; the
+//! [`blocking::i2s`] traits transfer buffers of frames.
+//!
+//! [`blocking::i2s`]: ../blocking/i2s/index.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// I2S error kind
+///
+/// This represents a common set of I2S operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The interface doesn't support the requested sample rate, standard or sample width
+    Unsupported,
+    /// A frame was lost because it was not read in a timely manner
+    Overrun,
+    /// A frame was missing because it was not written in a timely manner
+    Underrun,
+    /// The word select line changed at an unexpected time, e.g. in slave mode
+    FrameError,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Unsupported => "the sample rate, standard or sample width is not supported",
+            ErrorKind::Overrun => "a frame was lost because it was not read in time",
+            ErrorKind::Underrun => "a frame was missing because it was not written in time",
+            ErrorKind::FrameError => "the word select line changed at an unexpected time",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// I2S error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// I2S error type
+///
+/// All the I2S traits, non-blocking and blocking, inherit from this trait so that an interface
+/// defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Framing of the samples on the data line
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Standard {
+    /// Philips I2S: the most significant bit of a sample is sent one clock cycle after word
+    /// select changes, and word select is low for the left channel
+    Philips,
+    /// Left-justified, or MSB-justified: the most significant bit of a sample is sent as soon as
+    /// word select changes, and word select is high for the left channel
+    LeftJustified,
+    /// Right-justified, or LSB-justified: the least significant bit of a sample is sent right
+    /// before word select changes, and word select is high for the left channel
+    RightJustified,
+}
+
+/// Interface configuration
+pub trait Configure: ErrorType {
+    /// Sets the sample rate, in frames per second, e.g. `48_000`
+    ///
+    /// Returns an error if the interface can't generate the clocks for `hz`
+    fn set_sample_rate(&mut self, hz: u32) -> Result<(), Self::Error>;
+
+    /// Sets the framing `standard` and the width of the samples, in bits, e.g. `24`
+    ///
+    /// Returns an error if the interface doesn't support `standard` or `bits`
+    fn set_format(&mut self, standard: Standard, bits: u8) -> Result<(), Self::Error>;
+
+    /// Returns the actual sample rate, in frames per second, which may differ slightly from the
+    /// requested one
+    fn sample_rate(&self) -> u32;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Configure for Box<T>
+where
+    T: Configure + ?Sized,
+{
+    fn set_sample_rate(&mut self, hz: u32) -> Result<(), Self::Error> {
+        (**self).set_sample_rate(hz)
+    }
+
+    fn set_format(&mut self, standard: Standard, bits: u8) -> Result<(), Self::Error> {
+        (**self).set_format(standard, bits)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+}
+
+impl<T> Configure for &mut T
+where
+    T: Configure + ?Sized,
+{
+    fn set_sample_rate(&mut self, hz: u32) -> Result<(), Self::Error> {
+        (**self).set_sample_rate(hz)
+    }
+
+    fn set_format(&mut self, standard: Standard, bits: u8) -> Result<(), Self::Error> {
+        (**self).set_format(standard, bits)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+}
+
+/// Read stereo frames
+pub trait Read<Word>: ErrorType {
+    /// Reads a single frame, `[left, right]`
+    ///
+    /// Possible errors include *overrun*: a frame was lost because it was not read in a timely
+    /// manner.
+    fn read(&mut self) -> nb::Result<[Word; 2], Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, Word> Read<Word> for Box<T>
+where
+    T: Read<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<[Word; 2], Self::Error> {
+        (**self).read()
+    }
+}
+
+impl<T, Word> Read<Word> for &mut T
+where
+    T: Read<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<[Word; 2], Self::Error> {
+        (**self).read()
+    }
+}
+
+/// Write stereo frames
+pub trait Write<Word>: ErrorType {
+    /// Writes a single frame, `[left, right]`
+    ///
+    /// Possible errors include *underrun*: the interface ran out of frames to send because this
+    /// method was not called in a timely manner.
+    fn write(&mut self, frame: [Word; 2]) -> nb::Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, Word> Write<Word> for Box<T>
+where
+    T: Write<Word> + ?Sized,
+{
+    fn write(&mut self, frame: [Word; 2]) -> nb::Result<(), Self::Error> {
+        (**self).write(frame)
+    }
+}
+
+impl<T, Word> Write<Word> for &mut T
+where
+    T: Write<Word> + ?Sized,
+{
+    fn write(&mut self, frame: [Word; 2]) -> nb::Result<(), Self::Error> {
+        (**self).write(frame)
+    }
+}
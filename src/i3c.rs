@@ -0,0 +1,233 @@
+//! Improved Inter-Integrated Circuit (I3C)
+//!
+//! *This module is available if embedded-hal is built with the `"i3c"` feature.*
+//!
+//! These traits model the *controller* side of an I3C bus. Targets are addressed by the dynamic
+//! address the controller assigned to them (see [`blocking::i3c::AssignDynamicAddresses`]).
+//! Transfers that block until completion live in the [`blocking::i3c`] module; this module
+//! contains the types shared by both and the non-blocking In-Band Interrupt (IBI) API.
+//!
+//! [`blocking::i3c`]: ../blocking/i3c/index.html
+//! [`blocking::i3c::AssignDynamicAddresses`]: ../blocking/i3c/trait.AssignDynamicAddresses.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// Common Command Code (CCC)
+///
+/// Codes in the range `0x00 ..= 0x7F` are *broadcast* commands, codes in the range
+/// `0x80 ..= 0xFE` are *direct* commands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Ccc(pub u8);
+
+impl Ccc {
+    /// Enable target events (broadcast)
+    pub const ENEC: Ccc = Ccc(0x00);
+    /// Disable target events (broadcast)
+    pub const DISEC: Ccc = Ccc(0x01);
+    /// Reset all dynamic addresses (broadcast)
+    pub const RSTDAA: Ccc = Ccc(0x06);
+    /// Enter dynamic address assignment (broadcast)
+    pub const ENTDAA: Ccc = Ccc(0x07);
+    /// Set maximum write length (broadcast)
+    pub const SETMWL: Ccc = Ccc(0x09);
+    /// Set maximum read length (broadcast)
+    pub const SETMRL: Ccc = Ccc(0x0A);
+    /// Enable target events (direct)
+    pub const ENEC_DIRECT: Ccc = Ccc(0x80);
+    /// Disable target events (direct)
+    pub const DISEC_DIRECT: Ccc = Ccc(0x81);
+    /// Set dynamic address from static address (direct)
+    pub const SETDASA: Ccc = Ccc(0x87);
+    /// Set new dynamic address (direct)
+    pub const SETNEWDA: Ccc = Ccc(0x88);
+    /// Set maximum write length (direct)
+    pub const SETMWL_DIRECT: Ccc = Ccc(0x89);
+    /// Set maximum read length (direct)
+    pub const SETMRL_DIRECT: Ccc = Ccc(0x8A);
+    /// Get maximum write length (direct)
+    pub const GETMWL: Ccc = Ccc(0x8B);
+    /// Get maximum read length (direct)
+    pub const GETMRL: Ccc = Ccc(0x8C);
+    /// Get provisioned ID (direct)
+    pub const GETPID: Ccc = Ccc(0x8D);
+    /// Get bus characteristics register (direct)
+    pub const GETBCR: Ccc = Ccc(0x8E);
+    /// Get device characteristics register (direct)
+    pub const GETDCR: Ccc = Ccc(0x8F);
+    /// Get device status (direct)
+    pub const GETSTATUS: Ccc = Ccc(0x90);
+
+    /// Is this a broadcast command?
+    pub fn is_broadcast(&self) -> bool {
+        self.0 < 0x80
+    }
+
+    /// Is this a direct command?
+    pub fn is_direct(&self) -> bool {
+        !self.is_broadcast()
+    }
+}
+
+/// A target discovered during dynamic address assignment
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Target {
+    /// 48-bit Provisioned ID
+    pub pid: u64,
+    /// Bus Characteristics Register
+    pub bcr: u8,
+    /// Device Characteristics Register
+    pub dcr: u8,
+    /// Dynamic address assigned by the controller
+    pub address: u8,
+}
+
+/// An In-Band Interrupt (IBI) raised by a target
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Ibi {
+    /// Dynamic address of the target that raised the interrupt
+    pub address: u8,
+    /// Number of payload bytes (mandatory data byte included) that were stored in the buffer
+    pub len: usize,
+}
+
+/// I3C error kind
+///
+/// This represents a common set of I3C operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// Bus error occurred, e.g. a misplaced start or stop condition
+    Bus,
+    /// A target won the arbitration of the address header
+    ArbitrationLoss,
+    /// The target did not acknowledge its address, or the broadcast address was not
+    /// acknowledged
+    NoAcknowledge,
+    /// The parity (transition) bit of a data byte was wrong
+    Parity,
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Bus => "bus error",
+            ErrorKind::ArbitrationLoss => "a target won the arbitration of the address header",
+            ErrorKind::NoAcknowledge => "the target did not acknowledge its address",
+            ErrorKind::Parity => "the parity bit of a data byte was wrong",
+            ErrorKind::Overrun => "the peripheral receive buffer was overrun",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// I3C error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// I3C error type
+///
+/// All the I3C traits, non-blocking and blocking, inherit from this trait so that an I3C controller
+/// defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// In-Band Interrupt reception
+pub trait ReadIbi: ErrorType {
+    /// Reads the next pending In-Band Interrupt
+    ///
+    /// The IBI payload, starting with the mandatory data byte if the target sends one, is copied
+    /// into `payload`. Payload bytes that don't fit in `payload` are discarded.
+    ///
+    /// Returns `Err(nb::Error::WouldBlock)` if no IBI is pending.
+    fn read_ibi(&mut self, payload: &mut [u8]) -> nb::Result<Ibi, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ReadIbi for Box<T>
+where
+    T: ReadIbi + ?Sized,
+{
+    fn read_ibi(&mut self, payload: &mut [u8]) -> nb::Result<Ibi, Self::Error> {
+        (**self).read_ibi(payload)
+    }
+}
+
+impl<T> ReadIbi for &mut T
+where
+    T: ReadIbi + ?Sized,
+{
+    fn read_ibi(&mut self, payload: &mut [u8]) -> nb::Result<Ibi, Self::Error> {
+        (**self).read_ibi(payload)
+    }
+}
@@ -5,7 +5,26 @@
 //! release. That being said there's a part of the HAL that's currently considered unproven and is
 //! hidden behind an "unproven" Cargo feature. This API is even more volatile and it's exempt from
 //! semver rules: it can change in a non-backward compatible fashion or even disappear in between
-//! patch releases.
+//! patch releases. Each domain of the unproven API also has a Cargo feature of its own, e.g. "adc",
+//! "can" or, for the unproven digital and timer traits, "digital-unproven" and "timer-unproven",
+//! so that users can opt into just the traits they need; "unproven" enables all of them.
+//!
+//! The asynchronous API, in the `futures` module, is hidden behind an "async" Cargo feature, and
+//! the test doubles of the `mock` module behind a "mock" Cargo feature. The "defmt" Cargo feature
+//! implements `defmt::Format` for the public enums and structs, e.g. the error kinds, so they can
+//! be logged with deferred formatting. The "std" Cargo feature implements `std::error::Error` for
+//! the error kinds and the other error types defined by this crate, e.g. for host-side tools, and,
+//! on x86 and ARM Linux, provides the `linux` module, which implements the traits over the I2C,
+//! SPI, GPIO and serial device files, e.g. to develop drivers on a Raspberry Pi. The "alloc" Cargo
+//! feature implements the traits for `Box`es of their implementers, including boxed trait objects,
+//! e.g. to keep different peripherals in a collection. The "critical-section" Cargo feature lets the
+//! interrupt handlers share a bus through the `bus` module. The "compat" Cargo feature provides the
+//! `compat` module, whose wrappers convert implementations of the embedded-hal 0.2 traits into
+//! implementations of these ones and vice versa. The "serde" Cargo feature implements
+//! `Serialize` and `Deserialize` for the configuration types, e.g. `spi::Mode` or
+//! `can::BitTiming`, so that boards can load the settings of their peripherals from flash. The
+//! "fugit" Cargo feature provides the `fugit` module, which maps the timing traits onto the typed
+//! durations and rates of the `fugit` crate.
 //!
 //! # Design goals
 //!
@@ -54,6 +73,48 @@
 //! embedded-hal keyword](https://doc.rust-lang.org/cargo/reference/manifest.html#package-metadata)
 //! to your crate before publishing it!
 //!
+//! The traits are also implemented for mutable references to their implementers, so a driver that
+//! takes ownership of its peripherals can be given a borrowed one instead, e.g. to share a pin
+//! with the rest of the application once the driver is dropped. The exceptions are the traits
+//! implemented through a `Default` marker trait, e.g. `blocking::serial::Write`, and the DMA
+//! transfers, whose methods consume `self`.
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::digital::OutputPin;
+//!
+//! /// A driver that owns its pin
+//! struct Led<P> {
+//!     pin: P,
+//! }
+//!
+//! impl<P> Led<P>
+//! where
+//!     P: OutputPin,
+//! {
+//!     fn on(&mut self) {
+//!         self.pin.set_high()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut pa5 = Pa5 { high: false };
+//!
+//!     Led { pin: &mut pa5 }.on();
+//!
+//!     // the pin is available again
+//!     assert!(pa5.high);
+//!     pa5.set_low();
+//! }
+//!
+//! # struct Pa5 { high: bool }
+//! # impl OutputPin for Pa5 {
+//! #     fn set_low(&mut self) { self.high = false }
+//! #     fn set_high(&mut self) { self.high = true }
+//! # }
+//! ```
+//!
 //! # Detailed design
 //!
 //! ## Traits
@@ -141,15 +202,26 @@
 //! pub type Serial1 = Serial<USART1>;
 //!
 //! /// Serial interface error
+//! #[derive(Debug)]
 //! pub enum Error {
 //!     /// Buffer overrun
 //!     Overrun,
 //!     // omitted: other error variants
 //! }
 //!
-//! impl hal::serial::Read<u8> for Serial<USART1> {
+//! impl hal::serial::Error for Error {
+//!     fn kind(&self) -> hal::serial::ErrorKind {
+//!         match *self {
+//!             Error::Overrun => hal::serial::ErrorKind::Overrun,
+//!         }
+//!     }
+//! }
+//!
+//! impl hal::serial::ErrorType for Serial<USART1> {
 //!     type Error = Error;
+//! }
 //!
+//! impl hal::serial::Read<u8> for Serial<USART1> {
 //!     fn read(&mut self) -> nb::Result<u8, Error> {
 //!         // read the status register
 //!         let isr = self.usart.isr.read();
@@ -170,8 +242,6 @@
 //! }
 //!
 //! impl hal::serial::Write<u8> for Serial<USART1> {
-//!     type Error = Error;
-//!
 //!     fn write(&mut self, byte: u8) -> nb::Result<(), Error> {
 //!         // Similar to the `read` implementation
 //!         # Ok(())
@@ -360,12 +430,13 @@
 //! #     }
 //! #
 //! #     pub struct Serial1;
-//! #     impl ::hal::serial::Read<u8> for Serial1 {
+//! #     impl ::hal::serial::ErrorType for Serial1 {
 //! #         type Error = Void;
+//! #     }
+//! #     impl ::hal::serial::Read<u8> for Serial1 {
 //! #         fn read(&mut self) -> ::nb::Result<u8, Void> { Err(::nb::Error::WouldBlock) }
 //! #     }
 //! #     impl ::hal::serial::Write<u8> for Serial1 {
-//! #         type Error = Void;
 //! #         fn flush(&mut self) -> ::nb::Result<(), Void> { Err(::nb::Error::WouldBlock) }
 //! #         fn write(&mut self, _: u8) -> ::nb::Result<(), Void> { Err(::nb::Error::WouldBlock) }
 //! #     }
@@ -666,8 +737,10 @@
 //! #     fn deref_mut(&mut self) -> &mut T { self.0 }
 //! # }
 //! # struct Serial1;
-//! # impl ::hal::serial::Write<u8> for Serial1 {
+//! # impl ::hal::serial::ErrorType for Serial1 {
 //! #   type Error = Void;
+//! # }
+//! # impl ::hal::serial::Write<u8> for Serial1 {
 //! #   fn write(&mut self, _: u8) -> nb::Result<(), Void> { Err(::nb::Error::WouldBlock) }
 //! #   fn flush(&mut self) -> nb::Result<(), Void> { Err(::nb::Error::WouldBlock) }
 //! # }
@@ -685,20 +758,99 @@
 #![deny(warnings)]
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "critical-section")]
+extern crate critical_section;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "compat")]
+extern crate embedded_hal_v0_2;
+#[cfg(feature = "fugit")]
+extern crate fugit as fugit_crate;
 #[macro_use]
 extern crate nb;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "std")]
+extern crate std;
 extern crate void;
 
+#[cfg(feature = "adc")]
+pub mod adc;
+#[cfg(feature = "bitbang")]
+pub mod bitbang;
 pub mod blocking;
+#[cfg(feature = "bus")]
+pub mod bus;
+#[cfg(feature = "calibration")]
+pub mod calibration;
+#[cfg(feature = "can")]
+pub mod can;
+#[cfg(feature = "compat")]
+pub mod compat;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod digital;
+#[cfg(feature = "dma")]
+pub mod dma;
+#[cfg(feature = "erased")]
+pub mod erased;
+pub mod error;
+#[cfg(feature = "event")]
+pub mod event;
+#[cfg(feature = "fugit")]
+pub mod fugit;
+#[cfg(feature = "async")]
+pub mod futures;
+pub mod i2c;
+#[cfg(feature = "i2s")]
+pub mod i2s;
+#[cfg(feature = "i3c")]
+pub mod i3c;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "arm",
+        target_arch = "aarch64"
+    )
+))]
+pub mod linux;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "pdm")]
+pub mod pdm;
+#[cfg(feature = "power")]
+pub mod power;
 pub mod prelude;
+#[cfg(feature = "qspi")]
+pub mod qspi;
+#[cfg(feature = "rng")]
+pub mod rng;
+#[cfg(feature = "rtc")]
+pub mod rtc;
 pub mod serial;
 pub mod spi;
+#[cfg(feature = "storage")]
+pub mod storage;
+pub mod time;
 pub mod timer;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "typestate")]
+pub mod typestate;
+#[cfg(feature = "watchdog")]
+pub mod watchdog;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 
 /// Input capture
 ///
-/// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
+/// *This trait is available if embedded-hal is built with the `"capture"` feature.*
 ///
 /// # Examples
 ///
@@ -730,9 +882,7 @@ pub mod timer;
 ///
 /// # extern crate void;
 /// # use void::Void;
-/// # struct MilliSeconds(u32);
-/// # trait U32Ext { fn ms(self) -> MilliSeconds; }
-/// # impl U32Ext for u32 { fn ms(self) -> MilliSeconds { MilliSeconds(self) } }
+/// # use hal::time::{MilliSeconds, U32Ext};
 /// # struct Capture1;
 /// # enum Channel { _1 }
 /// # impl hal::Capture for Capture1 {
@@ -747,7 +897,7 @@ pub mod timer;
 /// #     fn set_resolution<T>(&mut self, _: T) where T: Into<MilliSeconds> {}
 /// # }
 /// ```
-#[cfg(feature = "unproven")]
+#[cfg(feature = "capture")]
 // reason: pre-singletons API. With singletons a `CapturePin` (cf. `PwmPin`) trait seems more
 // appropriate
 pub trait Capture {
@@ -762,10 +912,13 @@ pub trait Capture {
     /// Enumeration of channels that can be used with this `Capture` interface
     ///
     /// If your `Capture` interface has no channels you can use the type `()`
-    /// here
+    /// here. Pins are mapped to their channel by implementing `CaptureChannel<Self>`.
     type Channel;
 
     /// A time unit that can be converted into a human time unit (e.g. seconds)
+    ///
+    /// It SHOULD be `time::MicroSeconds`, or a type that `time::MicroSeconds` and
+    /// `time::MilliSeconds` convert into, so that drivers can set the resolution portably.
     type Time;
 
     /// The type of the value returned by `capture`
@@ -793,9 +946,92 @@ pub trait Capture {
         R: Into<Self::Time>;
 }
 
+#[cfg(all(feature = "alloc", feature = "capture"))]
+impl<T> Capture for Box<T>
+where
+    T: Capture + ?Sized,
+{
+    type Error = T::Error;
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Capture = T::Capture;
+
+    fn capture(&mut self, channel: Self::Channel) -> nb::Result<Self::Capture, Self::Error> {
+        (**self).capture(channel)
+    }
+
+    fn disable(&mut self, channel: Self::Channel) {
+        (**self).disable(channel)
+    }
+
+    fn enable(&mut self, channel: Self::Channel) {
+        (**self).enable(channel)
+    }
+
+    fn get_resolution(&self) -> Self::Time {
+        (**self).get_resolution()
+    }
+
+    fn set_resolution<R>(&mut self, resolution: R)
+    where
+        R: Into<Self::Time>,
+    {
+        (**self).set_resolution(resolution)
+    }
+}
+
+#[cfg(feature = "capture")]
+impl<T> Capture for &mut T
+where
+    T: Capture + ?Sized,
+{
+    type Error = T::Error;
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Capture = T::Capture;
+
+    fn capture(&mut self, channel: Self::Channel) -> nb::Result<Self::Capture, Self::Error> {
+        (**self).capture(channel)
+    }
+
+    fn disable(&mut self, channel: Self::Channel) {
+        (**self).disable(channel)
+    }
+
+    fn enable(&mut self, channel: Self::Channel) {
+        (**self).enable(channel)
+    }
+
+    fn get_resolution(&self) -> Self::Time {
+        (**self).get_resolution()
+    }
+
+    fn set_resolution<R>(&mut self, resolution: R)
+    where
+        R: Into<Self::Time>,
+    {
+        (**self).set_resolution(resolution)
+    }
+}
+
+/// A pin that is an input of a channel of the `CAPTURE` peripheral
+///
+/// *This trait is available if embedded-hal is built with the `"capture"` feature.*
+///
+/// It's the [`PwmChannel`](trait.PwmChannel.html) of the `Capture` interfaces: HALs implement it
+/// for their pin types so that capturing a pin with a timer it isn't connected to doesn't compile.
+#[cfg(feature = "capture")]
+pub trait CaptureChannel<CAPTURE>
+where
+    CAPTURE: Capture,
+{
+    /// Returns the channel of `CAPTURE` the pin is an input of
+    fn channel() -> CAPTURE::Channel;
+}
+
 /// Pulse Width Modulation
 ///
-/// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
+/// *This trait is available if embedded-hal is built with the `"pwm"` feature.*
 ///
 /// # Examples
 ///
@@ -823,35 +1059,36 @@ pub trait Capture {
 ///     pwm.set_duty(Channel::_2, max_duty / 4);
 /// }
 ///
-/// # struct KiloHertz(u32);
-/// # trait U32Ext { fn khz(self) -> KiloHertz; }
-/// # impl U32Ext for u32 { fn khz(self) -> KiloHertz { KiloHertz(self) } }
+/// # use hal::time::{Hertz, U32Ext};
 /// # enum Channel { _1, _2 }
 /// # struct Pwm1;
 /// # impl hal::Pwm for Pwm1 {
 /// #     type Channel = Channel;
-/// #     type Time = KiloHertz;
+/// #     type Time = Hertz;
 /// #     type Duty = u16;
 /// #     fn disable(&mut self, _: Channel) { unimplemented!() }
 /// #     fn enable(&mut self, _: Channel) { unimplemented!() }
 /// #     fn get_duty(&self, _: Channel) -> u16 { unimplemented!() }
 /// #     fn get_max_duty(&self) -> u16 { 0 }
 /// #     fn set_duty(&mut self, _: Channel, _: u16) {}
-/// #     fn get_period(&self) -> KiloHertz { unimplemented!() }
-/// #     fn set_period<T>(&mut self, _: T) where T: Into<KiloHertz> {}
+/// #     fn get_period(&self) -> Hertz { unimplemented!() }
+/// #     fn set_period<T>(&mut self, _: T) where T: Into<Hertz> {}
 /// # }
 /// ```
-#[cfg(feature = "unproven")]
+#[cfg(feature = "pwm")]
 // reason: pre-singletons API. The `PwmPin` trait seems more useful because it models independent
 // PWM channels. Here a certain number of channels are multiplexed in a single implementer.
 pub trait Pwm {
     /// Enumeration of channels that can be used with this `Pwm` interface
     ///
     /// If your `Pwm` interface has no channels you can use the type `()`
-    /// here
+    /// here. Pins are mapped to their channel by implementing `PwmChannel<Self>`.
     type Channel;
 
     /// A time unit that can be converted into a human time unit (e.g. seconds)
+    ///
+    /// It SHOULD be `time::Hertz`, or a type that `time::Hertz` converts into, so that drivers
+    /// can set the frequency of the signal portably.
     type Time;
 
     /// Type for the `duty` methods
@@ -884,9 +1121,205 @@ pub trait Pwm {
         P: Into<Self::Time>;
 }
 
+#[cfg(all(feature = "alloc", feature = "pwm"))]
+impl<T> Pwm for Box<T>
+where
+    T: Pwm + ?Sized,
+{
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Duty = T::Duty;
+
+    fn disable(&mut self, channel: Self::Channel) {
+        (**self).disable(channel)
+    }
+
+    fn enable(&mut self, channel: Self::Channel) {
+        (**self).enable(channel)
+    }
+
+    fn get_period(&self) -> Self::Time {
+        (**self).get_period()
+    }
+
+    fn get_duty(&self, channel: Self::Channel) -> Self::Duty {
+        (**self).get_duty(channel)
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        (**self).get_max_duty()
+    }
+
+    fn set_duty(&mut self, channel: Self::Channel, duty: Self::Duty) {
+        (**self).set_duty(channel, duty)
+    }
+
+    fn set_period<P>(&mut self, period: P)
+    where
+        P: Into<Self::Time>,
+    {
+        (**self).set_period(period)
+    }
+}
+
+#[cfg(feature = "pwm")]
+impl<T> Pwm for &mut T
+where
+    T: Pwm + ?Sized,
+{
+    type Channel = T::Channel;
+    type Time = T::Time;
+    type Duty = T::Duty;
+
+    fn disable(&mut self, channel: Self::Channel) {
+        (**self).disable(channel)
+    }
+
+    fn enable(&mut self, channel: Self::Channel) {
+        (**self).enable(channel)
+    }
+
+    fn get_period(&self) -> Self::Time {
+        (**self).get_period()
+    }
+
+    fn get_duty(&self, channel: Self::Channel) -> Self::Duty {
+        (**self).get_duty(channel)
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        (**self).get_max_duty()
+    }
+
+    fn set_duty(&mut self, channel: Self::Channel, duty: Self::Duty) {
+        (**self).set_duty(channel, duty)
+    }
+
+    fn set_period<P>(&mut self, period: P)
+    where
+        P: Into<Self::Time>,
+    {
+        (**self).set_period(period)
+    }
+}
+
+/// A pin that outputs a channel of the `PWM` peripheral
+///
+/// *This trait is available if embedded-hal is built with the `"pwm"` feature.*
+///
+/// HALs implement this trait for their pin types, mapping each pin to its channel, like
+/// [`adc::Channel`] does for ADCs, so that driving a pin with a timer it isn't connected to is a
+/// compile time error rather than a silent misconfiguration.
+///
+/// [`adc::Channel`]: adc/trait.Channel.html
+///
+/// # Examples
+///
+/// A fan whose speed is set through the pin it's wired to
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::{Pwm, PwmChannel};
+///
+/// struct Fan<PIN> {
+///     pin: PIN,
+/// }
+///
+/// impl<PIN> Fan<PIN> {
+///     fn set_speed<PWM>(&self, pwm: &mut PWM, duty: PWM::Duty)
+///     where
+///         PWM: Pwm,
+///         PIN: PwmChannel<PWM>,
+///     {
+///         pwm.set_duty(PIN::channel(), duty);
+///     }
+/// }
+///
+/// fn main() {
+///     let (mut tim2, pa1): (Tim2, Pa1) = {
+///         // ..
+/// #       (Tim2([0; 4]), Pa1)
+///     };
+///
+///     let fan = Fan { pin: pa1 };
+///     fan.set_speed(&mut tim2, 300);
+///     // fan.set_speed(&mut tim3, 300); // doesn't compile: PA1 isn't a TIM3 channel
+/// #   assert_eq!(tim2.0, [0, 300, 0, 0]);
+/// }
+///
+/// # #[derive(Clone, Copy)]
+/// # enum Channel { _1, _2, _3, _4 }
+/// # struct Tim2([u16; 4]);
+/// # struct Pa1;
+/// # impl PwmChannel<Tim2> for Pa1 {
+/// #     fn channel() -> Channel { Channel::_2 }
+/// # }
+/// # impl Pwm for Tim2 {
+/// #     type Channel = Channel;
+/// #     type Time = u32;
+/// #     type Duty = u16;
+/// #     fn disable(&mut self, _: Channel) {}
+/// #     fn enable(&mut self, _: Channel) {}
+/// #     fn get_duty(&self, channel: Channel) -> u16 { self.0[channel as usize] }
+/// #     fn get_max_duty(&self) -> u16 { 1000 }
+/// #     fn set_duty(&mut self, channel: Channel, duty: u16) { self.0[channel as usize] = duty }
+/// #     fn get_period(&self) -> u32 { 1000 }
+/// #     fn set_period<T>(&mut self, _: T) where T: Into<u32> {}
+/// # }
+/// ```
+#[cfg(feature = "pwm")]
+pub trait PwmChannel<PWM>
+where
+    PWM: Pwm,
+{
+    /// Returns the channel of `PWM` the pin outputs
+    fn channel() -> PWM::Channel;
+}
+
 /// A single PWM channel / pin
 ///
 /// See `Pwm` for details
+///
+/// # Examples
+///
+/// Dimming a LED
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::PwmPin;
+///
+/// /// Sets the brightness of `led`, in percent
+/// fn set_brightness<P>(led: &mut P, percent: u16)
+/// where
+///     P: PwmPin<Duty = u16>,
+/// {
+///     let duty = u32::from(led.get_max_duty()) * u32::from(percent) / 100;
+///     led.set_duty(duty as u16);
+/// }
+///
+/// fn main() {
+///     let mut led: Pwm1Ch1 = {
+///         // ..
+/// #       Pwm1Ch1(0)
+///     };
+///
+///     set_brightness(&mut led, 25);
+///     led.enable();
+/// #   assert_eq!(led.get_duty(), 1000);
+/// }
+///
+/// # struct Pwm1Ch1(u16);
+/// # impl PwmPin for Pwm1Ch1 {
+/// #     type Duty = u16;
+/// #     fn disable(&mut self) {}
+/// #     fn enable(&mut self) {}
+/// #     fn get_duty(&self) -> u16 { self.0 }
+/// #     fn get_max_duty(&self) -> u16 { 4000 }
+/// #     fn set_duty(&mut self, duty: u16) { self.0 = duty }
+/// # }
+/// ```
 pub trait PwmPin {
     /// Type for the `duty` methods
     ///
@@ -894,10 +1327,10 @@ pub trait PwmPin {
     /// (e.g. `0.0 .. 1.0`) or an integer representation (e.g. `0 .. 65535`)
     type Duty;
 
-    /// Disables a PWM `channel`
+    /// Disables the PWM output
     fn disable(&mut self);
 
-    /// Enables a PWM `channel`
+    /// Enables the PWM output
     fn enable(&mut self);
 
     /// Returns the current duty cycle
@@ -910,9 +1343,64 @@ pub trait PwmPin {
     fn set_duty(&mut self, duty: Self::Duty);
 }
 
+#[cfg(feature = "alloc")]
+impl<T> PwmPin for Box<T>
+where
+    T: PwmPin + ?Sized,
+{
+    type Duty = T::Duty;
+
+    fn disable(&mut self) {
+        (**self).disable()
+    }
+
+    fn enable(&mut self) {
+        (**self).enable()
+    }
+
+    fn get_duty(&self) -> Self::Duty {
+        (**self).get_duty()
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        (**self).get_max_duty()
+    }
+
+    fn set_duty(&mut self, duty: Self::Duty) {
+        (**self).set_duty(duty)
+    }
+}
+
+impl<T> PwmPin for &mut T
+where
+    T: PwmPin + ?Sized,
+{
+    type Duty = T::Duty;
+
+    fn disable(&mut self) {
+        (**self).disable()
+    }
+
+    fn enable(&mut self) {
+        (**self).enable()
+    }
+
+    fn get_duty(&self) -> Self::Duty {
+        (**self).get_duty()
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        (**self).get_max_duty()
+    }
+
+    fn set_duty(&mut self, duty: Self::Duty) {
+        (**self).set_duty(duty)
+    }
+}
+
 /// Quadrature encoder interface
 ///
-/// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
+/// *This trait is available if embedded-hal is built with the `"qei"` feature.*
 ///
 /// # Examples
 ///
@@ -947,9 +1435,7 @@ pub trait PwmPin {
 ///
 /// # extern crate void;
 /// # use void::Void;
-/// # struct Seconds(u32);
-/// # trait U32Ext { fn s(self) -> Seconds; }
-/// # impl U32Ext for u32 { fn s(self) -> Seconds { Seconds(self) } }
+/// # use hal::time::{MilliSeconds, U32Ext};
 /// # struct Qei1;
 /// # impl hal::Qei for Qei1 {
 /// #     type Count = u16;
@@ -958,12 +1444,12 @@ pub trait PwmPin {
 /// # }
 /// # struct Timer6;
 /// # impl hal::timer::CountDown for Timer6 {
-/// #     type Time = Seconds;
-/// #     fn start<T>(&mut self, _: T) where T: Into<Seconds> {}
+/// #     type Time = MilliSeconds;
+/// #     fn start<T>(&mut self, _: T) where T: Into<MilliSeconds> {}
 /// #     fn wait(&mut self) -> ::nb::Result<(), Void> { Ok(()) }
 /// # }
 /// ```
-#[cfg(feature = "unproven")]
+#[cfg(feature = "qei")]
 // reason: needs to be re-evaluated in the new singletons world. At the very least this needs a
 // reference implementation
 pub trait Qei {
@@ -977,11 +1463,44 @@ pub trait Qei {
     fn direction(&self) -> Direction;
 }
 
+#[cfg(all(feature = "alloc", feature = "qei"))]
+impl<T> Qei for Box<T>
+where
+    T: Qei + ?Sized,
+{
+    type Count = T::Count;
+
+    fn count(&self) -> Self::Count {
+        (**self).count()
+    }
+
+    fn direction(&self) -> Direction {
+        (**self).direction()
+    }
+}
+
+#[cfg(feature = "qei")]
+impl<T> Qei for &mut T
+where
+    T: Qei + ?Sized,
+{
+    type Count = T::Count;
+
+    fn count(&self) -> Self::Count {
+        (**self).count()
+    }
+
+    fn direction(&self) -> Direction {
+        (**self).direction()
+    }
+}
+
 /// Count direction
 ///
-/// *This enumeration is available if embedded-hal is built with the `"unproven"` feature.*
+/// *This enumeration is available if embedded-hal is built with the `"qei"` feature.*
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[cfg(feature = "unproven")]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg(feature = "qei")]
 // reason: part of the unproven `Qei` interface
 pub enum Direction {
     /// 3, 2, 1
@@ -0,0 +1,33 @@
+//! Delays that sleep the calling thread
+
+use std::thread;
+use std::time::Duration;
+
+use blocking::delay::{DelayMs, DelayUs};
+
+/// Delay that sleeps the calling thread
+///
+/// It implements the blocking `DelayMs` and `DelayUs` for `u8`, `u16` and `u32`. The thread may
+/// sleep longer than asked, but never shorter.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Delay;
+
+macro_rules! impl_delay {
+    ($($UXX:ty),+) => {
+        $(
+            impl DelayMs<$UXX> for Delay {
+                fn delay_ms(&mut self, ms: $UXX) {
+                    thread::sleep(Duration::from_millis(u64::from(ms)));
+                }
+            }
+
+            impl DelayUs<$UXX> for Delay {
+                fn delay_us(&mut self, us: $UXX) {
+                    thread::sleep(Duration::from_micros(u64::from(us)));
+                }
+            }
+        )+
+    }
+}
+
+impl_delay!(u8, u16, u32);
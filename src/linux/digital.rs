@@ -0,0 +1,214 @@
+//! GPIO lines, through the GPIO character device interface
+//!
+//! # Examples
+//!
+//! Blinking an LED on line 17 of the first GPIO chip of a Raspberry Pi
+//!
+//! ```no_run
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::delay::DelayMs;
+//! use hal::digital::OutputPin;
+//! use hal::linux::delay::Delay;
+//! use hal::linux::digital::CdevPin;
+//!
+//! fn main() {
+//!     let mut led = CdevPin::output("/dev/gpiochip0", 17, false).unwrap();
+//!
+//!     loop {
+//!         led.set_high();
+//!         Delay.delay_ms(500u16);
+//!         led.set_low();
+//!         Delay.delay_ms(500u16);
+//!     }
+//! }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::raw::{c_int, c_ulong};
+use std::os::unix::io::FromRawFd;
+use std::path::Path;
+
+use digital::OutputPin;
+#[cfg(feature = "digital-unproven")]
+use digital::{v2, InputPin, PinState, StatefulOutputPin};
+
+use super::ioctl_ptr;
+#[cfg(feature = "digital-unproven")]
+use super::Error;
+
+const GPIO_GET_LINEHANDLE_IOCTL: c_ulong = 0xc16c_b403;
+#[cfg(feature = "digital-unproven")]
+const GPIOHANDLE_GET_LINE_VALUES_IOCTL: c_ulong = 0xc040_b408;
+const GPIOHANDLE_SET_LINE_VALUES_IOCTL: c_ulong = 0xc040_b409;
+#[cfg(feature = "digital-unproven")]
+const GPIOHANDLE_SET_CONFIG_IOCTL: c_ulong = 0xc054_b40a;
+
+const GPIOHANDLE_REQUEST_INPUT: u32 = 0x01;
+const GPIOHANDLE_REQUEST_OUTPUT: u32 = 0x02;
+
+const GPIOHANDLES_MAX: usize = 64;
+
+#[repr(C)]
+struct HandleRequest {
+    lineoffsets: [u32; GPIOHANDLES_MAX],
+    flags: u32,
+    default_values: [u8; GPIOHANDLES_MAX],
+    consumer_label: [u8; 32],
+    lines: u32,
+    fd: c_int,
+}
+
+#[repr(C)]
+struct HandleData {
+    values: [u8; GPIOHANDLES_MAX],
+}
+
+#[cfg(feature = "digital-unproven")]
+#[repr(C)]
+struct HandleConfig {
+    flags: u32,
+    default_values: [u8; GPIOHANDLES_MAX],
+    padding: [u32; 4],
+}
+
+/// GPIO line of a `/dev/gpiochip*` device
+///
+/// The line is requested, under the "embedded-hal" consumer label, for as long as the pin
+/// exists. As the digital traits can't report errors, the methods panic if the kernel rejects
+/// reading or driving the line, which only happens if the device goes away.
+///
+/// With the `"digital-unproven"` feature, it implements `v2::IoPin`, whose conversions change the
+/// direction of the line in place. They need Linux 5.5 or later.
+#[derive(Debug)]
+pub struct CdevPin {
+    handle: File,
+}
+
+impl CdevPin {
+    /// Requests line `line` of the GPIO chip at `chip`, e.g. `/dev/gpiochip0`, as an output
+    /// driven high if `high` is true and low otherwise
+    pub fn output<P>(chip: P, line: u32, high: bool) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        CdevPin::request(chip.as_ref(), line, GPIOHANDLE_REQUEST_OUTPUT, high)
+    }
+
+    /// Requests line `line` of the GPIO chip at `chip`, e.g. `/dev/gpiochip0`, as an input
+    pub fn input<P>(chip: P, line: u32) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        CdevPin::request(chip.as_ref(), line, GPIOHANDLE_REQUEST_INPUT, false)
+    }
+
+    fn request(chip: &Path, line: u32, flags: u32, high: bool) -> io::Result<Self> {
+        let chip = OpenOptions::new().read(true).write(true).open(chip)?;
+
+        let mut request = HandleRequest {
+            lineoffsets: [0; GPIOHANDLES_MAX],
+            flags,
+            default_values: [0; GPIOHANDLES_MAX],
+            consumer_label: [0; 32],
+            lines: 1,
+            fd: 0,
+        };
+        request.lineoffsets[0] = line;
+        request.default_values[0] = high as u8;
+        request.consumer_label[..12].copy_from_slice(b"embedded-hal");
+
+        // NOTE(unsafe) the request has the layout of a `struct gpiohandle_request`, and the
+        // kernel hands over the ownership of the handle it returns
+        unsafe {
+            ioctl_ptr(&chip, GPIO_GET_LINEHANDLE_IOCTL, &mut request)?;
+            Ok(CdevPin {
+                handle: File::from_raw_fd(request.fd),
+            })
+        }
+    }
+
+    #[cfg(feature = "digital-unproven")]
+    fn get(&self) -> bool {
+        let mut data = HandleData {
+            values: [0; GPIOHANDLES_MAX],
+        };
+        // NOTE(unsafe) the data has the layout of a `struct gpiohandle_data`
+        unsafe { ioctl_ptr(&self.handle, GPIOHANDLE_GET_LINE_VALUES_IOCTL, &mut data) }
+            .expect("failed to read the GPIO line");
+        data.values[0] != 0
+    }
+
+    fn set(&mut self, high: bool) {
+        let mut data = HandleData {
+            values: [0; GPIOHANDLES_MAX],
+        };
+        data.values[0] = high as u8;
+        // NOTE(unsafe) the data has the layout of a `struct gpiohandle_data`
+        unsafe { ioctl_ptr(&self.handle, GPIOHANDLE_SET_LINE_VALUES_IOCTL, &mut data) }
+            .expect("failed to drive the GPIO line");
+    }
+}
+
+impl OutputPin for CdevPin {
+    fn set_low(&mut self) {
+        self.set(false)
+    }
+
+    fn set_high(&mut self) {
+        self.set(true)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl StatefulOutputPin for CdevPin {
+    fn is_set_high(&self) -> bool {
+        self.get()
+    }
+
+    fn is_set_low(&self) -> bool {
+        !self.get()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl InputPin for CdevPin {
+    fn is_high(&self) -> bool {
+        self.get()
+    }
+
+    fn is_low(&self) -> bool {
+        !self.get()
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl CdevPin {
+    fn configure(self, flags: u32, high: bool) -> Result<Self, Error> {
+        let mut config = HandleConfig {
+            flags,
+            default_values: [0; GPIOHANDLES_MAX],
+            padding: [0; 4],
+        };
+        config.default_values[0] = high as u8;
+        // NOTE(unsafe) the config has the layout of a `struct gpiohandle_config`
+        unsafe { ioctl_ptr(&self.handle, GPIOHANDLE_SET_CONFIG_IOCTL, &mut config)? };
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl v2::IoPin for CdevPin {
+    type Input = CdevPin;
+    type Output = CdevPin;
+    type Error = Error;
+
+    fn into_input_pin(self) -> Result<CdevPin, Error> {
+        self.configure(GPIOHANDLE_REQUEST_INPUT, false)
+    }
+
+    fn into_output_pin(self, state: PinState) -> Result<CdevPin, Error> {
+        self.configure(GPIOHANDLE_REQUEST_OUTPUT, state.into())
+    }
+}
@@ -0,0 +1,148 @@
+//! I2C buses, through the i2c-dev interface
+//!
+//! # Examples
+//!
+//! Reading the temperature register of a sensor on the I2C bus of the pin header of a Raspberry Pi
+//!
+//! ```no_run
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::i2c::WriteRead;
+//! use hal::linux::i2c::I2cdev;
+//!
+//! fn main() {
+//!     let mut i2c = I2cdev::open("/dev/i2c-1").unwrap();
+//!
+//!     let mut temperature = [0; 2];
+//!     i2c.write_read(0x48, &[0x00], &mut temperature).unwrap();
+//! }
+//! ```
+
+use std::convert::TryFrom;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::raw::c_ulong;
+use std::path::Path;
+use std::vec::Vec;
+
+use blocking::i2c::{Operation, Read, Transactional, Write, WriteRead};
+use i2c::ErrorType;
+
+use super::{ioctl_ptr, Error};
+
+const I2C_RDWR: c_ulong = 0x0707;
+const I2C_M_RD: u16 = 0x0001;
+const I2C_M_NOSTART: u16 = 0x4000;
+
+#[repr(C)]
+struct Message {
+    addr: u16,
+    flags: u16,
+    len: u16,
+    buf: *mut u8,
+}
+
+#[repr(C)]
+struct RdwrData {
+    msgs: *mut Message,
+    nmsgs: u32,
+}
+
+/// I2C bus behind a `/dev/i2c-*` device file
+///
+/// Each operation is a single combined transaction, with repeated starts between its reads and
+/// writes, so `write_read` and `exec` work with devices that need them. It requires the `i2c-dev`
+/// kernel module, and a bus driver that supports `I2C_RDWR`; `exec` with consecutive operations of
+/// the same kind also needs `I2C_FUNC_NOSTART`. Only 7-bit addresses are supported, and the
+/// buffers of the operations must be shorter than 64 KiB.
+#[derive(Debug)]
+pub struct I2cdev {
+    file: File,
+}
+
+impl I2cdev {
+    /// Opens the bus behind the device file at `path`, e.g. `/dev/i2c-1`
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(I2cdev { file })
+    }
+
+    fn transaction(&mut self, messages: &mut [Message]) -> Result<(), Error> {
+        let mut data = RdwrData {
+            msgs: messages.as_mut_ptr(),
+            nmsgs: messages.len() as u32,
+        };
+        // NOTE(unsafe) the messages point to buffers that outlive the call
+        unsafe { ioctl_ptr(&self.file, I2C_RDWR, &mut data)? };
+        Ok(())
+    }
+}
+
+fn message_len(len: usize) -> io::Result<u16> {
+    u16::try_from(len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "i2c buffer too long"))
+}
+
+fn write_message(address: u8, bytes: &[u8]) -> io::Result<Message> {
+    Ok(Message {
+        addr: u16::from(address),
+        flags: 0,
+        len: message_len(bytes.len())?,
+        // the kernel doesn't write to the buffer of a write message
+        buf: bytes.as_ptr() as *mut u8,
+    })
+}
+
+fn read_message(address: u8, buffer: &mut [u8]) -> io::Result<Message> {
+    Ok(Message {
+        addr: u16::from(address),
+        flags: I2C_M_RD,
+        len: message_len(buffer.len())?,
+        buf: buffer.as_mut_ptr(),
+    })
+}
+
+impl ErrorType for I2cdev {
+    type Error = Error;
+}
+
+impl Read for I2cdev {
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        self.transaction(&mut [read_message(address, buffer)?])
+    }
+}
+
+impl Write for I2cdev {
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Error> {
+        self.transaction(&mut [write_message(address, bytes)?])
+    }
+}
+
+impl WriteRead for I2cdev {
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Error> {
+        self.transaction(&mut [write_message(address, bytes)?, read_message(address, buffer)?])
+    }
+}
+
+impl Transactional for I2cdev {
+    fn exec(&mut self, address: u8, operations: &mut [Operation]) -> Result<(), Error> {
+        let mut messages: Vec<Message> = Vec::with_capacity(operations.len());
+        for operation in operations.iter_mut() {
+            let mut message = match *operation {
+                Operation::Read(ref mut buffer) => read_message(address, buffer)?,
+                Operation::Write(bytes) => write_message(address, bytes)?,
+            };
+            // consecutive operations of the same kind are merged: no repeated start, no address
+            if let Some(last) = messages.last() {
+                if last.flags & I2C_M_RD == message.flags & I2C_M_RD {
+                    message.flags |= I2C_M_NOSTART;
+                }
+            }
+            messages.push(message);
+        }
+        self.transaction(&mut messages)
+    }
+}
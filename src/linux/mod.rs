@@ -0,0 +1,120 @@
+//! Implementations of the HAL traits over the Linux userspace interfaces
+//!
+//! *This module is available if embedded-hal is built with the `"std"` feature, on x86, x86-64,
+//! ARM and AArch64 Linux.*
+//!
+//! The ioctl request codes, the `open` flags and the `termios` layout and constants used here are
+//! those of these architectures; other ones, e.g. MIPS or PowerPC, use different values.
+//!
+//! These let drivers be developed and integration-tested on e.g. a Raspberry Pi, with the real
+//! device wired to its pins, before they are used on a microcontroller:
+//!
+//! - [`i2c::I2cdev`] drives an I2C bus through `/dev/i2c-*`
+//! - [`spi::Spidev`] drives an SPI device through `/dev/spidev*`
+//! - [`digital::CdevPin`] drives a GPIO line through `/dev/gpiochip*`
+//! - [`serial::Serial`] drives a serial port through `/dev/tty*`
+//! - [`delay::Delay`] sleeps the calling thread
+//!
+//! The devices report the errors of the underlying system calls as [`Error`]s.
+//!
+//! [`i2c::I2cdev`]: i2c/struct.I2cdev.html
+//! [`spi::Spidev`]: spi/struct.Spidev.html
+//! [`digital::CdevPin`]: digital/struct.CdevPin.html
+//! [`serial::Serial`]: serial/struct.Serial.html
+//! [`delay::Delay`]: delay/struct.Delay.html
+//! [`Error`]: struct.Error.html
+
+use core::fmt;
+use std::fs::File;
+use std::io;
+use std::os::raw::{c_int, c_ulong};
+use std::os::unix::io::AsRawFd;
+
+pub mod delay;
+pub mod digital;
+pub mod i2c;
+pub mod serial;
+pub mod spi;
+
+/// Error of a system call made by one of the devices of this module
+#[derive(Debug)]
+pub struct Error {
+    io: io::Error,
+}
+
+impl Error {
+    /// Returns the underlying I/O error
+    pub fn io_error(&self) -> &io::Error {
+        &self.io
+    }
+
+    /// Returns the underlying I/O error, consuming this error
+    pub fn into_io_error(self) -> io::Error {
+        self.io
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(io: io::Error) -> Self {
+        Error { io }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.io, f)
+    }
+}
+
+impl ::std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)> {
+        Some(&self.io)
+    }
+}
+
+impl ::i2c::Error for Error {
+    fn kind(&self) -> ::i2c::ErrorKind {
+        const ENXIO: i32 = 6;
+        const EREMOTEIO: i32 = 121;
+
+        match self.io.raw_os_error() {
+            Some(ENXIO) => ::i2c::ErrorKind::NoAcknowledge(::i2c::NoAcknowledgeSource::Address),
+            Some(EREMOTEIO) => ::i2c::ErrorKind::NoAcknowledge(::i2c::NoAcknowledgeSource::Unknown),
+            _ => ::i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl ::digital::Error for Error {
+    fn kind(&self) -> ::digital::ErrorKind {
+        ::digital::ErrorKind::Other
+    }
+}
+
+impl ::serial::Error for Error {
+    fn kind(&self) -> ::serial::ErrorKind {
+        ::serial::ErrorKind::Other
+    }
+}
+
+impl ::spi::Error for Error {
+    fn kind(&self) -> ::spi::ErrorKind {
+        ::spi::ErrorKind::Other
+    }
+}
+
+extern "C" {
+    fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+}
+
+/// Issues the `request` ioctl on `file`
+///
+/// # Safety
+///
+/// `arg` must point to what `request` expects
+unsafe fn ioctl_ptr<T>(file: &File, request: c_ulong, arg: *mut T) -> io::Result<c_int> {
+    match ioctl(file.as_raw_fd(), request, arg) {
+        -1 => Err(io::Error::last_os_error()),
+        result => Ok(result),
+    }
+}
@@ -0,0 +1,173 @@
+//! Serial ports, through the TTY interface
+//!
+//! # Examples
+//!
+//! Echoing what's received on the UART of the pin header of a Raspberry Pi
+//!
+//! ```no_run
+//! #[macro_use(block)]
+//! extern crate nb;
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::linux::serial::Serial;
+//! use hal::serial::{Read, Write};
+//!
+//! fn main() {
+//!     let mut serial = Serial::open("/dev/ttyAMA0", 115_200).unwrap();
+//!
+//!     loop {
+//!         let byte = block!(serial.read()).unwrap();
+//!         block!(serial.write(byte)).unwrap();
+//!     }
+//! }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read as _Read, Write as _Write};
+use std::os::raw::c_int;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use blocking::serial::write;
+use nb;
+use serial::{ErrorType, Read, Write};
+
+use super::Error;
+
+const O_NOCTTY: c_int = 0o400;
+const O_NONBLOCK: c_int = 0o4000;
+
+const TCSANOW: c_int = 0;
+const CREAD: u32 = 0o200;
+const CLOCAL: u32 = 0o4000;
+
+#[repr(C)]
+struct Termios {
+    c_iflag: u32,
+    c_oflag: u32,
+    c_cflag: u32,
+    c_lflag: u32,
+    c_line: u8,
+    c_cc: [u8; 32],
+    c_ispeed: u32,
+    c_ospeed: u32,
+}
+
+extern "C" {
+    fn tcgetattr(fd: c_int, termios: *mut Termios) -> c_int;
+    fn tcsetattr(fd: c_int, optional_actions: c_int, termios: *const Termios) -> c_int;
+    fn cfmakeraw(termios: *mut Termios);
+    fn cfsetspeed(termios: *mut Termios, speed: u32) -> c_int;
+    fn tcdrain(fd: c_int) -> c_int;
+}
+
+fn check(result: c_int) -> io::Result<()> {
+    match result {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the `speed_t` of `baud_rate`
+fn speed(baud_rate: u32) -> io::Result<u32> {
+    Ok(match baud_rate {
+        1_200 => 0o11,
+        2_400 => 0o13,
+        4_800 => 0o14,
+        9_600 => 0o15,
+        19_200 => 0o16,
+        38_400 => 0o17,
+        57_600 => 0o10001,
+        115_200 => 0o10002,
+        230_400 => 0o10003,
+        460_800 => 0o10004,
+        500_000 => 0o10005,
+        576_000 => 0o10006,
+        921_600 => 0o10007,
+        1_000_000 => 0o10010,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported baud rate",
+            ))
+        }
+    })
+}
+
+/// Serial port behind a `/dev/tty*` device file
+///
+/// The port is configured for raw 8N1 communication, without flow control. Reads and writes
+/// never block: they return `WouldBlock` instead.
+#[derive(Debug)]
+pub struct Serial {
+    file: File,
+}
+
+impl Serial {
+    /// Opens the port behind the device file at `path`, e.g. `/dev/ttyUSB0`, at `baud_rate`
+    ///
+    /// Only the standard baud rates, from 1200 to 1000000 bauds, are supported.
+    pub fn open<P>(path: P, baud_rate: u32) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let speed = speed(baud_rate)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(O_NOCTTY | O_NONBLOCK)
+            .open(path)?;
+
+        // NOTE(unsafe) `Termios` has the layout of the glibc `struct termios`, and is fully
+        // initialized by `tcgetattr`
+        unsafe {
+            let mut termios: Termios = ::core::mem::zeroed();
+            check(tcgetattr(file.as_raw_fd(), &mut termios))?;
+            cfmakeraw(&mut termios);
+            termios.c_cflag |= CREAD | CLOCAL;
+            check(cfsetspeed(&mut termios, speed))?;
+            check(tcsetattr(file.as_raw_fd(), TCSANOW, &termios))?;
+        }
+
+        Ok(Serial { file })
+    }
+}
+
+fn nb_error(error: io::Error) -> nb::Error<Error> {
+    if error.kind() == io::ErrorKind::WouldBlock {
+        nb::Error::WouldBlock
+    } else {
+        nb::Error::Other(error.into())
+    }
+}
+
+impl ErrorType for Serial {
+    type Error = Error;
+}
+
+impl Read<u8> for Serial {
+    fn read(&mut self) -> nb::Result<u8, Error> {
+        let mut byte = [0];
+        match self.file.read(&mut byte).map_err(nb_error)? {
+            0 => Err(nb::Error::WouldBlock),
+            _ => Ok(byte[0]),
+        }
+    }
+}
+
+impl Write<u8> for Serial {
+    fn write(&mut self, word: u8) -> nb::Result<(), Error> {
+        match self.file.write(&[word]).map_err(nb_error)? {
+            0 => Err(nb::Error::WouldBlock),
+            _ => Ok(()),
+        }
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Error> {
+        // NOTE(unsafe) FFI call on a file descriptor we own
+        check(unsafe { tcdrain(self.file.as_raw_fd()) }).map_err(nb_error)
+    }
+}
+
+impl write::Default<u8> for Serial {}
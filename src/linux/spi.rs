@@ -0,0 +1,209 @@
+//! SPI devices, through the spidev interface
+//!
+//! # Examples
+//!
+//! Reading the ID register of a device on the first chip select of a Raspberry Pi
+//!
+//! ```no_run
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::spi::Transfer;
+//! use hal::linux::spi::Spidev;
+//! use hal::spi::{Mode, Phase, Polarity};
+//!
+//! fn main() {
+//!     let mut spi = Spidev::open("/dev/spidev0.0").unwrap();
+//!     let mode = Mode {
+//!         polarity: Polarity::IdleLow,
+//!         phase: Phase::CaptureOnFirstTransition,
+//!     };
+//!     spi.configure(&mode, 1_000_000).unwrap();
+//!
+//!     let mut id = [0x8f, 0];
+//!     spi.transfer(&mut id).unwrap();
+//! }
+//! ```
+
+use std::convert::TryFrom;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write as _Write};
+use std::mem;
+use std::os::raw::c_ulong;
+use std::path::Path;
+use std::vec::Vec;
+
+use blocking::spi::{Operation, Transactional, Transfer, Write, WriteIter};
+use spi::{ErrorType, Mode, Phase, Polarity};
+
+use super::{ioctl_ptr, Error};
+
+const SPI_IOC_MESSAGE_0: c_ulong = 0x4000_6b00;
+const SPI_IOC_WR_MODE: c_ulong = 0x4001_6b01;
+const SPI_IOC_WR_MAX_SPEED_HZ: c_ulong = 0x4004_6b04;
+
+const SPI_CPHA: u8 = 0x01;
+const SPI_CPOL: u8 = 0x02;
+
+/// `SPI_IOC_MESSAGE(n)`, whose size field is 14 bits wide
+fn spi_ioc_message(n: usize) -> io::Result<c_ulong> {
+    let size = n * mem::size_of::<IocTransfer>();
+    if size < 1 << 14 {
+        Ok(SPI_IOC_MESSAGE_0 | (size as c_ulong) << 16)
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidInput, "too many spi operations"))
+    }
+}
+
+fn transfer_len(words: &[u8]) -> io::Result<u32> {
+    u32::try_from(words.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "spi buffer too long"))
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct IocTransfer {
+    tx_buf: u64,
+    rx_buf: u64,
+    len: u32,
+    speed_hz: u32,
+    delay_usecs: u16,
+    bits_per_word: u8,
+    cs_change: u8,
+    tx_nbits: u8,
+    rx_nbits: u8,
+    word_delay_usecs: u8,
+    pad: u8,
+}
+
+/// SPI device behind a `/dev/spidev*` device file
+///
+/// Unlike the `spi` traits, which leave it to their user, the kernel asserts the chip select line
+/// of the device around each transfer, write and transaction.
+///
+/// The kernel rejects the transfers, writes and transactions longer than the `bufsiz` parameter
+/// of the `spidev` module, 4096 bytes by default: they fail with an error of kind `Other`. They
+/// are not split in smaller ones, which would deassert the chip select in the middle.
+#[derive(Debug)]
+pub struct Spidev {
+    file: File,
+}
+
+impl Spidev {
+    /// Opens the device behind the device file at `path`, e.g. `/dev/spidev0.0`
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(Spidev { file })
+    }
+
+    /// Sets the SPI mode and the maximum clock frequency of the transfers
+    pub fn configure(&mut self, mode: &Mode, max_speed_hz: u32) -> io::Result<()> {
+        let mut bits = 0;
+        if mode.polarity == Polarity::IdleHigh {
+            bits |= SPI_CPOL;
+        }
+        if mode.phase == Phase::CaptureOnSecondTransition {
+            bits |= SPI_CPHA;
+        }
+        let mut max_speed_hz = max_speed_hz;
+
+        // NOTE(unsafe) these ioctls read a `u8` and a `u32`, respectively
+        unsafe {
+            ioctl_ptr(&self.file, SPI_IOC_WR_MODE, &mut bits)?;
+            ioctl_ptr(&self.file, SPI_IOC_WR_MAX_SPEED_HZ, &mut max_speed_hz)?;
+        }
+        Ok(())
+    }
+}
+
+impl ErrorType for Spidev {
+    type Error = Error;
+}
+
+impl Transfer<u8> for Spidev {
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Error> {
+        // full duplex, in place: the kernel sends `words` and then overwrites them
+        let mut transfer = IocTransfer {
+            tx_buf: words.as_ptr() as u64,
+            rx_buf: words.as_mut_ptr() as u64,
+            len: transfer_len(words)?,
+            ..IocTransfer::default()
+        };
+        // NOTE(unsafe) the buffer outlives the call
+        unsafe { ioctl_ptr(&self.file, spi_ioc_message(1)?, &mut transfer)? };
+        Ok(words)
+    }
+}
+
+impl Write<u8> for Spidev {
+    fn write(&mut self, words: &[u8]) -> Result<(), Error> {
+        self.file.write_all(words)?;
+        Ok(())
+    }
+}
+
+impl WriteIter<u8> for Spidev {
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), Error>
+    where
+        WI: IntoIterator<Item = u8>,
+    {
+        // a single write, so that the chip select stays asserted
+        let words: Vec<u8> = words.into_iter().collect();
+        self.write(&words)
+    }
+}
+
+impl Transactional<u8> for Spidev {
+    fn exec(&mut self, operations: &mut [Operation<u8>]) -> Result<(), Error> {
+        // a single message, so that the chip select stays asserted between the operations
+        let mut transfers = operations
+            .iter_mut()
+            .map(|operation| match *operation {
+                Operation::Write(words) => Ok(IocTransfer {
+                    tx_buf: words.as_ptr() as u64,
+                    len: transfer_len(words)?,
+                    ..IocTransfer::default()
+                }),
+                Operation::Transfer(ref mut words) => Ok(IocTransfer {
+                    tx_buf: words.as_ptr() as u64,
+                    rx_buf: words.as_mut_ptr() as u64,
+                    len: transfer_len(words)?,
+                    ..IocTransfer::default()
+                }),
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let request = spi_ioc_message(transfers.len())?;
+        // NOTE(unsafe) the buffers outlive the call
+        unsafe { ioctl_ptr(&self.file, request, transfers.as_mut_ptr())? };
+        Ok(())
+    }
+}
+
+impl Transfer<u8> for &mut Spidev {
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Error> {
+        (**self).transfer(words)
+    }
+}
+
+impl Write<u8> for &mut Spidev {
+    fn write(&mut self, words: &[u8]) -> Result<(), Error> {
+        (**self).write(words)
+    }
+}
+
+impl WriteIter<u8> for &mut Spidev {
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), Error>
+    where
+        WI: IntoIterator<Item = u8>,
+    {
+        (**self).write_iter(words)
+    }
+}
+
+impl Transactional<u8> for &mut Spidev {
+    fn exec(&mut self, operations: &mut [Operation<u8>]) -> Result<(), Error> {
+        (**self).exec(operations)
+    }
+}
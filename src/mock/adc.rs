@@ -0,0 +1,158 @@
+//! Scripted ADC
+//!
+//! *This module is available if embedded-hal is built with the `"mock"` and `"adc"` features.*
+//!
+//! The mock implements the [`OneShot`] trait for the [`Pin`]s it provides, whose channel is a const
+//! generic parameter; its conversions complete immediately. With the `"async"` feature, it also
+//! implements the asynchronous [`futures::adc::OneShot`], whose futures are always ready.
+//!
+//! [`OneShot`]: ../../adc/trait.OneShot.html
+//! [`Pin`]: struct.Pin.html
+//! [`futures::adc::OneShot`]: ../../futures/adc/trait.OneShot.html
+//!
+//! # Examples
+//!
+//! Checking that a driver samples the right channel, and how it handles an overrun
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//! #[macro_use(block)]
+//! extern crate nb;
+//!
+//! use hal::adc::{Channel, ErrorKind, OneShot};
+//! use hal::mock::adc::{Mock, Pin, Transaction};
+//!
+//! fn battery_mv<A, P>(adc: &mut A, pin: &mut P) -> Result<u32, A::Error>
+//! where
+//!     A: OneShot<Mock, u16, P>,
+//!     P: Channel<Mock>,
+//! {
+//!     Ok(u32::from(block!(adc.read(pin))?) * 3300 / 4095 * 2)
+//! }
+//!
+//! fn main() {
+//!     let mut adc = Mock::new(&[
+//!         Transaction::read(1, 2296),
+//!         Transaction::read(1, 0).with_error(ErrorKind::Overrun),
+//!     ]);
+//!     let mut pin = Pin::<1>;
+//!
+//!     assert_eq!(battery_mv(&mut adc.clone(), &mut pin), Ok(3700));
+//!     assert_eq!(battery_mv(&mut adc.clone(), &mut pin), Err(ErrorKind::Overrun));
+//!
+//!     adc.done();
+//! }
+//! ```
+
+#[cfg(feature = "async")]
+use core::future::{self, Ready};
+
+use adc::{Channel, ErrorKind, ErrorType, OneShot};
+#[cfg(feature = "async")]
+use futures;
+use nb;
+
+use super::Expectations;
+
+/// Transaction expected by the ADC mock
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Transaction {
+    channel: u8,
+    value: u16,
+    error: Option<ErrorKind>,
+}
+
+impl Transaction {
+    /// `OneShot::read` of the `Pin` of `channel`, which returns `value`
+    pub fn read(channel: u8, value: u16) -> Self {
+        Transaction {
+            channel,
+            value,
+            error: None,
+        }
+    }
+
+    /// Makes the transaction fail with `error`
+    ///
+    /// The channel is still checked.
+    pub fn with_error(mut self, error: ErrorKind) -> Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+/// Analog pin on channel `CHANNEL` of the ADC mock
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Pin<const CHANNEL: u8>;
+
+impl<const CHANNEL: u8> Channel<Mock> for Pin<CHANNEL> {
+    type ID = u8;
+
+    fn channel() -> u8 {
+        CHANNEL
+    }
+}
+
+/// ADC mock
+///
+/// It implements `OneShot<Mock, u16, Pin<CHANNEL>>`, and its asynchronous counterpart with the
+/// `"async"` feature, with `adc::ErrorKind` as its error type.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    expectations: Expectations<Transaction>,
+}
+
+impl Mock {
+    /// Creates an ADC that expects `transactions`
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Mock {
+            expectations: Expectations::new(transactions),
+        }
+    }
+
+    /// Appends `transactions` to the expected ones
+    pub fn expect(&mut self, transactions: &[Transaction]) {
+        self.expectations.extend(transactions);
+    }
+
+    /// Checks that all the expected transactions happened
+    ///
+    /// # Panics
+    ///
+    /// If some of them didn't
+    pub fn done(&mut self) {
+        self.expectations.done();
+    }
+}
+
+impl ErrorType for Mock {
+    type Error = ErrorKind;
+}
+
+impl Mock {
+    fn convert(&mut self, channel: u8) -> Result<u16, ErrorKind> {
+        let transaction = self.expectations.next("adc read");
+        assert_eq!(channel, transaction.channel, "unexpected adc channel");
+        match transaction.error {
+            Some(error) => Err(error),
+            None => Ok(transaction.value),
+        }
+    }
+}
+
+impl<const CHANNEL: u8> OneShot<Mock, u16, Pin<CHANNEL>> for Mock {
+    fn read(&mut self, _: &mut Pin<CHANNEL>) -> nb::Result<u16, ErrorKind> {
+        Ok(self.convert(CHANNEL)?)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const CHANNEL: u8> futures::adc::OneShot<Mock, u16, Pin<CHANNEL>> for Mock {
+    type ReadFuture<'a> = Ready<Result<u16, ErrorKind>>;
+
+    fn read<'a>(&'a mut self, _: &'a mut Pin<CHANNEL>) -> Self::ReadFuture<'a> {
+        future::ready(self.convert(CHANNEL))
+    }
+}
@@ -0,0 +1,78 @@
+//! Delay that doesn't wait
+//!
+//! # Examples
+//!
+//! Checking that a driver gives a device enough time to boot
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::delay::DelayMs;
+//! use hal::mock::delay::Mock;
+//!
+//! fn power_up<D: DelayMs<u8>>(delay: &mut D) {
+//!     // ..
+//!     delay.delay_ms(20);
+//!     // ..
+//!     delay.delay_ms(5);
+//! }
+//!
+//! fn main() {
+//!     let delay = Mock::new();
+//!
+//!     power_up(&mut delay.clone());
+//!
+//!     assert!(delay.elapsed_us() >= 25_000);
+//! }
+//! ```
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+
+use blocking::delay::{DelayMs, DelayUs};
+
+/// Delay mock
+///
+/// Instead of waiting, it adds up the delays it's asked for, so that tests run at full speed but
+/// can still check the timing of the code under test. It implements the blocking `DelayMs` and
+/// `DelayUs` for `u8`, `u16` and `u32`. Its clones share the elapsed time.
+#[derive(Clone, Debug, Default)]
+pub struct Mock {
+    elapsed_us: Rc<Cell<u64>>,
+}
+
+impl Mock {
+    /// Creates a delay that hasn't elapsed any time yet
+    pub fn new() -> Self {
+        Mock::default()
+    }
+
+    /// Returns the sum of the delays so far, in microseconds
+    pub fn elapsed_us(&self) -> u64 {
+        self.elapsed_us.get()
+    }
+
+    fn elapse(&self, us: u64) {
+        self.elapsed_us.set(self.elapsed_us.get() + us);
+    }
+}
+
+macro_rules! impl_delay {
+    ($($UXX:ty),+) => {
+        $(
+            impl DelayMs<$UXX> for Mock {
+                fn delay_ms(&mut self, ms: $UXX) {
+                    self.elapse(u64::from(ms) * 1_000);
+                }
+            }
+
+            impl DelayUs<$UXX> for Mock {
+                fn delay_us(&mut self, us: $UXX) {
+                    self.elapse(u64::from(us));
+                }
+            }
+        )+
+    }
+}
+
+impl_delay!(u8, u16, u32);
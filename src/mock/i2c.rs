@@ -0,0 +1,310 @@
+//! Scripted I2C bus
+//!
+//! # Examples
+//!
+//! Checking that a driver reads the right register of the right device, and how it handles a
+//! missing device
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::i2c::WriteRead;
+//! use hal::i2c::{ErrorKind, NoAcknowledgeSource};
+//! use hal::mock::i2c::{Mock, Transaction};
+//!
+//! const ADDRESS: u8 = 0x48;
+//!
+//! fn temperature<I: WriteRead>(i2c: &mut I) -> Result<i16, I::Error> {
+//!     let mut buffer = [0; 2];
+//!     i2c.write_read(ADDRESS, &[0x00], &mut buffer)?;
+//!     Ok(i16::from_be_bytes(buffer) >> 4)
+//! }
+//!
+//! fn main() {
+//!     let absent = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+//!     let mut i2c = Mock::new(&[
+//!         Transaction::write_read(ADDRESS, &[0x00], &[0x19, 0x00]),
+//!         Transaction::write_read(ADDRESS, &[0x00], &[0, 0]).with_error(absent),
+//!     ]);
+//!
+//!     assert_eq!(temperature(&mut i2c.clone()), Ok(0x190));
+//!     assert_eq!(temperature(&mut i2c.clone()), Err(absent));
+//!
+//!     i2c.done();
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+use blocking::i2c::{Operation, Read, Transactional, Write, WriteRead};
+use i2c::{ErrorKind, ErrorType};
+
+use super::Expectations;
+
+/// Transaction expected by the I2C mock
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    address: u8,
+    kind: Kind,
+    error: Option<ErrorKind>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Kind {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    WriteRead(Vec<u8>, Vec<u8>),
+    Exec(Vec<Segment>),
+}
+
+/// Segment of a transaction expected by `Transaction::exec`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    kind: SegmentKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum SegmentKind {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+}
+
+impl Segment {
+    /// `Operation::Read`, which returns `response`
+    pub fn read(response: &[u8]) -> Self {
+        Segment {
+            kind: SegmentKind::Read(response.to_vec()),
+        }
+    }
+
+    /// `Operation::Write` of `bytes`
+    pub fn write(bytes: &[u8]) -> Self {
+        Segment {
+            kind: SegmentKind::Write(bytes.to_vec()),
+        }
+    }
+}
+
+impl Transaction {
+    /// `Read::read` from the device at `address`, which returns `response`
+    pub fn read(address: u8, response: &[u8]) -> Self {
+        Transaction::new(address, Kind::Read(response.to_vec()))
+    }
+
+    /// `Write::write` of `bytes` to the device at `address`
+    pub fn write(address: u8, bytes: &[u8]) -> Self {
+        Transaction::new(address, Kind::Write(bytes.to_vec()))
+    }
+
+    /// `WriteRead::write_read` of `bytes` to the device at `address`, which returns `response`
+    pub fn write_read(address: u8, bytes: &[u8], response: &[u8]) -> Self {
+        Transaction::new(address, Kind::WriteRead(bytes.to_vec(), response.to_vec()))
+    }
+
+    /// `Transactional::exec` of `segments` on the device at `address`
+    ///
+    /// As on the bus, consecutive operations of the same kind are merged, on both sides: e.g.
+    /// `Operation::Write(&[1])` followed by `Operation::Write(&[2, 3])` match `Segment::write(&[1,
+    /// 2, 3])`, and vice versa.
+    pub fn exec(address: u8, segments: &[Segment]) -> Self {
+        Transaction::new(address, Kind::Exec(segments.to_vec()))
+    }
+
+    /// Makes the transaction fail with `error`
+    ///
+    /// The arguments of the call are still checked, and the buffer of a failed read is still
+    /// filled.
+    pub fn with_error(mut self, error: ErrorKind) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    fn new(address: u8, kind: Kind) -> Self {
+        Transaction {
+            address,
+            kind,
+            error: None,
+        }
+    }
+
+    fn result(&self) -> Result<(), ErrorKind> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// I2C bus mock
+///
+/// It implements the blocking `Read`, `Write`, `WriteRead` and `Transactional` traits, for 7-bit
+/// addresses, with `i2c::ErrorKind` as its error type.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    expectations: Expectations<Transaction>,
+}
+
+impl Mock {
+    /// Creates a bus that expects `transactions`
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Mock {
+            expectations: Expectations::new(transactions),
+        }
+    }
+
+    /// Appends `transactions` to the expected ones
+    pub fn expect(&mut self, transactions: &[Transaction]) {
+        self.expectations.extend(transactions);
+    }
+
+    /// Checks that all the expected transactions happened
+    ///
+    /// # Panics
+    ///
+    /// If some of them didn't
+    pub fn done(&mut self) {
+        self.expectations.done();
+    }
+
+    fn next(&mut self, operation: &str, address: u8) -> Transaction {
+        let transaction = self.expectations.next(operation);
+        assert_eq!(address, transaction.address, "unexpected i2c address");
+        transaction
+    }
+}
+
+fn is_read(operation: &Operation) -> bool {
+    match *operation {
+        Operation::Read(_) => true,
+        Operation::Write(_) => false,
+    }
+}
+
+fn operation_len(operation: &Operation) -> usize {
+    match *operation {
+        Operation::Read(ref buffer) => buffer.len(),
+        Operation::Write(bytes) => bytes.len(),
+    }
+}
+
+/// Merges the consecutive segments of the same kind, as they are transferred on the bus
+fn merge(segments: &[Segment]) -> Vec<SegmentKind> {
+    let mut merged: Vec<SegmentKind> = Vec::new();
+    for segment in segments {
+        match (merged.last_mut(), &segment.kind) {
+            (Some(&mut SegmentKind::Read(ref mut bytes)), &SegmentKind::Read(ref more))
+            | (Some(&mut SegmentKind::Write(ref mut bytes)), &SegmentKind::Write(ref more)) => {
+                bytes.extend_from_slice(more)
+            }
+            (_, kind) => merged.push(kind.clone()),
+        }
+    }
+    merged
+}
+
+fn respond(buffer: &mut [u8], response: &[u8]) {
+    assert_eq!(buffer.len(), response.len(), "unexpected i2c read length");
+    buffer.copy_from_slice(response);
+}
+
+impl ErrorType for Mock {
+    type Error = ErrorKind;
+}
+
+impl Read for Mock {
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), ErrorKind> {
+        let transaction = self.next("i2c read", address);
+        match transaction.kind {
+            Kind::Read(ref response) => respond(buffer, response),
+            _ => panic!("unexpected i2c read, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+}
+
+impl Write for Mock {
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), ErrorKind> {
+        let transaction = self.next("i2c write", address);
+        match transaction.kind {
+            Kind::Write(ref expected) => assert_eq!(*bytes, **expected, "unexpected i2c write"),
+            _ => panic!("unexpected i2c write, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+}
+
+impl WriteRead for Mock {
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), ErrorKind> {
+        let transaction = self.next("i2c write_read", address);
+        match transaction.kind {
+            Kind::WriteRead(ref expected, ref response) => {
+                assert_eq!(*bytes, **expected, "unexpected i2c write_read");
+                respond(buffer, response);
+            }
+            _ => panic!("unexpected i2c write_read, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+}
+
+impl Transactional for Mock {
+    fn exec(&mut self, address: u8, operations: &mut [Operation]) -> Result<(), ErrorKind> {
+        let transaction = self.next("i2c exec", address);
+        match transaction.kind {
+            Kind::Exec(ref segments) => {
+                // consecutive operations of the same kind are a single transfer on the bus, so
+                // they are compared with the merged segments
+                let expected = merge(segments);
+                let mut expected = expected.iter();
+                let mut start = 0;
+                while start < operations.len() {
+                    let reading = is_read(&operations[start]);
+                    let end = start
+                        + operations[start..]
+                            .iter()
+                            .take_while(|operation| is_read(operation) == reading)
+                            .count();
+                    let run = &mut operations[start..end];
+                    match (reading, expected.next()) {
+                        (true, Some(SegmentKind::Read(response))) => {
+                            let len: usize = run.iter().map(operation_len).sum();
+                            assert_eq!(len, response.len(), "unexpected i2c read length");
+                            let mut response = &response[..];
+                            for operation in run.iter_mut() {
+                                if let Operation::Read(ref mut buffer) = *operation {
+                                    let (head, tail) = response.split_at(buffer.len());
+                                    buffer.copy_from_slice(head);
+                                    response = tail;
+                                }
+                            }
+                        }
+                        (false, Some(SegmentKind::Write(expected))) => {
+                            let mut bytes = Vec::new();
+                            for operation in run.iter() {
+                                if let Operation::Write(chunk) = *operation {
+                                    bytes.extend_from_slice(chunk);
+                                }
+                            }
+                            assert_eq!(bytes, *expected, "unexpected i2c exec write")
+                        }
+                        (_, segment) => panic!(
+                            "unexpected i2c exec operations {:?}, expected {:?}",
+                            run, segment
+                        ),
+                    }
+                    start = end;
+                }
+                if let Some(segment) = expected.next() {
+                    panic!("missing i2c exec operation, expected {:?}", segment);
+                }
+            }
+            _ => panic!("unexpected i2c exec, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+}
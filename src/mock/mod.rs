@@ -0,0 +1,138 @@
+//! Mock implementations of the HAL traits
+//!
+//! *This module is available if embedded-hal is built with the `"mock"` feature.*
+//!
+//! These test doubles let driver and middleware crates exercise their code on the host, without
+//! hardware.
+//!
+//! The pin, SPI, I2C, serial and ADC mocks are scripted: they are created from the list of
+//! transactions the code under test is expected to perform, in order, and panic as soon as it
+//! does something else. Clones of a mock share its expectations, so a test can hand a clone to a
+//! driver and keep the original to call `done`, which panics if some of the expected transactions
+//! didn't happen. The `"mock"` feature enables the `"alloc"` feature to store the expectations.
+//!
+//! # Examples
+//!
+//! Unit testing a display driver written against `SpiDevice`: the bus mock and a pin mock for the
+//! chip select make up the device, with an `ExclusiveDevice`, and the delay mock checks that the
+//! reset pulse is long enough
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::delay::DelayMs;
+//! use hal::blocking::spi::{ExclusiveDevice, Operation, SpiDevice};
+//! use hal::digital::{OutputPin, PinState};
+//! use hal::mock::pin;
+//! use hal::mock::{delay, spi};
+//!
+//! struct Display<SPI, RST> {
+//!     spi: SPI,
+//!     reset: RST,
+//! }
+//!
+//! impl<SPI, RST> Display<SPI, RST>
+//! where
+//!     SPI: SpiDevice<u8>,
+//!     RST: OutputPin,
+//! {
+//!     fn init<D: DelayMs<u8>>(&mut self, delay: &mut D) -> Result<(), SPI::Error> {
+//!         self.reset.set_low();
+//!         delay.delay_ms(10);
+//!         self.reset.set_high();
+//!         // sleep out, display on
+//!         self.spi.transaction(&mut [Operation::Write(&[0x11]), Operation::Write(&[0x29])])
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut bus = spi::Mock::new(&[
+//!         spi::Transaction::write(&[0x11]),
+//!         spi::Transaction::write(&[0x29]),
+//!     ]);
+//!     let mut cs = pin::Mock::new(&[
+//!         // `ExclusiveDevice::new` deselects the device
+//!         pin::Transaction::Set(PinState::High),
+//!         pin::Transaction::Set(PinState::Low),
+//!         pin::Transaction::Set(PinState::High),
+//!     ]);
+//!     let mut reset = pin::Mock::new(&[
+//!         pin::Transaction::Set(PinState::Low),
+//!         pin::Transaction::Set(PinState::High),
+//!     ]);
+//!     let delay = delay::Mock::new();
+//!
+//!     let mut display = Display {
+//!         spi: ExclusiveDevice::new(bus.clone(), cs.clone()),
+//!         reset: reset.clone(),
+//!     };
+//!     display.init(&mut delay.clone()).unwrap();
+//!
+//!     assert_eq!(delay.elapsed_us(), 10_000);
+//!     bus.done();
+//!     cs.done();
+//!     reset.done();
+//! }
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::fmt::Debug;
+
+#[cfg(feature = "adc")]
+pub mod adc;
+pub mod delay;
+pub mod i2c;
+#[cfg(feature = "storage")]
+pub mod nor_flash;
+pub mod pin;
+pub mod serial;
+pub mod spi;
+
+/// Expected transactions of a scripted mock, shared by its clones
+#[derive(Debug)]
+struct Expectations<T> {
+    queue: Rc<RefCell<VecDeque<T>>>,
+}
+
+impl<T> Expectations<T>
+where
+    T: Clone + Debug,
+{
+    fn new(transactions: &[T]) -> Self {
+        Expectations {
+            queue: Rc::new(RefCell::new(transactions.iter().cloned().collect())),
+        }
+    }
+
+    fn extend(&self, transactions: &[T]) {
+        self.queue.borrow_mut().extend(transactions.iter().cloned());
+    }
+
+    /// Pops the next expected transaction; `operation` names the call for the panic message
+    fn next(&self, operation: &str) -> T {
+        match self.queue.borrow_mut().pop_front() {
+            Some(transaction) => transaction,
+            None => panic!("unexpected {}: no more transactions were expected", operation),
+        }
+    }
+
+    fn done(&self) {
+        let queue = self.queue.borrow();
+        assert!(
+            queue.is_empty(),
+            "{} expected transaction(s) didn't happen: {:?}",
+            queue.len(),
+            *queue
+        );
+    }
+}
+
+impl<T> Clone for Expectations<T> {
+    fn clone(&self) -> Self {
+        Expectations {
+            queue: self.queue.clone(),
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! RAM-backed NOR flash
+//!
+//! *This module is available if embedded-hal is built with the `"mock"` and `"storage"` features.*
+//!
+//! [`Mock`] behaves like a real NOR flash: it starts out erased, `write`s can only clear bits and
+//! misaligned operations are rejected. On top of that it counts the erases of each block, can wear
+//! blocks out and can be told to fail, or to be interrupted half-way through, an upcoming operation.
+//!
+//! [`Mock`]: struct.Mock.html
+//!
+//! # Examples
+//!
+//! Checking that a log survives a power loss in the middle of an append
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::mock::nor_flash::{Error, Failure, Mock};
+//! use hal::storage::nor_flash::{NorFlash, ReadNorFlash};
+//!
+//! fn main() {
+//!     // 4 blocks of 256 bytes, programmed in 4-byte words
+//!     let mut flash = Mock::<4, 256, 4>::new();
+//!
+//!     flash.write(0, b"rec1").unwrap();
+//!
+//!     // power is lost after the first 2 bytes of the next write
+//!     flash.fail_after(0, Failure::Partial(2));
+//!     assert_eq!(flash.write(4, b"rec2"), Err(Error::Injected));
+//!     assert_eq!(flash.memory()[..8], *b"rec1re\xff\xff");
+//!
+//!     // the failure only affected that one operation
+//!     flash.erase(0, 256).unwrap();
+//!     assert_eq!(flash.erase_count(0), 1);
+//!
+//!     let mut record = [0; 4];
+//!     flash.read(0, &mut record).unwrap();
+//!     assert_eq!(record, [0xff; 4]);
+//! }
+//! ```
+
+use core::fmt;
+
+use storage::nor_flash::{MultiwriteNorFlash, NorFlash, ReadNorFlash};
+use storage::{self, ErrorKind, ErrorType};
+
+/// Error returned by the mock
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Error {
+    /// The arguments were not aligned to the read, write or erase size
+    NotAligned,
+    /// The operation would have gone past the end of the flash
+    OutOfBounds,
+    /// A block was erased more times than the endurance set with `set_endurance`
+    WornOut,
+    /// The operation failed because of a failure injected with `fail_after`
+    Injected,
+}
+
+impl storage::Error for Error {
+    fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::NotAligned => ErrorKind::NotAligned,
+            Error::OutOfBounds => ErrorKind::OutOfBounds,
+            Error::WornOut | Error::Injected => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Error::NotAligned => "the arguments were not aligned to the read, write or erase size",
+            Error::OutOfBounds => "the access was out of the bounds of the flash",
+            Error::WornOut => "the block is worn out",
+            Error::Injected => "injected failure",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for Error {}
+
+/// Failure to inject in an erase or write operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Failure {
+    /// The operation fails without modifying the memory
+    Error,
+    /// The operation only modifies its first `n` bytes before failing, as if power was lost
+    Partial(usize),
+}
+
+/// NOR flash mock made of `BLOCKS` erase blocks of `ERASE_SIZE` bytes, programmed in units of
+/// `WRITE_SIZE` bytes
+///
+/// Programming ANDs the data with the previous contents, so the mock also implements
+/// `MultiwriteNorFlash`.
+pub struct Mock<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> {
+    memory: [[u8; ERASE_SIZE]; BLOCKS],
+    erase_counts: [u32; BLOCKS],
+    endurance: Option<u32>,
+    failure: Option<(u32, Failure)>,
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize>
+    Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+    /// Creates an erased flash
+    ///
+    /// # Panics
+    ///
+    /// If `ERASE_SIZE` or `WRITE_SIZE` is zero
+    pub const fn new() -> Self {
+        assert!(ERASE_SIZE != 0, "ERASE_SIZE must not be zero");
+        assert!(WRITE_SIZE != 0, "WRITE_SIZE must not be zero");
+        Mock {
+            memory: [[0xff; ERASE_SIZE]; BLOCKS],
+            erase_counts: [0; BLOCKS],
+            endurance: None,
+            failure: None,
+        }
+    }
+
+    /// Returns the contents of the flash
+    pub fn memory(&self) -> &[u8] {
+        self.memory.as_flattened()
+    }
+
+    /// Returns the contents of the flash, to, for example, flip bits behind the back of the code
+    /// under test
+    pub fn memory_mut(&mut self) -> &mut [u8] {
+        self.memory.as_flattened_mut()
+    }
+
+    /// Returns the number of times `block` has been successfully erased
+    ///
+    /// # Panics
+    ///
+    /// If `block` is not less than `BLOCKS`
+    pub fn erase_count(&self, block: usize) -> u32 {
+        self.erase_counts[block]
+    }
+
+    /// Makes erasing a block that has already been erased `erases` times fail with
+    /// `Error::WornOut`; `None` (the default) means that blocks never wear out
+    pub fn set_endurance(&mut self, erases: Option<u32>) {
+        self.endurance = erases;
+    }
+
+    /// Makes the erase or write operation that follows the next `operations` successful ones fail
+    ///
+    /// Only one failure can be pending at a time; calling this method again replaces it.
+    pub fn fail_after(&mut self, operations: u32, failure: Failure) {
+        self.failure = Some((operations, failure));
+    }
+
+    /// Counts one erase or write operation; returns the failure to inject in it, if any
+    fn tick(&mut self) -> Option<Failure> {
+        match self.failure {
+            Some((0, failure)) => {
+                self.failure = None;
+                Some(failure)
+            }
+            Some((ref mut operations, _)) => {
+                *operations -= 1;
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn check(offset: usize, len: usize, align: usize) -> Result<(), Error> {
+        if offset / align * align != offset || len / align * align != len {
+            Err(Error::NotAligned)
+        } else if offset + len > BLOCKS * ERASE_SIZE {
+            Err(Error::OutOfBounds)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> Default
+    for Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> ErrorType
+    for Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+    type Error = Error;
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> ReadNorFlash
+    for Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Error> {
+        let offset = offset as usize;
+        Self::check(offset, bytes.len(), Self::READ_SIZE)?;
+
+        bytes.copy_from_slice(&self.memory()[offset..offset + bytes.len()]);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        BLOCKS * ERASE_SIZE
+    }
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> NorFlash
+    for Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+    const WRITE_SIZE: usize = WRITE_SIZE;
+
+    const ERASE_SIZE: usize = ERASE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Error> {
+        let (from, to) = (from as usize, to as usize);
+        if from > to {
+            return Err(Error::OutOfBounds);
+        }
+        Self::check(from, to - from, ERASE_SIZE)?;
+
+        let blocks = from / ERASE_SIZE..to / ERASE_SIZE;
+        if let Some(endurance) = self.endurance {
+            if blocks.clone().any(|block| self.erase_counts[block] >= endurance) {
+                return Err(Error::WornOut);
+            }
+        }
+
+        let (len, result) = match self.tick() {
+            None => (to - from, Ok(())),
+            Some(Failure::Error) => (0, Err(Error::Injected)),
+            Some(Failure::Partial(n)) => (n.min(to - from), Err(Error::Injected)),
+        };
+
+        for byte in &mut self.memory_mut()[from..from + len] {
+            *byte = 0xff;
+        }
+        // only the erases that complete count
+        if result.is_ok() {
+            for block in blocks {
+                self.erase_counts[block] += 1;
+            }
+        }
+
+        result
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        let offset = offset as usize;
+        Self::check(offset, bytes.len(), WRITE_SIZE)?;
+
+        let (len, result) = match self.tick() {
+            None => (bytes.len(), Ok(())),
+            Some(Failure::Error) => (0, Err(Error::Injected)),
+            Some(Failure::Partial(n)) => (n.min(bytes.len()), Err(Error::Injected)),
+        };
+
+        let cells = &mut self.memory_mut()[offset..offset + len];
+        for (cell, byte) in cells.iter_mut().zip(bytes) {
+            *cell &= *byte;
+        }
+
+        result
+    }
+}
+
+impl<const BLOCKS: usize, const ERASE_SIZE: usize, const WRITE_SIZE: usize> MultiwriteNorFlash
+    for Mock<BLOCKS, ERASE_SIZE, WRITE_SIZE>
+{
+}
@@ -0,0 +1,138 @@
+//! Scripted digital pin
+//!
+//! # Examples
+//!
+//! Checking that a driver pulses a reset line and then waits for the ready line
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::digital::{OutputPin, PinState};
+//! use hal::mock::pin::{Mock, Transaction};
+//!
+//! fn reset<P: OutputPin>(reset: &mut P) {
+//!     reset.set_low();
+//!     reset.set_high();
+//! }
+//!
+//! fn main() {
+//!     let mut pin = Mock::new(&[
+//!         Transaction::Set(PinState::Low),
+//!         Transaction::Set(PinState::High),
+//!     ]);
+//!
+//!     reset(&mut pin.clone());
+//!
+//!     pin.done();
+//! }
+//! ```
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+
+use digital::{OutputPin, PinState};
+#[cfg(feature = "digital-unproven")]
+use digital::{toggleable, InputPin, StatefulOutputPin};
+
+use super::Expectations;
+
+/// Transaction expected by the pin mock
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Transaction {
+    /// The pin is read with `is_high` or `is_low`, and is at the given level
+    Get(PinState),
+    /// The pin is driven to the given level with `set_high` or `set_low`
+    Set(PinState),
+}
+
+/// Digital pin mock
+///
+/// It implements `OutputPin` and, with the `"digital-unproven"` feature, `InputPin`,
+/// `StatefulOutputPin` and `ToggleableOutputPin`. `is_set_high` and `is_set_low` report the
+/// level the pin was last driven to and aren't transactions.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    expectations: Expectations<Transaction>,
+    set: Rc<Cell<Option<PinState>>>,
+}
+
+impl Mock {
+    /// Creates a pin that expects `transactions`
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Mock {
+            expectations: Expectations::new(transactions),
+            set: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Appends `transactions` to the expected ones
+    pub fn expect(&mut self, transactions: &[Transaction]) {
+        self.expectations.extend(transactions);
+    }
+
+    /// Checks that all the expected transactions happened
+    ///
+    /// # Panics
+    ///
+    /// If some of them didn't
+    pub fn done(&mut self) {
+        self.expectations.done();
+    }
+
+    fn set(&mut self, state: PinState) {
+        let expected = self.expectations.next("pin set");
+        assert_eq!(expected, Transaction::Set(state), "unexpected pin set");
+        self.set.set(Some(state));
+    }
+
+    #[cfg(feature = "digital-unproven")]
+    fn get(&self) -> PinState {
+        match self.expectations.next("pin get") {
+            Transaction::Get(state) => state,
+            expected => panic!("unexpected pin get, expected {:?}", expected),
+        }
+    }
+
+    #[cfg(feature = "digital-unproven")]
+    fn get_set(&self) -> PinState {
+        self.set
+            .get()
+            .expect("the pin state was read before the pin was set")
+    }
+}
+
+impl OutputPin for Mock {
+    fn set_low(&mut self) {
+        self.set(PinState::Low)
+    }
+
+    fn set_high(&mut self) {
+        self.set(PinState::High)
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl InputPin for Mock {
+    fn is_high(&self) -> bool {
+        self.get() == PinState::High
+    }
+
+    fn is_low(&self) -> bool {
+        self.get() == PinState::Low
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl StatefulOutputPin for Mock {
+    fn is_set_high(&self) -> bool {
+        self.get_set() == PinState::High
+    }
+
+    fn is_set_low(&self) -> bool {
+        self.get_set() == PinState::Low
+    }
+}
+
+#[cfg(feature = "digital-unproven")]
+impl toggleable::Default for Mock {}
@@ -0,0 +1,165 @@
+//! Scripted serial interface
+//!
+//! # Examples
+//!
+//! Checking that a driver answers a command, and reports a corrupted one
+//!
+//! ```
+//! #[macro_use(block)]
+//! extern crate nb;
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::serial::Write;
+//! use hal::mock::serial::{Mock, Transaction};
+//! use hal::serial::{ErrorKind, Read};
+//!
+//! fn answer<S>(serial: &mut S) -> Result<(), S::Error>
+//! where
+//!     S: Read<u8> + Write<u8>,
+//! {
+//!     if block!(serial.read())? == b'?' {
+//!         serial.bwrite_all(b"ok")?;
+//!     }
+//!     serial.bflush()
+//! }
+//!
+//! fn main() {
+//!     let mut serial = Mock::new(&[
+//!         Transaction::read(b'?'),
+//!         Transaction::write(b'o'),
+//!         Transaction::write(b'k'),
+//!         Transaction::flush(),
+//!         Transaction::read(b'?').with_error(ErrorKind::Parity),
+//!     ]);
+//!
+//!     assert_eq!(answer(&mut serial.clone()), Ok(()));
+//!     assert_eq!(answer(&mut serial.clone()), Err(ErrorKind::Parity));
+//!
+//!     serial.done();
+//! }
+//! ```
+
+use blocking::serial::write;
+use nb;
+use serial::{ErrorKind, ErrorType, Read, Write};
+
+use super::Expectations;
+
+/// Transaction expected by the serial mock
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Transaction {
+    kind: Kind,
+    error: Option<ErrorKind>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+enum Kind {
+    Read(u8),
+    Write(u8),
+    Flush,
+}
+
+impl Transaction {
+    /// `Read::read`, which returns `word`
+    pub fn read(word: u8) -> Self {
+        Transaction::new(Kind::Read(word))
+    }
+
+    /// `Write::write` of `word`
+    pub fn write(word: u8) -> Self {
+        Transaction::new(Kind::Write(word))
+    }
+
+    /// `Write::flush`
+    pub fn flush() -> Self {
+        Transaction::new(Kind::Flush)
+    }
+
+    /// Makes the transaction fail with `error`
+    ///
+    /// The word of a failed read is discarded; the word of a failed write is still checked.
+    pub fn with_error(mut self, error: ErrorKind) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    fn new(kind: Kind) -> Self {
+        Transaction { kind, error: None }
+    }
+
+    fn result(&self) -> nb::Result<(), ErrorKind> {
+        match self.error {
+            Some(error) => Err(nb::Error::Other(error)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Serial interface mock
+///
+/// It implements `Read<u8>` and `Write<u8>`, and the blocking `Write<u8>` on top of them, with
+/// `serial::ErrorKind` as its error type. The operations never return `WouldBlock`.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    expectations: Expectations<Transaction>,
+}
+
+impl Mock {
+    /// Creates an interface that expects `transactions`
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Mock {
+            expectations: Expectations::new(transactions),
+        }
+    }
+
+    /// Appends `transactions` to the expected ones
+    pub fn expect(&mut self, transactions: &[Transaction]) {
+        self.expectations.extend(transactions);
+    }
+
+    /// Checks that all the expected transactions happened
+    ///
+    /// # Panics
+    ///
+    /// If some of them didn't
+    pub fn done(&mut self) {
+        self.expectations.done();
+    }
+}
+
+impl ErrorType for Mock {
+    type Error = ErrorKind;
+}
+
+impl Read<u8> for Mock {
+    fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+        let transaction = self.expectations.next("serial read");
+        match transaction.kind {
+            Kind::Read(word) => transaction.result().map(|_| word),
+            _ => panic!("unexpected serial read, expected {:?}", transaction),
+        }
+    }
+}
+
+impl Write<u8> for Mock {
+    fn write(&mut self, word: u8) -> nb::Result<(), ErrorKind> {
+        let transaction = self.expectations.next("serial write");
+        match transaction.kind {
+            Kind::Write(expected) => assert_eq!(word, expected, "unexpected serial write"),
+            _ => panic!("unexpected serial write, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+
+    fn flush(&mut self) -> nb::Result<(), ErrorKind> {
+        let transaction = self.expectations.next("serial flush");
+        match transaction.kind {
+            Kind::Flush => transaction.result(),
+            _ => panic!("unexpected serial flush, expected {:?}", transaction),
+        }
+    }
+}
+
+impl write::Default<u8> for Mock {}
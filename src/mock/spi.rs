@@ -0,0 +1,219 @@
+//! Scripted SPI bus
+//!
+//! # Examples
+//!
+//! Checking the register read of a driver, and how it handles a failing bus
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::spi::Transfer;
+//! use hal::mock::spi::{Mock, Transaction};
+//! use hal::spi::ErrorKind;
+//!
+//! fn read_register<S: Transfer<u8>>(spi: &mut S, register: u8) -> Result<u8, S::Error> {
+//!     let mut buffer = [register | 0x80, 0];
+//!     spi.transfer(&mut buffer)?;
+//!     Ok(buffer[1])
+//! }
+//!
+//! fn main() {
+//!     let mut spi = Mock::new(&[
+//!         Transaction::transfer(&[0x8f, 0x00], &[0x00, 0x33]),
+//!         Transaction::transfer(&[0x8f, 0x00], &[0x00, 0x00]).with_error(ErrorKind::ModeFault),
+//!     ]);
+//!
+//!     assert_eq!(read_register(&mut spi.clone(), 0x0f), Ok(0x33));
+//!     assert_eq!(read_register(&mut spi.clone(), 0x0f), Err(ErrorKind::ModeFault));
+//!
+//!     spi.done();
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+use blocking;
+use nb;
+use spi::{ErrorKind, ErrorType, FullDuplex};
+
+use super::Expectations;
+
+/// Transaction expected by the SPI mock
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    kind: Kind,
+    error: Option<ErrorKind>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Kind {
+    Send(u8),
+    Read(u8),
+    Transfer(Vec<u8>, Vec<u8>),
+    Write(Vec<u8>),
+}
+
+impl Transaction {
+    /// `FullDuplex::send` of `word`
+    pub fn send(word: u8) -> Self {
+        Transaction::new(Kind::Send(word))
+    }
+
+    /// `FullDuplex::read`, which returns `word`
+    pub fn read(word: u8) -> Self {
+        Transaction::new(Kind::Read(word))
+    }
+
+    /// `blocking::spi::Transfer::transfer` of `words`, which are replaced with `response`
+    pub fn transfer(words: &[u8], response: &[u8]) -> Self {
+        assert_eq!(
+            words.len(),
+            response.len(),
+            "a transfer receives as many words as it sends"
+        );
+        Transaction::new(Kind::Transfer(words.to_vec(), response.to_vec()))
+    }
+
+    /// `blocking::spi::Write::write` of `words`, or `WriteIter::write_iter` of their iterator
+    pub fn write(words: &[u8]) -> Self {
+        Transaction::new(Kind::Write(words.to_vec()))
+    }
+
+    /// Makes the transaction fail with `error`
+    ///
+    /// The arguments of the call are still checked, and the words of a failed transfer are
+    /// still replaced.
+    pub fn with_error(mut self, error: ErrorKind) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    fn new(kind: Kind) -> Self {
+        Transaction { kind, error: None }
+    }
+
+    fn result(&self) -> Result<(), ErrorKind> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// SPI bus mock
+///
+/// It implements `FullDuplex<u8>` and the blocking `Transfer<u8>`, `Write<u8>` and
+/// `WriteIter<u8>`, with `spi::ErrorKind` as its error type. It also implements the blocking
+/// `Transactional<u8>`, whose operations are expected as `write` and `transfer` transactions.
+/// `&mut Mock` implements them all too.
+///
+/// A device of the bus is mocked by an `ExclusiveDevice` made of the bus mock and of a
+/// [pin mock](../pin/struct.Mock.html) for the chip select, which implements `SpiDevice<u8>`.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    expectations: Expectations<Transaction>,
+}
+
+impl Mock {
+    /// Creates a bus that expects `transactions`
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Mock {
+            expectations: Expectations::new(transactions),
+        }
+    }
+
+    /// Appends `transactions` to the expected ones
+    pub fn expect(&mut self, transactions: &[Transaction]) {
+        self.expectations.extend(transactions);
+    }
+
+    /// Checks that all the expected transactions happened
+    ///
+    /// # Panics
+    ///
+    /// If some of them didn't
+    pub fn done(&mut self) {
+        self.expectations.done();
+    }
+}
+
+impl ErrorType for Mock {
+    type Error = ErrorKind;
+}
+
+impl FullDuplex<u8> for Mock {
+    fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+        let transaction = self.expectations.next("spi read");
+        match transaction.kind {
+            Kind::Read(word) => transaction.result().map(|_| word).map_err(nb::Error::Other),
+            _ => panic!("unexpected spi read, expected {:?}", transaction),
+        }
+    }
+
+    fn send(&mut self, word: u8) -> nb::Result<(), ErrorKind> {
+        let transaction = self.expectations.next("spi send");
+        match transaction.kind {
+            Kind::Send(expected) => assert_eq!(word, expected, "unexpected spi send"),
+            _ => panic!("unexpected spi send, expected {:?}", transaction),
+        }
+        transaction.result().map_err(nb::Error::Other)
+    }
+}
+
+impl blocking::spi::Transfer<u8> for Mock {
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ErrorKind> {
+        let transaction = self.expectations.next("spi transfer");
+        match transaction.kind {
+            Kind::Transfer(ref expected, ref response) => {
+                assert_eq!(*words, **expected, "unexpected spi transfer");
+                words.copy_from_slice(response);
+            }
+            _ => panic!("unexpected spi transfer, expected {:?}", transaction),
+        }
+        transaction.result().map(move |_| &*words)
+    }
+}
+
+impl blocking::spi::Write<u8> for Mock {
+    fn write(&mut self, words: &[u8]) -> Result<(), ErrorKind> {
+        let transaction = self.expectations.next("spi write");
+        match transaction.kind {
+            Kind::Write(ref expected) => assert_eq!(*words, **expected, "unexpected spi write"),
+            _ => panic!("unexpected spi write, expected {:?}", transaction),
+        }
+        transaction.result()
+    }
+}
+
+impl blocking::spi::transactional::Default<u8> for Mock {}
+
+impl blocking::spi::WriteIter<u8> for Mock {
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), ErrorKind>
+    where
+        WI: IntoIterator<Item = u8>,
+    {
+        let words: Vec<u8> = words.into_iter().collect();
+        blocking::spi::Write::write(self, &words)
+    }
+}
+
+impl blocking::spi::Transfer<u8> for &mut Mock {
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ErrorKind> {
+        blocking::spi::Transfer::transfer(*self, words)
+    }
+}
+
+impl blocking::spi::Write<u8> for &mut Mock {
+    fn write(&mut self, words: &[u8]) -> Result<(), ErrorKind> {
+        blocking::spi::Write::write(*self, words)
+    }
+}
+
+impl blocking::spi::WriteIter<u8> for &mut Mock {
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), ErrorKind>
+    where
+        WI: IntoIterator<Item = u8>,
+    {
+        blocking::spi::WriteIter::write_iter(*self, words)
+    }
+}
@@ -0,0 +1,172 @@
+//! Pulse Density Modulation (PDM) input
+//!
+//! *This module is available if embedded-hal is built with the `"pdm"` feature.*
+//!
+//! PDM interfaces clock one or two MEMS microphones and decimate their 1-bit output into PCM
+//! samples. Two microphones can share a data line, one driving it on the rising edge of the clock
+//! and the other one on the falling edge; in that case samples are returned interleaved, left
+//! channel first.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// PDM error kind
+///
+/// This represents a common set of PDM operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The interface can't generate the requested clock or doesn't support the requested
+    /// decimation ratio
+    Unsupported,
+    /// A sample was lost because it was not read in a timely manner
+    Overrun,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Unsupported => "the clock or decimation ratio is not supported",
+            ErrorKind::Overrun => "a sample was lost because it was not read in time",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// PDM error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// PDM error type
+///
+/// All the PDM traits, non-blocking and blocking, inherit from this trait so that a PDM interface
+/// defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Capture configuration
+pub trait Configure: ErrorType {
+    /// Sets the frequency of the clock fed to the microphones, in Hz
+    ///
+    /// Returns an error if the interface can't generate `hz`
+    fn set_clock(&mut self, hz: u32) -> Result<(), Self::Error>;
+
+    /// Sets the decimation ratio, the number of PDM bits that are filtered into each PCM sample
+    ///
+    /// Returns an error if the interface doesn't support `ratio`
+    fn set_decimation(&mut self, ratio: u16) -> Result<(), Self::Error>;
+
+    /// Returns the resulting PCM sample rate, in samples per second and channel
+    fn sample_rate(&self) -> u32;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Configure for Box<T>
+where
+    T: Configure + ?Sized,
+{
+    fn set_clock(&mut self, hz: u32) -> Result<(), Self::Error> {
+        (**self).set_clock(hz)
+    }
+
+    fn set_decimation(&mut self, ratio: u16) -> Result<(), Self::Error> {
+        (**self).set_decimation(ratio)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+}
+
+impl<T> Configure for &mut T
+where
+    T: Configure + ?Sized,
+{
+    fn set_clock(&mut self, hz: u32) -> Result<(), Self::Error> {
+        (**self).set_clock(hz)
+    }
+
+    fn set_decimation(&mut self, ratio: u16) -> Result<(), Self::Error> {
+        (**self).set_decimation(ratio)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+}
+
+/// Read PCM samples
+///
+/// `Word` is the sample type, usually `i16` or `i32`.
+pub trait Read<Word>: ErrorType {
+    /// Reads a single PCM sample
+    ///
+    /// Possible errors include *overrun*: a sample was lost because it was not read in a timely
+    /// manner.
+    fn read(&mut self) -> nb::Result<Word, Self::Error>;
+}
@@ -0,0 +1,200 @@
+//! Power management
+//!
+//! *This module is available if embedded-hal is built with the `"power"` feature.*
+//!
+//! Deep sleep modes usually power down the peripherals, which lose their configuration. The
+//! [`Suspend`] trait lets a low-power framework save the state of any peripheral before entering
+//! such a mode, and restore it afterwards, without knowing what that state is.
+//!
+//! [`Suspend`]: trait.Suspend.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use void::Void;
+
+/// Power management error kind
+///
+/// This represents a common set of power management errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic code can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The peripheral can't be suspended right now, e.g. because a transfer is in progress
+    Busy,
+    /// The peripheral couldn't be brought back to the saved state
+    Restore,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Busy => "the peripheral can't be suspended right now",
+            ErrorKind::Restore => "the peripheral couldn't be brought back to the saved state",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// Power management error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Power management error type
+///
+/// All the power management traits inherit from this trait so that a peripheral defines its error
+/// type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Saving and restoring the state of a peripheral around deep sleep
+///
+/// # Contract
+///
+/// - `suspend` MUST save everything that's needed to bring the peripheral back to its current
+///   state, e.g. its configuration registers, in the returned token, and MAY then power it down.
+/// - Between `suspend` and `resume` the peripheral MUST NOT be used, and its power domain MAY be
+///   switched off.
+/// - `resume` MUST bring the peripheral back to the state saved in `token`.
+///
+/// # Examples
+///
+/// Cycling all the peripherals of an application through deep sleep
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::power::Suspend;
+///
+/// fn deep_sleep<P>(peripheral: &mut P) -> Result<(), P::Error>
+/// where
+///     P: Suspend,
+/// {
+///     let token = peripheral.suspend()?;
+///     // ..
+///     peripheral.resume(token)
+/// }
+///
+/// fn main() {
+///     let mut uart: Uart1 = {
+///         // ..
+/// #       Uart1 { brr: 0x683 }
+///     };
+///
+///     deep_sleep(&mut uart).unwrap();
+/// #   assert_eq!(uart.brr, 0x683);
+/// }
+///
+/// # use hal::power::{ErrorKind, ErrorType};
+/// # struct Uart1 { brr: u16 }
+/// # impl ErrorType for Uart1 { type Error = ErrorKind; }
+/// # impl Suspend for Uart1 {
+/// #     type Token = u16;
+/// #     fn suspend(&mut self) -> Result<u16, ErrorKind> {
+/// #         Ok(std::mem::replace(&mut self.brr, 0))
+/// #     }
+/// #     fn resume(&mut self, brr: u16) -> Result<(), ErrorKind> { self.brr = brr; Ok(()) }
+/// # }
+/// ```
+pub trait Suspend: ErrorType {
+    /// The saved state of the peripheral
+    type Token;
+
+    /// Saves the state of the peripheral, and returns it
+    fn suspend(&mut self) -> Result<Self::Token, Self::Error>;
+
+    /// Restores the state of the peripheral saved in `token`
+    fn resume(&mut self, token: Self::Token) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Suspend for Box<T>
+where
+    T: Suspend + ?Sized,
+{
+    type Token = T::Token;
+
+    fn suspend(&mut self) -> Result<Self::Token, Self::Error> {
+        (**self).suspend()
+    }
+
+    fn resume(&mut self, token: Self::Token) -> Result<(), Self::Error> {
+        (**self).resume(token)
+    }
+}
+
+impl<T> Suspend for &mut T
+where
+    T: Suspend + ?Sized,
+{
+    type Token = T::Token;
+
+    fn suspend(&mut self) -> Result<Self::Token, Self::Error> {
+        (**self).suspend()
+    }
+
+    fn resume(&mut self, token: Self::Token) -> Result<(), Self::Error> {
+        (**self).resume(token)
+    }
+}
@@ -3,31 +3,248 @@
 //! The traits have been renamed to avoid collisions with other items when
 //! performing a glob import.
 
-#[cfg(feature = "unproven")]
+#[cfg(feature = "capture")]
 pub use ::Capture as _embedded_hal_Capture;
-#[cfg(feature = "unproven")]
+#[cfg(feature = "pwm")]
 pub use ::Pwm as _embedded_hal_Pwm;
 pub use ::PwmPin as _embedded_hal_PwmPin;
-#[cfg(feature = "unproven")]
+#[cfg(feature = "qei")]
 pub use ::Qei as _embedded_hal_Qei;
+#[cfg(feature = "timer-unproven")]
+pub use ::timer::Cancel as _embedded_hal_timer_Cancel;
 pub use ::timer::CountDown as _embedded_hal_timer_CountDown;
+#[cfg(feature = "adc")]
+pub use ::adc::Error as _embedded_hal_adc_Error;
+#[cfg(feature = "adc")]
+pub use ::adc::OneShot as _embedded_hal_adc_OneShot;
+#[cfg(feature = "can")]
+pub use ::blocking::can::{
+    Receive as _embedded_hal_blocking_can_Receive,
+    Transmit as _embedded_hal_blocking_can_Transmit,
+};
 pub use ::blocking::delay::DelayMs as _embedded_hal_blocking_delay_DelayMs;
 pub use ::blocking::delay::DelayUs as _embedded_hal_blocking_delay_DelayUs;
 pub use ::blocking::i2c::{
     Read as _embedded_hal_blocking_i2c_Read,
+    ReadExt as _embedded_hal_blocking_i2c_ReadExt,
+    Transactional as _embedded_hal_blocking_i2c_Transactional,
     Write as _embedded_hal_blocking_i2c_Write,
     WriteRead as _embedded_hal_blocking_i2c_WriteRead,
+    WriteReadExt as _embedded_hal_blocking_i2c_WriteReadExt,
+};
+#[cfg(feature = "i2s")]
+pub use ::blocking::i2s::{
+    Read as _embedded_hal_blocking_i2s_Read,
+    Write as _embedded_hal_blocking_i2s_Write,
+};
+#[cfg(feature = "i3c")]
+pub use ::blocking::i3c::{
+    AssignDynamicAddresses as _embedded_hal_blocking_i3c_AssignDynamicAddresses,
+    Broadcast as _embedded_hal_blocking_i3c_Broadcast,
+    Direct as _embedded_hal_blocking_i3c_Direct,
+    Read as _embedded_hal_blocking_i3c_Read,
+    Write as _embedded_hal_blocking_i3c_Write,
+    WriteRead as _embedded_hal_blocking_i3c_WriteRead,
+};
+#[cfg(feature = "pdm")]
+pub use ::blocking::pdm::Read as _embedded_hal_blocking_pdm_Read;
+#[cfg(feature = "qspi")]
+pub use ::blocking::qspi::{
+    Read as _embedded_hal_blocking_qspi_Read,
+    Write as _embedded_hal_blocking_qspi_Write,
+};
+pub use ::blocking::serial::{
+    ReadExt as _embedded_hal_blocking_serial_ReadExt,
+    Write as _embedded_hal_blocking_serial_Write,
 };
-pub use ::blocking::serial::Write as _embedded_hal_blocking_serial_Write;
 pub use ::blocking::spi::{
+    SpiDevice as _embedded_hal_blocking_spi_SpiDevice,
+    Transactional as _embedded_hal_blocking_spi_Transactional,
     Transfer as _embedded_hal_blocking_spi_Transfer,
+    TransferExt as _embedded_hal_blocking_spi_TransferExt,
     Write as _embedded_hal_blocking_spi_Write,
+    WriteIter as _embedded_hal_blocking_spi_WriteIter,
 };
-#[cfg(feature = "unproven")]
+#[cfg(feature = "rng")]
 pub use ::blocking::rng::Read as _embedded_hal_blocking_rng_Read;
-pub use ::digital::OutputPin as _embedded_hal_digital_OutputPin;
-#[cfg(feature = "unproven")]
-pub use ::digital::InputPin as _embedded_hal_digital_InputPin;
+#[cfg(feature = "calibration")]
+pub use ::calibration::{
+    Calibrate as _embedded_hal_calibration_Calibrate,
+    Error as _embedded_hal_calibration_Error,
+};
+#[cfg(feature = "can")]
+pub use ::can::{
+    DataTiming as _embedded_hal_can_DataTiming,
+    Error as _embedded_hal_can_Error,
+    Frame as _embedded_hal_can_Frame,
+    NominalTiming as _embedded_hal_can_NominalTiming,
+    Receive as _embedded_hal_can_Receive,
+    Transmit as _embedded_hal_can_Transmit,
+};
+pub use ::digital::{
+    Error as _embedded_hal_digital_Error,
+    OutputPin as _embedded_hal_digital_OutputPin,
+};
+#[cfg(feature = "digital-unproven")]
+pub use ::digital::{
+    InputPin as _embedded_hal_digital_InputPin,
+    StatefulOutputPin as _embedded_hal_digital_StatefulOutputPin,
+    ToggleableOutputPin as _embedded_hal_digital_ToggleableOutputPin,
+    Wait as _embedded_hal_digital_Wait,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::{
+    ReadBuffer as _embedded_hal_dma_ReadBuffer,
+    Transfer as _embedded_hal_dma_Transfer,
+    WriteBuffer as _embedded_hal_dma_WriteBuffer,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::channel::Allocator as _embedded_hal_dma_channel_Allocator;
+#[cfg(feature = "dma")]
+pub use ::dma::circular::{
+    CircularRead as _embedded_hal_dma_circular_CircularRead,
+    CircularWrite as _embedded_hal_dma_circular_CircularWrite,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::mem::{
+    MemCopy as _embedded_hal_dma_mem_MemCopy,
+    MemFill as _embedded_hal_dma_mem_MemFill,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::scatter_gather::{
+    Gather as _embedded_hal_dma_scatter_gather_Gather,
+    ReadSegments as _embedded_hal_dma_scatter_gather_ReadSegments,
+    Scatter as _embedded_hal_dma_scatter_gather_Scatter,
+    WriteSegments as _embedded_hal_dma_scatter_gather_WriteSegments,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::serial::{
+    ReadExact as _embedded_hal_dma_serial_ReadExact,
+    WriteAll as _embedded_hal_dma_serial_WriteAll,
+};
+#[cfg(feature = "dma")]
+pub use ::dma::spi::{
+    FullDuplex as _embedded_hal_dma_spi_FullDuplex,
+    ReadExact as _embedded_hal_dma_spi_ReadExact,
+    WriteAll as _embedded_hal_dma_spi_WriteAll,
+};
+#[cfg(feature = "event")]
+pub use ::event::EventSource as _embedded_hal_event_EventSource;
+#[cfg(feature = "fugit")]
+pub use ::fugit::{
+    CountDownExt as _embedded_hal_fugit_CountDownExt,
+    DelayExt as _embedded_hal_fugit_DelayExt,
+    IntoDuration as _embedded_hal_fugit_IntoDuration,
+    IntoRate as _embedded_hal_fugit_IntoRate,
+};
+#[cfg(all(feature = "capture", feature = "fugit"))]
+pub use ::fugit::CaptureExt as _embedded_hal_fugit_CaptureExt;
+#[cfg(all(feature = "fugit", feature = "pwm"))]
+pub use ::fugit::PwmExt as _embedded_hal_fugit_PwmExt;
+#[cfg(all(feature = "fugit", feature = "watchdog"))]
+pub use ::fugit::WatchdogEnableExt as _embedded_hal_fugit_WatchdogEnableExt;
+#[cfg(feature = "async")]
+pub use ::futures::RegisterWaker as _embedded_hal_futures_RegisterWaker;
+#[cfg(all(feature = "async", feature = "adc"))]
+pub use ::futures::adc::OneShot as _embedded_hal_futures_adc_OneShot;
+#[cfg(feature = "async")]
+pub use ::futures::delay::{
+    DelayMs as _embedded_hal_futures_delay_DelayMs,
+    DelayUs as _embedded_hal_futures_delay_DelayUs,
+};
+#[cfg(all(feature = "async", feature = "digital-unproven"))]
+pub use ::futures::digital::Wait as _embedded_hal_futures_digital_Wait;
+#[cfg(feature = "async")]
+pub use ::futures::i2c::{
+    Read as _embedded_hal_futures_i2c_Read,
+    Write as _embedded_hal_futures_i2c_Write,
+    WriteRead as _embedded_hal_futures_i2c_WriteRead,
+};
+#[cfg(feature = "async")]
+pub use ::futures::serial::{
+    Read as _embedded_hal_futures_serial_Read,
+    Write as _embedded_hal_futures_serial_Write,
+};
+#[cfg(feature = "async")]
+pub use ::futures::spi::{
+    SpiDevice as _embedded_hal_futures_spi_SpiDevice,
+    Transfer as _embedded_hal_futures_spi_Transfer,
+    Write as _embedded_hal_futures_spi_Write,
+};
+#[cfg(all(feature = "async", feature = "storage"))]
+pub use ::futures::storage::{
+    Eeprom as _embedded_hal_futures_storage_Eeprom,
+    NorFlash as _embedded_hal_futures_storage_NorFlash,
+    ReadNorFlash as _embedded_hal_futures_storage_ReadNorFlash,
+};
+pub use ::i2c::Error as _embedded_hal_i2c_Error;
+#[cfg(feature = "i2s")]
+pub use ::i2s::{
+    Configure as _embedded_hal_i2s_Configure,
+    Error as _embedded_hal_i2s_Error,
+    Read as _embedded_hal_i2s_Read,
+    Write as _embedded_hal_i2s_Write,
+};
+#[cfg(feature = "i3c")]
+pub use ::i3c::{
+    Error as _embedded_hal_i3c_Error,
+    ReadIbi as _embedded_hal_i3c_ReadIbi,
+};
+#[cfg(feature = "pdm")]
+pub use ::pdm::{
+    Configure as _embedded_hal_pdm_Configure,
+    Error as _embedded_hal_pdm_Error,
+    Read as _embedded_hal_pdm_Read,
+};
+#[cfg(feature = "power")]
+pub use ::power::{
+    Error as _embedded_hal_power_Error,
+    Suspend as _embedded_hal_power_Suspend,
+};
+#[cfg(feature = "qspi")]
+pub use ::qspi::{
+    Error as _embedded_hal_qspi_Error,
+    MemoryMapped as _embedded_hal_qspi_MemoryMapped,
+};
+#[cfg(feature = "rng")]
+pub use ::rng::Error as _embedded_hal_rng_Error;
+#[cfg(feature = "rtc")]
+pub use ::rtc::{
+    Alarm as _embedded_hal_rtc_Alarm,
+    Error as _embedded_hal_rtc_Error,
+    Rtc as _embedded_hal_rtc_Rtc,
+};
+pub use ::serial::Error as _embedded_hal_serial_Error;
 pub use ::serial::Read as _embedded_hal_serial_Read;
 pub use ::serial::Write as _embedded_hal_serial_Write;
+pub use ::spi::Error as _embedded_hal_spi_Error;
 pub use ::spi::FullDuplex as _embedded_hal_spi_FullDuplex;
+#[cfg(feature = "storage")]
+pub use ::storage::eeprom::Eeprom as _embedded_hal_storage_eeprom_Eeprom;
+#[cfg(feature = "storage")]
+pub use ::storage::internal_flash::InternalFlash as _embedded_hal_storage_internal_flash_InternalFlash;
+#[cfg(feature = "storage")]
+pub use ::storage::nand_flash::NandFlash as _embedded_hal_storage_nand_flash_NandFlash;
+#[cfg(feature = "storage")]
+pub use ::storage::nor_flash::{
+    NorFlash as _embedded_hal_storage_nor_flash_NorFlash,
+    ReadNorFlash as _embedded_hal_storage_nor_flash_ReadNorFlash,
+};
+#[cfg(feature = "storage")]
+pub use ::storage::{
+    Error as _embedded_hal_storage_Error,
+    ReadStorage as _embedded_hal_storage_ReadStorage,
+    Storage as _embedded_hal_storage_Storage,
+};
+#[cfg(feature = "typestate")]
+pub use ::typestate::{
+    Disable as _embedded_hal_typestate_Disable,
+    Enable as _embedded_hal_typestate_Enable,
+    IntoMode as _embedded_hal_typestate_IntoMode,
+};
+#[cfg(feature = "watchdog")]
+pub use ::watchdog::{
+    Watchdog as _embedded_hal_watchdog_Watchdog,
+    WatchdogDisable as _embedded_hal_watchdog_WatchdogDisable,
+    WatchdogEnable as _embedded_hal_watchdog_WatchdogEnable,
+};
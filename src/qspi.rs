@@ -0,0 +1,393 @@
+//! Quad Serial Peripheral Interface (serial memory controllers)
+//!
+//! *This module is available if embedded-hal is built with the `"qspi"` feature.*
+//!
+//! Despite the name these traits also cover dual and octal controllers, as well as double data
+//! rate (DDR) transfers. Each phase of a [`Command`] specifies its own number of lines and data
+//! rate, so e.g. `1-1-4` (quad output), `4-4-4` (QPI) and `8D-8D-8D` (octal DDR) commands can all
+//! be expressed.
+//!
+//! Serial flash controllers usually operate in one of two modes:
+//!
+//! - *indirect* mode, where the CPU issues commands to the memory device one by one. This is how a
+//!   device is erased and programmed. See the [`blocking::qspi`] module.
+//! - *memory-mapped* mode, also known as eXecute-In-Place (XIP), where the controller translates
+//!   reads of a region of the address space into read commands. See the [`MemoryMapped`] trait.
+//!
+//! [`blocking::qspi`]: ../blocking/qspi/index.html
+//! [`MemoryMapped`]: trait.MemoryMapped.html
+//! [`Command`]: struct.Command.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+use core::ops::Range;
+
+use void::Void;
+
+/// Number of data lines used during a phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Width {
+    /// One line (standard SPI)
+    Single,
+    /// Two lines
+    Dual,
+    /// Four lines
+    Quad,
+    /// Eight lines
+    Octal,
+}
+
+/// Number of bits transferred per line and clock cycle during a phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum DataRate {
+    /// One bit per clock cycle (Single Data Rate)
+    Single,
+    /// Two bits per clock cycle, one on each clock edge (Double Data Rate)
+    Double,
+}
+
+/// Size of the opcode sent during the instruction phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum InstructionSize {
+    /// One byte
+    _8Bit,
+    /// Two bytes, as used by octal devices (usually the opcode followed by its complement)
+    _16Bit,
+}
+
+/// Size of the address sent during the address phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum AddressSize {
+    /// One byte
+    _8Bit,
+    /// Two bytes
+    _16Bit,
+    /// Three bytes
+    _24Bit,
+    /// Four bytes
+    _32Bit,
+}
+
+/// Instruction phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Instruction {
+    /// Instruction opcode
+    ///
+    /// For 8-bit instructions only the low byte is sent
+    pub code: u16,
+    /// Size of the opcode
+    pub size: InstructionSize,
+    /// Lines used to send the opcode
+    pub width: Width,
+    /// Data rate used to send the opcode
+    pub rate: DataRate,
+}
+
+/// Address phase of a command
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Address {
+    /// Address sent to the memory device
+    pub value: u32,
+    /// Number of address bytes
+    pub size: AddressSize,
+    /// Lines used to send the address
+    pub width: Width,
+    /// Data rate used to send the address
+    pub rate: DataRate,
+}
+
+/// A command sent to a serial memory device
+///
+/// A command is made of up to four phases, in this order: instruction, address, dummy cycles and
+/// data. Phases set to `None` (or zero dummy cycles) are skipped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Command {
+    /// Instruction phase
+    pub instruction: Option<Instruction>,
+    /// Address phase
+    pub address: Option<Address>,
+    /// Number of dummy clock cycles between the address and data phases
+    pub dummy_cycles: u8,
+    /// Lines used during the data phase
+    pub data_width: Width,
+    /// Data rate used during the data phase
+    pub data_rate: DataRate,
+}
+
+/// QSPI error kind
+///
+/// This represents a common set of QSPI operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The controller doesn't support the widths, data rates or sizes requested by a command
+    Unsupported,
+    /// An indirect transfer was requested while the controller is in memory-mapped mode
+    MemoryMapped,
+    /// The memory didn't complete the command in time
+    Timeout,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Unsupported => "the controller does not support the command",
+            ErrorKind::MemoryMapped => "the controller is in memory-mapped mode",
+            ErrorKind::Timeout => "the memory did not complete the command in time",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// QSPI error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// QSPI error type
+///
+/// All the QSPI traits, indirect and memory-mapped, inherit from this trait so that a memory
+/// controller defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Memory-mapped (eXecute-In-Place) mode
+///
+/// # Contract
+///
+/// - While memory-mapped, indirect transfers (see [`blocking::qspi`]) MUST return an error
+///   instead of disturbing the ongoing memory-mapped accesses.
+/// - After `exit_memory_mapped` returns `Ok`, reading from `mapped_range` is no longer valid
+///   until memory-mapped mode is entered again. Code executing from the mapped region must not
+///   call `exit_memory_mapped`.
+///
+/// [`blocking::qspi`]: ../blocking/qspi/index.html
+///
+/// # Examples
+///
+/// Updating a region of a serial flash that's normally executed in place
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::blocking::qspi::Write;
+/// use hal::qspi::{
+///     Address, AddressSize, Command, DataRate, Instruction, InstructionSize, MemoryMapped, Width,
+/// };
+///
+/// fn program<Q>(
+///     qspi: &mut Q,
+///     read: Command,
+///     page_program: Command,
+///     data: &[u8],
+/// ) -> Result<(), Q::Error>
+/// where
+///     Q: MemoryMapped + Write,
+/// {
+///     qspi.exit_memory_mapped()?;
+///     qspi.write(page_program, data)?;
+///     qspi.enter_memory_mapped(read)
+/// }
+///
+/// fn main() {
+///     let mut qspi: Qspi = {
+///         // ..
+/// #       Qspi { mapped: true }
+///     };
+///
+///     // 1-4-4 read
+///     let fast_read_quad = Command {
+///         instruction: Some(Instruction {
+///             code: 0xEB,
+///             size: InstructionSize::_8Bit,
+///             width: Width::Single,
+///             rate: DataRate::Single,
+///         }),
+///         address: Some(Address {
+///             value: 0,
+///             size: AddressSize::_24Bit,
+///             width: Width::Quad,
+///             rate: DataRate::Single,
+///         }),
+///         dummy_cycles: 6,
+///         data_width: Width::Quad,
+///         data_rate: DataRate::Single,
+///     };
+///     // 1-1-1 page program
+///     let page_program = Command {
+///         instruction: Some(Instruction {
+///             code: 0x02,
+///             size: InstructionSize::_8Bit,
+///             width: Width::Single,
+///             rate: DataRate::Single,
+///         }),
+///         address: Some(Address {
+///             value: 0x1000,
+///             size: AddressSize::_24Bit,
+///             width: Width::Single,
+///             rate: DataRate::Single,
+///         }),
+///         dummy_cycles: 0,
+///         data_width: Width::Single,
+///         data_rate: DataRate::Single,
+///     };
+///
+///     program(&mut qspi, fast_read_quad, page_program, &[0xde, 0xad]).unwrap();
+///     assert!(qspi.is_memory_mapped());
+/// }
+///
+/// # use std::ops::Range;
+/// # use hal::qspi::{ErrorKind, ErrorType};
+/// # struct Qspi { mapped: bool }
+/// # impl ErrorType for Qspi {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl MemoryMapped for Qspi {
+/// #     fn enter_memory_mapped(&mut self, _: Command) -> Result<(), ErrorKind> {
+/// #         self.mapped = true;
+/// #         Ok(())
+/// #     }
+/// #     fn exit_memory_mapped(&mut self) -> Result<(), ErrorKind> {
+/// #         self.mapped = false;
+/// #         Ok(())
+/// #     }
+/// #     fn is_memory_mapped(&self) -> bool { self.mapped }
+/// #     fn mapped_range(&self) -> Range<usize> { 0x9000_0000..0x9100_0000 }
+/// # }
+/// # impl Write for Qspi {
+/// #     fn write(&mut self, _: Command, _: &[u8]) -> Result<(), ErrorKind> {
+/// #         if self.mapped { Err(ErrorKind::MemoryMapped) } else { Ok(()) }
+/// #     }
+/// # }
+/// ```
+pub trait MemoryMapped: ErrorType {
+    /// Enters memory-mapped mode
+    ///
+    /// Reads of the mapped region will be translated into `read` commands. The `value` of the
+    /// `read` command address phase is ignored; the address is derived from the accessed location.
+    fn enter_memory_mapped(&mut self, read: Command) -> Result<(), Self::Error>;
+
+    /// Leaves memory-mapped mode, returning the controller to indirect mode
+    fn exit_memory_mapped(&mut self) -> Result<(), Self::Error>;
+
+    /// Is the controller in memory-mapped mode?
+    fn is_memory_mapped(&self) -> bool;
+
+    /// Returns the range of CPU addresses the memory device is (or will be) mapped to
+    fn mapped_range(&self) -> Range<usize>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> MemoryMapped for Box<T>
+where
+    T: MemoryMapped + ?Sized,
+{
+    fn enter_memory_mapped(&mut self, read: Command) -> Result<(), Self::Error> {
+        (**self).enter_memory_mapped(read)
+    }
+
+    fn exit_memory_mapped(&mut self) -> Result<(), Self::Error> {
+        (**self).exit_memory_mapped()
+    }
+
+    fn is_memory_mapped(&self) -> bool {
+        (**self).is_memory_mapped()
+    }
+
+    fn mapped_range(&self) -> Range<usize> {
+        (**self).mapped_range()
+    }
+}
+
+impl<T> MemoryMapped for &mut T
+where
+    T: MemoryMapped + ?Sized,
+{
+    fn enter_memory_mapped(&mut self, read: Command) -> Result<(), Self::Error> {
+        (**self).enter_memory_mapped(read)
+    }
+
+    fn exit_memory_mapped(&mut self) -> Result<(), Self::Error> {
+        (**self).exit_memory_mapped()
+    }
+
+    fn is_memory_mapped(&self) -> bool {
+        (**self).is_memory_mapped()
+    }
+
+    fn mapped_range(&self) -> Range<usize> {
+        (**self).mapped_range()
+    }
+}
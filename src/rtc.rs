@@ -0,0 +1,369 @@
+//! Real-time clocks
+//!
+//! *This module is available if embedded-hal is built with the `"rtc"` feature.*
+//!
+//! [`Rtc`] reads and sets the calendar time of a real-time clock, be it the RTC peripheral of the
+//! device or an external chip like the DS3231, as a [`DateTime`]. [`Alarm`] programs its alarm,
+//! which is awaited with `nb`.
+//!
+//! [`Rtc`]: trait.Rtc.html
+//! [`DateTime`]: struct.DateTime.html
+//! [`Alarm`]: trait.Alarm.html
+//!
+//! # Examples
+//!
+//! A data logger that timestamps its samples
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::rtc::{DateTime, Rtc};
+//!
+//! fn log<R>(rtc: &mut R, log: &mut Vec<(DateTime, u16)>, sample: u16) -> Result<(), R::Error>
+//! where
+//!     R: Rtc,
+//! {
+//!     let now = rtc.datetime()?;
+//!     log.push((now, sample));
+//!     Ok(())
+//! }
+//!
+//! fn main() {
+//!     let mut ds3231: Ds3231 = {
+//!         // ..
+//! #       Ds3231(DateTime { year: 2024, month: 2, day: 29, hour: 23, minute: 59, second: 58 })
+//!     };
+//!
+//!     let mut samples = Vec::new();
+//!     log(&mut ds3231, &mut samples, 512).unwrap();
+//!     log(&mut ds3231, &mut samples, 513).unwrap();
+//!
+//!     assert!(samples[0].0 < samples[1].0);
+//!     assert_eq!(samples[1].0.to_string(), "2024-02-29T23:59:59");
+//! }
+//!
+//! # use hal::rtc::{ErrorKind, ErrorType};
+//! # struct Ds3231(DateTime);
+//! # impl ErrorType for Ds3231 { type Error = ErrorKind; }
+//! # impl Rtc for Ds3231 {
+//! #     fn datetime(&mut self) -> Result<DateTime, ErrorKind> {
+//! #         let now = self.0;
+//! #         self.0.second += 1;
+//! #         Ok(now)
+//! #     }
+//! #     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), ErrorKind> {
+//! #         self.0 = *datetime;
+//! #         Ok(())
+//! #     }
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use nb;
+use void::Void;
+
+/// RTC error kind
+///
+/// This represents a common set of RTC operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The date and time aren't valid, or can't be represented by the clock, e.g. a year past
+    /// 2099, or an alarm too far ahead
+    InvalidDateTime,
+    /// The clock lost the time, or was never set, e.g. its backup battery ran out; it must be set
+    /// again
+    NotSet,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::InvalidDateTime => "the date and time are invalid or out of range",
+            ErrorKind::NotSet => "the clock lost the time, or was never set",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// RTC error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// RTC error type
+///
+/// All the RTC traits inherit from this trait so that a clock defines its error type only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Calendar date and time, without time zone
+///
+/// Which time zone the clock keeps is up to the application; UTC avoids the jumps of daylight
+/// saving time. The fields are ordered from the most significant one, so the derived ordering is
+/// chronological, and `Display` formats them as in ISO 8601, e.g. `2024-02-29T23:59:59`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct DateTime {
+    /// Year, e.g. `2024`
+    pub year: u16,
+    /// Month, from `1` to `12`
+    pub month: u8,
+    /// Day of the month, from `1`
+    pub day: u8,
+    /// Hour, from `0` to `23`
+    pub hour: u8,
+    /// Minute, from `0` to `59`
+    pub minute: u8,
+    /// Second, from `0` to `59`
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Returns `true` if all the fields are in range, considering the length of the month and
+    /// leap years
+    ///
+    /// `Rtc::set_datetime` and `Alarm::set_alarm` fail with `ErrorKind::InvalidDateTime` if it
+    /// returns `false`.
+    pub fn is_valid(&self) -> bool {
+        let leap = match (self.year % 4, self.year % 100, self.year % 400) {
+            (_, _, 0) => true,
+            (_, 0, _) => false,
+            (0, _, _) => true,
+            _ => false,
+        };
+        let days = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return false,
+        };
+
+        self.day >= 1 && self.day <= days && self.hour < 24 && self.minute < 60 && self.second < 60
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Reading and setting the time of a real-time clock
+pub trait Rtc: ErrorType {
+    /// Returns the current date and time
+    ///
+    /// Fails with `ErrorKind::NotSet` if the clock doesn't know the time.
+    fn datetime(&mut self) -> Result<DateTime, Self::Error>;
+
+    /// Sets the current date and time
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Rtc for Box<T>
+where
+    T: Rtc + ?Sized,
+{
+    fn datetime(&mut self) -> Result<DateTime, Self::Error> {
+        (**self).datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        (**self).set_datetime(datetime)
+    }
+}
+
+impl<T> Rtc for &mut T
+where
+    T: Rtc + ?Sized,
+{
+    fn datetime(&mut self) -> Result<DateTime, Self::Error> {
+        (**self).datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        (**self).set_datetime(datetime)
+    }
+}
+
+/// Alarm of a real-time clock
+///
+/// # Examples
+///
+/// Sleeping until the next sample of a data logger is due
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::rtc::{Alarm, DateTime};
+///
+/// fn sleep_until<R: Alarm>(rtc: &mut R, datetime: &DateTime) -> Result<(), R::Error> {
+///     rtc.set_alarm(datetime)?;
+///     // (a low-power implementation would `wfi` while it would block)
+///     block!(rtc.wait_alarm())
+/// }
+///
+/// fn main() {
+///     let next = DateTime { year: 2024, month: 6, day: 1, hour: 12, minute: 0, second: 5 };
+///
+///     let mut rtc: Rtc0 = {
+///         // ..
+/// #       Rtc0 { now: DateTime { second: 0, ..next }, alarm: None }
+///     };
+///
+///     sleep_until(&mut rtc, &next).unwrap();
+/// #   assert_eq!(rtc.now, next);
+/// }
+///
+/// # use hal::rtc::{ErrorKind, ErrorType, Rtc};
+/// # struct Rtc0 { now: DateTime, alarm: Option<DateTime> }
+/// # impl ErrorType for Rtc0 { type Error = ErrorKind; }
+/// # impl Rtc for Rtc0 {
+/// #     fn datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(self.now) }
+/// #     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), ErrorKind> {
+/// #         self.now = *datetime;
+/// #         Ok(())
+/// #     }
+/// # }
+/// # impl Alarm for Rtc0 {
+/// #     fn set_alarm(&mut self, datetime: &DateTime) -> Result<(), ErrorKind> {
+/// #         self.alarm = Some(*datetime);
+/// #         Ok(())
+/// #     }
+/// #     fn clear_alarm(&mut self) -> Result<(), ErrorKind> {
+/// #         self.alarm = None;
+/// #         Ok(())
+/// #     }
+/// #     fn wait_alarm(&mut self) -> nb::Result<(), ErrorKind> {
+/// #         match self.alarm {
+/// #             Some(alarm) if alarm <= self.now => {
+/// #                 self.alarm = None;
+/// #                 Ok(())
+/// #             }
+/// #             _ => {
+/// #                 self.now.second += 1;
+/// #                 Err(nb::Error::WouldBlock)
+/// #             }
+/// #         }
+/// #     }
+/// # }
+/// ```
+pub trait Alarm: Rtc {
+    /// Programs the alarm to go off at `datetime`, replacing the previous alarm
+    fn set_alarm(&mut self, datetime: &DateTime) -> Result<(), Self::Error>;
+
+    /// Disarms the alarm
+    fn clear_alarm(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns `Ok` once the alarm went off, and disarms it
+    ///
+    /// It returns `WouldBlock` until then, and while no alarm is programmed.
+    fn wait_alarm(&mut self) -> nb::Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Alarm for Box<T>
+where
+    T: Alarm + ?Sized,
+{
+    fn set_alarm(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        (**self).set_alarm(datetime)
+    }
+
+    fn clear_alarm(&mut self) -> Result<(), Self::Error> {
+        (**self).clear_alarm()
+    }
+
+    fn wait_alarm(&mut self) -> nb::Result<(), Self::Error> {
+        (**self).wait_alarm()
+    }
+}
+
+impl<T> Alarm for &mut T
+where
+    T: Alarm + ?Sized,
+{
+    fn set_alarm(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        (**self).set_alarm(datetime)
+    }
+
+    fn clear_alarm(&mut self) -> Result<(), Self::Error> {
+        (**self).clear_alarm()
+    }
+
+    fn wait_alarm(&mut self) -> nb::Result<(), Self::Error> {
+        (**self).wait_alarm()
+    }
+}
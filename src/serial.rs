@@ -1,27 +1,173 @@
 //! Serial interface
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
 use nb;
+use void::Void;
+
+/// Serial error kind
+///
+/// This represents a common set of Serial operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// Received data does not conform to the peripheral configuration, e.g. a missing stop
+    /// bit
+    FrameFormat,
+    /// Parity check failed
+    Parity,
+    /// Serial line is too noisy to read valid data
+    Noise,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Overrun => "the peripheral receive buffer was overrun",
+            ErrorKind::FrameFormat => "received data does not match the peripheral configuration",
+            ErrorKind::Parity => "parity check failed",
+            ErrorKind::Noise => "serial line is too noisy to read valid data",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// Serial error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Serial error type
+///
+/// All the serial traits, non-blocking and blocking, inherit from this trait so that a serial
+/// interface, or each of its halves, defines its error type only once.
+pub trait ErrorType {
+    /// Read or write error
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
 
 /// Read half of a serial interface
 ///
 /// Some serial interfaces support different data sizes (8 bits, 9 bits, etc.);
 /// This can be encoded in this trait via the `Word` type parameter.
-pub trait Read<Word> {
-    /// Read error
-    type Error;
-
+pub trait Read<Word>: ErrorType {
     /// Reads a single word from the serial interface
     fn read(&mut self) -> nb::Result<Word, Self::Error>;
 }
 
-/// Write half of a serial interface
-pub trait Write<Word> {
-    /// Write error
-    type Error;
+#[cfg(feature = "alloc")]
+impl<T, Word> Read<Word> for Box<T>
+where
+    T: Read<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<Word, Self::Error> {
+        (**self).read()
+    }
+}
 
+impl<T, Word> Read<Word> for &mut T
+where
+    T: Read<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<Word, Self::Error> {
+        (**self).read()
+    }
+}
+
+/// Write half of a serial interface
+pub trait Write<Word>: ErrorType {
     /// Writes a single word to the serial interface
     fn write(&mut self, word: Word) -> nb::Result<(), Self::Error>;
 
     /// Ensures that none of the previously written words are still buffered
     fn flush(&mut self) -> nb::Result<(), Self::Error>;
 }
+
+#[cfg(feature = "alloc")]
+impl<T, Word> Write<Word> for Box<T>
+where
+    T: Write<Word> + ?Sized,
+{
+    fn write(&mut self, word: Word) -> nb::Result<(), Self::Error> {
+        (**self).write(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        (**self).flush()
+    }
+}
+
+impl<T, Word> Write<Word> for &mut T
+where
+    T: Write<Word> + ?Sized,
+{
+    fn write(&mut self, word: Word) -> nb::Result<(), Self::Error> {
+        (**self).write(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        (**self).flush()
+    }
+}
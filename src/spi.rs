@@ -1,6 +1,112 @@
 //! Serial Peripheral Interface
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
 use nb;
+use void::Void;
+
+/// SPI error kind
+///
+/// This represents a common set of SPI operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that generic drivers can handle them.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// Multiple devices on the SPI bus are trying to drive the slave select pin, e.g. in a
+    /// multi-master setup
+    ModeFault,
+    /// Received data does not conform to the peripheral configuration
+    FrameFormat,
+    /// An error occurred while asserting or deasserting the chip select line
+    ChipSelectFault,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::Overrun => "the peripheral receive buffer was overrun",
+            ErrorKind::ModeFault => "several devices are driving the slave select pin",
+            ErrorKind::FrameFormat => "received data does not match the peripheral configuration",
+            ErrorKind::ChipSelectFault => "the chip select line could not be (de)asserted",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// SPI error
+///
+/// The `Error` associated type of [`ErrorType`] must implement this trait.
+///
+/// [`ErrorType`]: trait.ErrorType.html
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// SPI error type
+///
+/// All the SPI traits, non-blocking and blocking, inherit from this trait so that an SPI
+/// interface defines its error type only once.
+pub trait ErrorType {
+    /// An enumeration of SPI errors
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
 
 /// Full duplex (master mode)
 ///
@@ -12,10 +118,7 @@ use nb;
 ///
 /// - Some SPIs can work with 8-bit *and* 16-bit words. You can overload this trait with different
 /// `Word` types to allow operation in both modes.
-pub trait FullDuplex<Word> {
-    /// An enumeration of SPI errors
-    type Error;
-
+pub trait FullDuplex<Word>: ErrorType {
     /// Reads the word stored in the shift register
     ///
     /// **NOTE** A word must be sent to the slave before attempting to call this
@@ -26,8 +129,37 @@ pub trait FullDuplex<Word> {
     fn send(&mut self, word: Word) -> nb::Result<(), Self::Error>;
 }
 
+#[cfg(feature = "alloc")]
+impl<T, Word> FullDuplex<Word> for Box<T>
+where
+    T: FullDuplex<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<Word, Self::Error> {
+        (**self).read()
+    }
+
+    fn send(&mut self, word: Word) -> nb::Result<(), Self::Error> {
+        (**self).send(word)
+    }
+}
+
+impl<T, Word> FullDuplex<Word> for &mut T
+where
+    T: FullDuplex<Word> + ?Sized,
+{
+    fn read(&mut self) -> nb::Result<Word, Self::Error> {
+        (**self).read()
+    }
+
+    fn send(&mut self, word: Word) -> nb::Result<(), Self::Error> {
+        (**self).send(word)
+    }
+}
+
 /// Clock polarity
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Polarity {
     /// Clock signal low when idle
     IdleLow,
@@ -36,7 +168,9 @@ pub enum Polarity {
 }
 
 /// Clock phase
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Phase {
     /// Data in "captured" on the first clock transition
     CaptureOnFirstTransition,
@@ -45,7 +179,9 @@ pub enum Phase {
 }
 
 /// SPI mode
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Mode {
     /// Clock polarity
     pub polarity: Polarity,
@@ -0,0 +1,129 @@
+//! EEPROM
+//!
+//! EEPROMs, be they external I2C / SPI devices or MCU-internal, can be written byte by byte
+//! without erasing first. Implementations are expected to deal with device specifics like page
+//! boundaries and write cycle times: `write` returns once the data has been committed.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use storage::nor_flash::ReadNorFlash;
+
+/// Byte-addressable EEPROM
+///
+/// Reads are provided by the [`ReadNorFlash`] supertrait, which EEPROMs implement with a
+/// `READ_SIZE` of 1.
+///
+/// [`ReadNorFlash`]: ../nor_flash/trait.ReadNorFlash.html
+///
+/// # Examples
+///
+/// Keeping a boot counter
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::eeprom::Eeprom;
+/// use hal::storage::nor_flash::ReadNorFlash;
+///
+/// const BOOT_COUNT: u32 = 0x10;
+///
+/// fn count_boot<E>(eeprom: &mut E) -> Result<u16, E::Error>
+/// where
+///     E: Eeprom,
+/// {
+///     let mut bytes = [0; 2];
+///     eeprom.read(BOOT_COUNT, &mut bytes)?;
+///
+///     let count = u16::from_le_bytes(bytes).wrapping_add(1);
+///     eeprom.write(BOOT_COUNT, &count.to_le_bytes())?;
+///
+///     Ok(count)
+/// }
+///
+/// fn main() {
+///     let mut eeprom: At24c02 = {
+///         // ..
+/// #       At24c02([0; 256])
+///     };
+///
+///     assert_eq!(count_boot(&mut eeprom).unwrap(), 1);
+///     assert_eq!(count_boot(&mut eeprom).unwrap(), 2);
+///     assert_eq!(eeprom.read_byte(BOOT_COUNT).unwrap(), 2);
+/// }
+///
+/// # use hal::storage::{ErrorKind, ErrorType};
+/// # struct At24c02([u8; 256]);
+/// # impl ErrorType for At24c02 {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl ReadNorFlash for At24c02 {
+/// #     const READ_SIZE: usize = 1;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize { self.0.len() }
+/// # }
+/// # impl Eeprom for At24c02 {
+/// #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         self.0[offset..offset + bytes.len()].copy_from_slice(bytes);
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait Eeprom: ReadNorFlash {
+    /// Writes `bytes` starting at `offset`
+    ///
+    /// Returns an error if the write would go past the end of the device.
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Reads the byte at `offset`
+    fn read_byte(&mut self, offset: u32) -> Result<u8, Self::Error> {
+        let mut byte = [0];
+        self.read(offset, &mut byte)?;
+        Ok(byte[0])
+    }
+
+    /// Writes `byte` at `offset`
+    fn write_byte(&mut self, offset: u32, byte: u8) -> Result<(), Self::Error> {
+        self.write(offset, &[byte])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Eeprom for Box<T>
+where
+    T: Eeprom + ?Sized,
+{
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(offset, bytes)
+    }
+
+    fn read_byte(&mut self, offset: u32) -> Result<u8, Self::Error> {
+        (**self).read_byte(offset)
+    }
+
+    fn write_byte(&mut self, offset: u32, byte: u8) -> Result<(), Self::Error> {
+        (**self).write_byte(offset, byte)
+    }
+}
+
+impl<T> Eeprom for &mut T
+where
+    T: Eeprom + ?Sized,
+{
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(offset, bytes)
+    }
+
+    fn read_byte(&mut self, offset: u32) -> Result<u8, Self::Error> {
+        (**self).read_byte(offset)
+    }
+
+    fn write_byte(&mut self, offset: u32, byte: u8) -> Result<(), Self::Error> {
+        (**self).write_byte(offset, byte)
+    }
+}
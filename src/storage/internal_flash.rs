@@ -0,0 +1,186 @@
+//! MCU-internal flash (in-application programming)
+//!
+//! The flash controller of most microcontrollers is locked out of reset to guard against
+//! accidental writes; it must be unlocked before pages can be erased or programmed, and should be
+//! locked again afterwards.
+//!
+//! **NOTE** the CPU may stall, or fault, while the flash bank it executes from is being erased or
+//! programmed. It is the responsibility of implementations to run the operations from RAM, or to
+//! otherwise make them safe, on devices where that matters.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use storage::nor_flash::ReadNorFlash;
+
+/// In-application programming of the MCU's own flash
+///
+/// Reads are provided by the [`ReadNorFlash`] supertrait.
+///
+/// [`ReadNorFlash`]: ../nor_flash/trait.ReadNorFlash.html
+///
+/// # Contract
+///
+/// - `erase_page` and `program` MUST return an error while the flash is locked.
+/// - `lock` MUST leave the flash locked even if it was not unlocked.
+///
+/// # Examples
+///
+/// Writing a firmware update to a slot
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::internal_flash::InternalFlash;
+/// use hal::storage::nor_flash::ReadNorFlash;
+///
+/// fn install<F>(flash: &mut F, slot: u32, image: &[u8]) -> Result<(), F::Error>
+/// where
+///     F: InternalFlash,
+/// {
+///     let pages = (image.len() + F::PAGE_SIZE - 1) / F::PAGE_SIZE;
+///     let first = slot / F::PAGE_SIZE as u32;
+///
+///     flash.unlock()?;
+///     let result = (|| {
+///         for page in first..first + pages as u32 {
+///             flash.erase_page(page)?;
+///         }
+///         flash.program(slot, image)
+///     })();
+///     flash.lock();
+///
+///     result
+/// }
+///
+/// fn main() {
+///     let mut flash: Flash = {
+///         // ..
+/// #       Flash { locked: true, memory: [0; 4096] }
+///     };
+///
+///     install(&mut flash, 2048, &[0xaa; 1536]).unwrap();
+///     assert!(flash.is_locked());
+///
+///     let mut word = [0; 4];
+///     flash.read(2048 + 1532, &mut word).unwrap();
+///     assert_eq!(word, [0xaa; 4]);
+/// }
+///
+/// # use hal::storage::{ErrorKind, ErrorType};
+/// # struct Flash { locked: bool, memory: [u8; 4096] }
+/// # impl ErrorType for Flash {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl ReadNorFlash for Flash {
+/// #     const READ_SIZE: usize = 1;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.memory[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize { self.memory.len() }
+/// # }
+/// # impl InternalFlash for Flash {
+/// #     const PAGE_SIZE: usize = 1024;
+/// #     const WRITE_SIZE: usize = 4;
+/// #     fn unlock(&mut self) -> Result<(), ErrorKind> { self.locked = false; Ok(()) }
+/// #     fn lock(&mut self) { self.locked = true }
+/// #     fn is_locked(&self) -> bool { self.locked }
+/// #     fn erase_page(&mut self, page: u32) -> Result<(), ErrorKind> {
+/// #         if self.locked { return Err(ErrorKind::WriteProtected) }
+/// #         let start = page as usize * Self::PAGE_SIZE;
+/// #         for byte in &mut self.memory[start..start + Self::PAGE_SIZE] { *byte = 0xff }
+/// #         Ok(())
+/// #     }
+/// #     fn program(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ErrorKind> {
+/// #         if self.locked { return Err(ErrorKind::WriteProtected) }
+/// #         let offset = offset as usize;
+/// #         self.memory[offset..offset + bytes.len()].copy_from_slice(bytes);
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait InternalFlash: ReadNorFlash {
+    /// Size of a page, the unit of erasure, in bytes
+    const PAGE_SIZE: usize;
+
+    /// The minimum number of bytes that can be programmed; `offset` and the length of the data
+    /// passed to `program` must be multiples of it
+    const WRITE_SIZE: usize;
+
+    /// Unlocks the flash controller, allowing erase and program operations
+    fn unlock(&mut self) -> Result<(), Self::Error>;
+
+    /// Locks the flash controller
+    fn lock(&mut self);
+
+    /// Is the flash controller locked?
+    fn is_locked(&self) -> bool;
+
+    /// Erases `page`, the page that starts at offset `page * PAGE_SIZE`
+    fn erase_page(&mut self, page: u32) -> Result<(), Self::Error>;
+
+    /// Programs `bytes` starting at `offset`
+    ///
+    /// The target bytes are expected to have been erased. Returns an error if the arguments are
+    /// not aligned to `WRITE_SIZE` or if the write would go past the end of the flash.
+    fn program(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> InternalFlash for Box<T>
+where
+    T: InternalFlash + ?Sized,
+{
+    const PAGE_SIZE: usize = T::PAGE_SIZE;
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+
+    fn unlock(&mut self) -> Result<(), Self::Error> {
+        (**self).unlock()
+    }
+
+    fn lock(&mut self) {
+        (**self).lock()
+    }
+
+    fn is_locked(&self) -> bool {
+        (**self).is_locked()
+    }
+
+    fn erase_page(&mut self, page: u32) -> Result<(), Self::Error> {
+        (**self).erase_page(page)
+    }
+
+    fn program(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).program(offset, bytes)
+    }
+}
+
+impl<T> InternalFlash for &mut T
+where
+    T: InternalFlash + ?Sized,
+{
+    const PAGE_SIZE: usize = T::PAGE_SIZE;
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+
+    fn unlock(&mut self) -> Result<(), Self::Error> {
+        (**self).unlock()
+    }
+
+    fn lock(&mut self) {
+        (**self).lock()
+    }
+
+    fn is_locked(&self) -> bool {
+        (**self).is_locked()
+    }
+
+    fn erase_page(&mut self, page: u32) -> Result<(), Self::Error> {
+        (**self).erase_page(page)
+    }
+
+    fn program(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).program(offset, bytes)
+    }
+}
@@ -0,0 +1,449 @@
+//! Non-volatile storage
+//!
+//! *This module is available if embedded-hal is built with the `"storage"` feature.*
+//!
+//! All the traits in this module are blocking: operations return once they have completed. All
+//! `offset`s are byte offsets relative to the start of the storage device (or region) the trait is
+//! implemented for.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp;
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+
+use void::Void;
+
+use self::eeprom::Eeprom;
+use self::nor_flash::ReadNorFlash;
+
+pub mod eeprom;
+pub mod internal_flash;
+pub mod nand_flash;
+pub mod nor_flash;
+pub mod region;
+
+/// Storage error kind
+///
+/// This represents a common set of storage operation errors. HAL implementations are free to
+/// define more specific or additional error types; the [`Error`] trait maps them to one of these
+/// kinds so that file systems and other generic code can decide how to recover.
+///
+/// [`Error`]: trait.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ErrorKind {
+    /// The arguments were not aligned to the read, write or erase granularity of the device
+    NotAligned,
+    /// The operation would have accessed storage past the end of the device or partition
+    OutOfBounds,
+    /// The storage is write protected or locked
+    WriteProtected,
+    /// The data read back is corrupted, e.g. it has more bit errors than ECC can correct
+    Corrupted,
+    /// The device didn't complete the operation in time
+    Timeout,
+    /// A different error occurred. The original error may contain more information
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::NotAligned => "the arguments were not aligned to the device granularity",
+            ErrorKind::OutOfBounds => "the access was out of the bounds of the storage",
+            ErrorKind::WriteProtected => "the storage is write protected",
+            ErrorKind::Corrupted => "the data read back is corrupted",
+            ErrorKind::Timeout => "the device did not complete the operation in time",
+            ErrorKind::Other => "a different error occurred",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ErrorKind {}
+
+/// Storage error
+///
+/// The `Error` associated types of the storage traits must implement this trait.
+///
+/// # Examples
+///
+/// Retrying timed out erases
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::nor_flash::NorFlash;
+/// use hal::storage::{Error, ErrorKind};
+///
+/// fn erase<F>(flash: &mut F, from: u32, to: u32) -> Result<(), F::Error>
+/// where
+///     F: NorFlash,
+/// {
+///     let mut retries = 3;
+///     loop {
+///         match flash.erase(from, to) {
+///             Err(ref e) if e.kind() == ErrorKind::Timeout && retries > 0 => retries -= 1,
+///             result => return result,
+///         }
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub trait Error: Debug {
+    /// Returns the kind of this error
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Error for Box<E>
+where
+    E: Error + ?Sized,
+{
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for Void {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Storage error type
+///
+/// All the storage traits inherit from this trait so that a storage device defines its error type
+/// only once.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ErrorType for Box<T>
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+impl<T> ErrorType for &mut T
+where
+    T: ErrorType + ?Sized,
+{
+    type Error = T::Error;
+}
+
+/// Read-only storage
+///
+/// Unlike [`ReadNorFlash`], reads are not subject to any alignment requirement. This trait is
+/// implemented for all the implementers of `ReadNorFlash`, and thus for all writable storage
+/// devices except NAND flash, and for byte slices, which can be used to access memory-mapped
+/// flash.
+///
+/// The implementation for `ReadNorFlash` reads the unaligned start and end of a read through a
+/// buffer of `READ_SIZE` bytes. It's on the stack if `READ_SIZE` is at most 32 bytes, and on the
+/// heap otherwise, which needs the `"alloc"` feature: without it, unaligned reads from the devices
+/// with a larger `READ_SIZE` panic.
+///
+/// The methods of this trait have distinct names so that they don't collide with the ones of
+/// `ReadNorFlash` when both traits are in scope.
+///
+/// [`ReadNorFlash`]: nor_flash/trait.ReadNorFlash.html
+///
+/// # Examples
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::ReadStorage;
+///
+/// /// Reads a length-prefixed asset
+/// fn load<'b, S>(storage: &mut S, offset: u32, buffer: &'b mut [u8]) -> Option<&'b [u8]>
+/// where
+///     S: ReadStorage,
+/// {
+///     let mut len = [0];
+///     storage.read_bytes(offset, &mut len).ok()?;
+///     let asset = buffer.get_mut(..len[0] as usize)?;
+///     storage.read_bytes(offset + 1, asset).ok()?;
+///     Some(asset)
+/// }
+///
+/// fn main() {
+///     // e.g. an image of a memory-mapped flash
+///     let mut flash: &[u8] = &[0xff, 3, b'f', b'o', b'o', 0xff];
+///
+///     let mut buffer = [0; 16];
+///     assert_eq!(load(&mut flash, 1, &mut buffer), Some(&b"foo"[..]));
+///     assert_eq!(load(&mut flash, 0, &mut buffer), None);
+/// }
+/// ```
+///
+/// Unaligned reads from a flash that is read in blocks of 64 bytes
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::ReadStorage;
+///
+/// # #[cfg(feature = "alloc")]
+/// fn main() {
+///     let mut flash: Flash = {
+///         // ..
+/// #       Flash((0..=255).collect())
+///     };
+///
+///     let mut bytes = [0; 70];
+///     flash.read_bytes(60, &mut bytes).unwrap();
+///     assert!(bytes.iter().zip(60..).all(|(&byte, i)| byte == i));
+///
+///     let mut byte = [0];
+///     flash.read_bytes(130, &mut byte).unwrap();
+///     assert_eq!(byte, [130]);
+/// }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+///
+/// # use hal::storage::nor_flash::ReadNorFlash;
+/// # use hal::storage::{ErrorKind, ErrorType};
+/// # struct Flash(Vec<u8>);
+/// # impl ErrorType for Flash { type Error = ErrorKind; }
+/// # impl ReadNorFlash for Flash {
+/// #     const READ_SIZE: usize = 64;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         if offset % 64 != 0 || bytes.len() % 64 != 0 { return Err(ErrorKind::NotAligned) }
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize { self.0.len() }
+/// # }
+/// ```
+pub trait ReadStorage: ErrorType {
+    /// Reads enough bytes, starting at `offset`, to fill `bytes`
+    ///
+    /// Returns an error if the read would go past the end of the storage.
+    fn read_bytes(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Returns the capacity of the storage, in bytes
+    fn storage_capacity(&self) -> usize;
+}
+
+/// Largest `READ_SIZE` for which the blanket implementation reads the unaligned parts of a read
+/// through a buffer on the stack
+const MAX_READ_SIZE: usize = 32;
+
+impl<F> ReadStorage for F
+where
+    F: ReadNorFlash,
+{
+    /// Unaligned reads are split into aligned reads; the unaligned start and end go through a
+    /// buffer of `READ_SIZE` bytes, which is on the heap if `READ_SIZE` is greater than 32 bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the read is unaligned, `READ_SIZE` is greater than 32 bytes and the `"alloc"` feature
+    /// is disabled
+    fn read_bytes(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let align = F::READ_SIZE;
+        if align <= 1 {
+            return self.read(offset, bytes);
+        }
+        let (head, tail) = (offset as usize % align, bytes.len() % align);
+        if head == 0 && tail == 0 {
+            return self.read(offset, bytes);
+        }
+
+        let mut stack = [0; MAX_READ_SIZE];
+        #[cfg(feature = "alloc")]
+        let mut heap = Vec::new();
+        let scratch: &mut [u8] = if align <= MAX_READ_SIZE {
+            &mut stack[..align]
+        } else {
+            #[cfg(feature = "alloc")]
+            {
+                heap.resize(align, 0);
+                &mut heap
+            }
+            #[cfg(not(feature = "alloc"))]
+            panic!("unaligned reads from a flash with a `READ_SIZE` greater than 32 need `alloc`")
+        };
+
+        let mut offset = offset;
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let skip = offset as usize % align;
+            let left = bytes.len() - pos;
+
+            let n = if skip == 0 && left >= align {
+                // aligned bulk: read straight into the caller's buffer
+                let n = left - left % align;
+                self.read(offset, &mut bytes[pos..pos + n])?;
+                n
+            } else {
+                // unaligned head or tail: go through the scratch buffer
+                self.read(offset - skip as u32, scratch)?;
+                let n = cmp::min(align - skip, left);
+                bytes[pos..pos + n].copy_from_slice(&scratch[skip..skip + n]);
+                n
+            };
+
+            pos += n;
+            offset += n as u32;
+        }
+
+        Ok(())
+    }
+
+    fn storage_capacity(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// Writable storage
+///
+/// Unlike the writes of the device traits, `write_bytes` is not subject to any alignment
+/// requirement and doesn't need the storage to be erased first: the bytes around the written ones
+/// are preserved. This lets file systems and configuration stores be written once for all kinds
+/// of storage. The trait is implemented for all the [`Eeprom`]s, and NOR flash memories are
+/// turned into `Storage` by [`RmwStorage`], which erases and rewrites the blocks that a write
+/// changes; the erase granularity of the latter is `NorFlash::ERASE_SIZE`.
+///
+/// [`Eeprom`]: eeprom/trait.Eeprom.html
+/// [`RmwStorage`]: nor_flash/struct.RmwStorage.html
+///
+/// # Examples
+///
+/// Saving a configuration to the last block of a flash
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::nor_flash::RmwStorage;
+/// use hal::storage::{ReadStorage, Storage};
+///
+/// fn save<S>(storage: &mut S, config: &[u8; 4]) -> Result<(), S::Error>
+/// where
+///     S: Storage,
+/// {
+///     let offset = storage.storage_capacity() - 4096;
+///     storage.write_bytes(offset as u32 + 1, config)
+/// }
+///
+/// fn main() {
+///     let flash: W25q32 = {
+///         // ..
+/// #       W25q32(vec![0xff; 16 * 4096])
+///     };
+///
+///     let mut block = [0; 4096];
+///     let mut storage = RmwStorage::new(flash, &mut block);
+///     save(&mut storage, b"\x01\x2a\x00\x10").unwrap();
+///
+///     let mut config = [0; 6];
+///     storage.read_bytes(15 * 4096, &mut config).unwrap();
+///     assert_eq!(config, *b"\xff\x01\x2a\x00\x10\xff");
+/// }
+///
+/// # use hal::storage::nor_flash::{NorFlash, ReadNorFlash};
+/// # use hal::storage::{ErrorKind, ErrorType};
+/// # struct W25q32(Vec<u8>);
+/// # impl ErrorType for W25q32 { type Error = ErrorKind; }
+/// # impl ReadNorFlash for W25q32 {
+/// #     const READ_SIZE: usize = 1;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize { self.0.len() }
+/// # }
+/// # impl NorFlash for W25q32 {
+/// #     const WRITE_SIZE: usize = 1;
+/// #     const ERASE_SIZE: usize = 4096;
+/// #     fn erase(&mut self, from: u32, to: u32) -> Result<(), ErrorKind> {
+/// #         self.0[from as usize..to as usize].iter_mut().for_each(|cell| *cell = 0xff);
+/// #         Ok(())
+/// #     }
+/// #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         for (cell, byte) in self.0[offset..offset + bytes.len()].iter_mut().zip(bytes) {
+/// #             *cell &= *byte;
+/// #         }
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait Storage: ReadStorage {
+    /// Writes `bytes` starting at `offset`
+    ///
+    /// Returns an error if the write would go past the end of the storage.
+    fn write_bytes(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<E> Storage for E
+where
+    E: Eeprom,
+{
+    fn write_bytes(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.write(offset, bytes)
+    }
+}
+
+/// The requested bytes are out of the bounds of the storage
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct OutOfBounds;
+
+impl Error for OutOfBounds {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::OutOfBounds
+    }
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&ErrorKind::OutOfBounds, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for OutOfBounds {}
+
+impl ErrorType for &[u8] {
+    type Error = OutOfBounds;
+}
+
+impl ReadStorage for &[u8] {
+    fn read_bytes(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), OutOfBounds> {
+        let start = offset as usize;
+        let source = start
+            .checked_add(bytes.len())
+            .and_then(|end| self.get(start..end))
+            .ok_or(OutOfBounds)?;
+
+        bytes.copy_from_slice(source);
+        Ok(())
+    }
+
+    fn storage_capacity(&self) -> usize {
+        self.len()
+    }
+}
@@ -0,0 +1,142 @@
+//! NAND flash memory
+//!
+//! NAND flash is organized in blocks made of [`PAGES_PER_BLOCK`] pages. Each page has a main
+//! area of [`PAGE_SIZE`] bytes and a spare (out-of-band) area of [`SPARE_SIZE`] bytes, usually
+//! used for ECC codes and file system metadata. Pages are read and programmed as a whole; blocks
+//! are the unit of erasure.
+//!
+//! Unlike NOR flash, NAND devices may ship with, and develop over time, *bad blocks* that must
+//! not be used. Users of this trait are expected to check [`is_bad_block`] before using a block
+//! and to call [`mark_bad_block`] when erasing or programming a block fails.
+//!
+//! Pages are identified by their absolute index: page `p` of block `b` has index
+//! `b * PAGES_PER_BLOCK + p`.
+//!
+//! [`PAGES_PER_BLOCK`]: trait.NandFlash.html#associatedconstant.PAGES_PER_BLOCK
+//! [`PAGE_SIZE`]: trait.NandFlash.html#associatedconstant.PAGE_SIZE
+//! [`SPARE_SIZE`]: trait.NandFlash.html#associatedconstant.SPARE_SIZE
+//! [`is_bad_block`]: trait.NandFlash.html#tymethod.is_bad_block
+//! [`mark_bad_block`]: trait.NandFlash.html#tymethod.mark_bad_block
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use storage::ErrorType;
+
+/// NAND flash device
+pub trait NandFlash: ErrorType {
+    /// Size of the main area of a page, in bytes
+    const PAGE_SIZE: usize;
+
+    /// Size of the spare area of a page, in bytes
+    const SPARE_SIZE: usize;
+
+    /// Number of pages in a block
+    const PAGES_PER_BLOCK: u32;
+
+    /// Returns the number of blocks in the device, bad blocks included
+    fn block_count(&self) -> u32;
+
+    /// Reads the main area of `page` into `data`, which must be `PAGE_SIZE` bytes long
+    ///
+    /// Returns the number of bit errors corrected by the device or controller ECC, if any, so
+    /// callers can relocate data before errors become uncorrectable. Uncorrectable errors are
+    /// reported as an `Err`.
+    fn read_page(&mut self, page: u32, data: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Reads the first `spare.len()` bytes of the spare area of `page`
+    ///
+    /// Returns an error if `spare` is longer than `SPARE_SIZE`.
+    fn read_spare(&mut self, page: u32, spare: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Programs the main area of `page` with `data`, which must be `PAGE_SIZE` bytes long, and the
+    /// beginning of its spare area with `spare`
+    ///
+    /// The page must have been erased. Pages within a block must be programmed in increasing
+    /// order. Bytes of the spare area not covered by `spare` are left erased.
+    fn program_page(&mut self, page: u32, data: &[u8], spare: &[u8]) -> Result<(), Self::Error>;
+
+    /// Erases `block`
+    fn erase_block(&mut self, block: u32) -> Result<(), Self::Error>;
+
+    /// Is `block` marked as bad?
+    fn is_bad_block(&mut self, block: u32) -> Result<bool, Self::Error>;
+
+    /// Marks `block` as bad, so that it is reported as such by `is_bad_block` from now on
+    fn mark_bad_block(&mut self, block: u32) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NandFlash for Box<T>
+where
+    T: NandFlash + ?Sized,
+{
+    const PAGE_SIZE: usize = T::PAGE_SIZE;
+    const SPARE_SIZE: usize = T::SPARE_SIZE;
+    const PAGES_PER_BLOCK: u32 = T::PAGES_PER_BLOCK;
+
+    fn block_count(&self) -> u32 {
+        (**self).block_count()
+    }
+
+    fn read_page(&mut self, page: u32, data: &mut [u8]) -> Result<usize, Self::Error> {
+        (**self).read_page(page, data)
+    }
+
+    fn read_spare(&mut self, page: u32, spare: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read_spare(page, spare)
+    }
+
+    fn program_page(&mut self, page: u32, data: &[u8], spare: &[u8]) -> Result<(), Self::Error> {
+        (**self).program_page(page, data, spare)
+    }
+
+    fn erase_block(&mut self, block: u32) -> Result<(), Self::Error> {
+        (**self).erase_block(block)
+    }
+
+    fn is_bad_block(&mut self, block: u32) -> Result<bool, Self::Error> {
+        (**self).is_bad_block(block)
+    }
+
+    fn mark_bad_block(&mut self, block: u32) -> Result<(), Self::Error> {
+        (**self).mark_bad_block(block)
+    }
+}
+
+impl<T> NandFlash for &mut T
+where
+    T: NandFlash + ?Sized,
+{
+    const PAGE_SIZE: usize = T::PAGE_SIZE;
+    const SPARE_SIZE: usize = T::SPARE_SIZE;
+    const PAGES_PER_BLOCK: u32 = T::PAGES_PER_BLOCK;
+
+    fn block_count(&self) -> u32 {
+        (**self).block_count()
+    }
+
+    fn read_page(&mut self, page: u32, data: &mut [u8]) -> Result<usize, Self::Error> {
+        (**self).read_page(page, data)
+    }
+
+    fn read_spare(&mut self, page: u32, spare: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read_spare(page, spare)
+    }
+
+    fn program_page(&mut self, page: u32, data: &[u8], spare: &[u8]) -> Result<(), Self::Error> {
+        (**self).program_page(page, data, spare)
+    }
+
+    fn erase_block(&mut self, block: u32) -> Result<(), Self::Error> {
+        (**self).erase_block(block)
+    }
+
+    fn is_bad_block(&mut self, block: u32) -> Result<bool, Self::Error> {
+        (**self).is_bad_block(block)
+    }
+
+    fn mark_bad_block(&mut self, block: u32) -> Result<(), Self::Error> {
+        (**self).mark_bad_block(block)
+    }
+}
@@ -0,0 +1,315 @@
+//! NOR flash memory
+//!
+//! NOR flash is erased in blocks of [`ERASE_SIZE`] bytes; erasing sets all the bits of a block to
+//! `1`. Programming (writing) can only change bits from `1` to `0`, so a location must be erased
+//! before it can be written again. [`RmwStorage`] hides this, for the code that writes through
+//! the [`Storage`] trait.
+//!
+//! [`ERASE_SIZE`]: trait.NorFlash.html#associatedconstant.ERASE_SIZE
+//! [`RmwStorage`]: struct.RmwStorage.html
+//! [`Storage`]: ../trait.Storage.html
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::cmp;
+
+use storage::{ErrorType, Storage};
+
+/// Read-only NOR flash
+///
+/// This is also the read interface of other storage devices, like [`Eeprom`]s, that are read the
+/// same way.
+///
+/// [`Eeprom`]: ../eeprom/trait.Eeprom.html
+pub trait ReadNorFlash: ErrorType {
+    /// The minimum number of bytes the device can read; `offset` and the length of the buffer
+    /// passed to `read` must be multiples of it
+    const READ_SIZE: usize;
+
+    /// Reads enough bytes, starting at `offset`, to fill `bytes`
+    ///
+    /// Returns an error if the arguments are not aligned to `READ_SIZE` or if the read would go
+    /// past the end of the device.
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Returns the capacity of the device, in bytes
+    fn capacity(&self) -> usize;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ReadNorFlash for Box<T>
+where
+    T: ReadNorFlash + ?Sized,
+{
+    const READ_SIZE: usize = T::READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+impl<T> ReadNorFlash for &mut T
+where
+    T: ReadNorFlash + ?Sized,
+{
+    const READ_SIZE: usize = T::READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+/// NOR flash that can be erased and programmed
+///
+/// # Examples
+///
+/// Persisting a configuration record in the last block of a device
+///
+/// ```
+/// extern crate embedded_hal as hal;
+///
+/// use hal::storage::nor_flash::{NorFlash, ReadNorFlash};
+///
+/// fn save<F>(flash: &mut F, record: &[u8]) -> Result<(), F::Error>
+/// where
+///     F: NorFlash,
+/// {
+///     let to = flash.capacity() as u32;
+///     let from = to - F::ERASE_SIZE as u32;
+///
+///     flash.erase(from, to)?;
+///     flash.write(from, record)
+/// }
+///
+/// fn main() {
+///     let mut flash: Flash = {
+///         // ..
+/// #       Flash([0; 8192])
+///     };
+///
+///     save(&mut flash, b"config-v1").unwrap_err(); // not aligned to `WRITE_SIZE`
+///     save(&mut flash, b"config-v1\0\0\0").unwrap();
+///
+///     let mut record = [0; 12];
+///     flash.read(8192 - 4096, &mut record).unwrap();
+///     assert_eq!(&record, b"config-v1\0\0\0");
+/// }
+///
+/// # use hal::storage::{ErrorKind, ErrorType};
+/// # struct Flash([u8; 8192]);
+/// # impl ErrorType for Flash {
+/// #     type Error = ErrorKind;
+/// # }
+/// # impl ReadNorFlash for Flash {
+/// #     const READ_SIZE: usize = 1;
+/// #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+/// #         let offset = offset as usize;
+/// #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+/// #         Ok(())
+/// #     }
+/// #     fn capacity(&self) -> usize { self.0.len() }
+/// # }
+/// # impl NorFlash for Flash {
+/// #     const WRITE_SIZE: usize = 4;
+/// #     const ERASE_SIZE: usize = 4096;
+/// #     fn erase(&mut self, from: u32, to: u32) -> Result<(), ErrorKind> {
+/// #         for byte in &mut self.0[from as usize..to as usize] { *byte = 0xff }
+/// #         Ok(())
+/// #     }
+/// #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ErrorKind> {
+/// #         if bytes.len() % Self::WRITE_SIZE != 0 { return Err(ErrorKind::NotAligned) }
+/// #         let offset = offset as usize;
+/// #         for (cell, byte) in self.0[offset..offset + bytes.len()].iter_mut().zip(bytes) {
+/// #             *cell &= *byte;
+/// #         }
+/// #         Ok(())
+/// #     }
+/// # }
+/// ```
+pub trait NorFlash: ReadNorFlash {
+    /// The minimum number of bytes the device can program; `offset` and the length of the buffer
+    /// passed to `write` must be multiples of it
+    const WRITE_SIZE: usize;
+
+    /// The minimum number of bytes the device can erase; `from` and `to` passed to `erase` must be
+    /// multiples of it
+    const ERASE_SIZE: usize;
+
+    /// Erases the bytes in the range `from .. to`
+    ///
+    /// Returns an error if the arguments are not aligned to `ERASE_SIZE`, if `from` is greater
+    /// than `to` or if `to` is past the end of the device.
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error>;
+
+    /// Programs `bytes` starting at `offset`
+    ///
+    /// The target bytes are expected to have been erased. If they haven't, and the device
+    /// implements `MultiwriteNorFlash`, the result is that of AND-ing the previous contents with
+    /// `bytes`. Otherwise the resulting contents are unspecified.
+    ///
+    /// Returns an error if the arguments are not aligned to `WRITE_SIZE` or if the write would go
+    /// past the end of the device.
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NorFlash for Box<T>
+where
+    T: NorFlash + ?Sized,
+{
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+    const ERASE_SIZE: usize = T::ERASE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        (**self).erase(from, to)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(offset, bytes)
+    }
+}
+
+impl<T> NorFlash for &mut T
+where
+    T: NorFlash + ?Sized,
+{
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+    const ERASE_SIZE: usize = T::ERASE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        (**self).erase(from, to)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(offset, bytes)
+    }
+}
+
+/// Marker trait that indicates that a NOR flash allows programming the same location several times
+/// between erases
+///
+/// Each program operation can only clear bits, i.e. the result of programming `bytes` over
+/// already programmed data is `previous & bytes`. Log-structured storage formats rely on this to,
+/// for example, invalidate a record in place by clearing a flag.
+///
+/// Devices that protect each programmed word with ECC usually don't allow this and must not
+/// implement this trait.
+pub trait MultiwriteNorFlash: NorFlash {}
+
+#[cfg(feature = "alloc")]
+impl<T: MultiwriteNorFlash + ?Sized> MultiwriteNorFlash for Box<T> {}
+
+impl<T: MultiwriteNorFlash + ?Sized> MultiwriteNorFlash for &mut T {}
+
+/// [`Storage`] on top of a NOR flash
+///
+/// Each write reads the blocks it changes into a buffer of `ERASE_SIZE` bytes, merges the new
+/// bytes, then erases and programs the blocks again; the blocks whose contents don't change
+/// aren't erased. A write that is interrupted, e.g. by a reset, can leave a block erased.
+///
+/// [`Storage`]: ../trait.Storage.html
+#[derive(Debug)]
+pub struct RmwStorage<'a, F> {
+    flash: F,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, F> RmwStorage<'a, F>
+where
+    F: NorFlash,
+{
+    /// Wraps `flash`, using `buffer` to merge the written bytes into the blocks
+    ///
+    /// # Panics
+    ///
+    /// If one of the sizes of `F` is zero, if `F::ERASE_SIZE` is not a multiple of `F::READ_SIZE`
+    /// and `F::WRITE_SIZE`, or if `buffer` is shorter than `F::ERASE_SIZE`
+    pub fn new(flash: F, buffer: &'a mut [u8]) -> Self {
+        let (read, write, erase) = (F::READ_SIZE, F::WRITE_SIZE, F::ERASE_SIZE);
+        assert!(
+            read != 0 && write != 0 && erase != 0,
+            "the read, write and erase sizes must not be zero"
+        );
+        assert!(
+            erase / read * read == erase && erase / write * write == erase,
+            "the erase size must be a multiple of the read and write sizes"
+        );
+        assert!(
+            buffer.len() >= F::ERASE_SIZE,
+            "the buffer must hold an erase block"
+        );
+        RmwStorage {
+            flash,
+            buffer: &mut buffer[..F::ERASE_SIZE],
+        }
+    }
+
+    /// Returns the wrapped flash
+    pub fn into_inner(self) -> F {
+        self.flash
+    }
+}
+
+impl<'a, F> ErrorType for RmwStorage<'a, F>
+where
+    F: ErrorType,
+{
+    type Error = F::Error;
+}
+
+impl<'a, F> ReadNorFlash for RmwStorage<'a, F>
+where
+    F: ReadNorFlash,
+{
+    const READ_SIZE: usize = F::READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        self.flash.read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        self.flash.capacity()
+    }
+}
+
+impl<'a, F> Storage for RmwStorage<'a, F>
+where
+    F: NorFlash,
+{
+    fn write_bytes(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        let size = F::ERASE_SIZE;
+        let start = offset as usize;
+        let end = start + bytes.len();
+        // last block first, so that the device rejects a write past its end before anything is
+        // erased
+        for block in (start / size..=(end - 1) / size).rev() {
+            let from = block * size;
+            self.flash.read(from as u32, self.buffer)?;
+
+            let (lo, hi) = (cmp::max(start, from), cmp::min(end, from + size));
+            let new = &bytes[lo - start..hi - start];
+            let old = &mut self.buffer[lo - from..hi - from];
+            if old == new {
+                continue;
+            }
+            old.copy_from_slice(new);
+
+            self.flash.erase(from as u32, (from + size) as u32)?;
+            self.flash.write(from as u32, self.buffer)?;
+        }
+
+        Ok(())
+    }
+}
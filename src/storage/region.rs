@@ -0,0 +1,277 @@
+//! Regions and partitions of a storage device
+//!
+//! A [`Region`] is a window of the address space of a storage device. Regions are plain values:
+//! they can be declared as constants, split and nested without borrowing the device. A region is
+//! bound to a device with [`Region::partition`], producing a [`Partition`] that implements the
+//! same storage traits as the device but only gives access to the region; accesses outside of it
+//! fail with [`Error::OutOfBounds`].
+//!
+//! Regions of NOR flash should start and end on erase block boundaries.
+//!
+//! [`Region`]: struct.Region.html
+//! [`Region::partition`]: struct.Region.html#method.partition
+//! [`Partition`]: struct.Partition.html
+//! [`Error::OutOfBounds`]: enum.Error.html#variant.OutOfBounds
+//!
+//! # Examples
+//!
+//! A/B firmware slots followed by a configuration area
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::storage::eeprom::Eeprom;
+//! use hal::storage::nor_flash::ReadNorFlash;
+//! use hal::storage::region::{Error, Region};
+//!
+//! const FLASH: Region = Region::new(0, 1024);
+//! const SLOTS: Region = Region::new(0, 768);
+//! const CONFIG: Region = Region::new(768, 256);
+//!
+//! fn main() {
+//!     let mut device: Device = {
+//!         // ..
+//! #       Device([0; 1024])
+//!     };
+//!
+//!     let (slot_a, slot_b) = SLOTS.split_at(384).unwrap();
+//!     assert_eq!(slot_b, Region::new(384, 384));
+//!     assert_eq!(FLASH.sub(768, 256), Some(CONFIG));
+//!
+//!     slot_b.partition(&mut device).write(0, b"image B").unwrap();
+//!     assert_eq!(device.0[384..391], *b"image B");
+//!
+//!     // writes can't spill over into the next region
+//!     let mut a = slot_a.partition(&mut device);
+//!     assert_eq!(a.write(380, b"too long"), Err(Error::OutOfBounds));
+//!
+//!     // partitions can be further divided
+//!     let mut config = CONFIG.partition(&mut device);
+//!     config.sub(16, 16).unwrap().write_byte(0, 42).unwrap();
+//!     assert_eq!(device.0[768 + 16], 42);
+//! }
+//!
+//! # use hal::storage::{ErrorKind, ErrorType};
+//! # struct Device([u8; 1024]);
+//! # impl ErrorType for Device {
+//! #     type Error = ErrorKind;
+//! # }
+//! # impl ReadNorFlash for Device {
+//! #     const READ_SIZE: usize = 1;
+//! #     fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ErrorKind> {
+//! #         let offset = offset as usize;
+//! #         bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+//! #         Ok(())
+//! #     }
+//! #     fn capacity(&self) -> usize { self.0.len() }
+//! # }
+//! # impl Eeprom for Device {
+//! #     fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ErrorKind> {
+//! #         let offset = offset as usize;
+//! #         self.0[offset..offset + bytes.len()].copy_from_slice(bytes);
+//! #         Ok(())
+//! #     }
+//! # }
+//! ```
+
+use core::fmt;
+
+use storage::eeprom::Eeprom;
+use storage::nor_flash::{MultiwriteNorFlash, NorFlash, ReadNorFlash};
+use storage::{self, ErrorKind, ErrorType};
+
+/// A window of `size` bytes starting at `offset`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Region {
+    offset: u32,
+    size: u32,
+}
+
+impl Region {
+    /// Creates a region of `size` bytes starting at `offset`
+    pub const fn new(offset: u32, size: u32) -> Region {
+        Region { offset, size }
+    }
+
+    /// Returns the offset of the start of this region
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the size of this region, in bytes
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the region of `size` bytes starting at `offset` *relative to the start of this
+    /// region*, or `None` if it doesn't fit in this region
+    pub fn sub(&self, offset: u32, size: u32) -> Option<Region> {
+        if self.contains(offset, size) {
+            Some(Region::new(self.offset + offset, size))
+        } else {
+            None
+        }
+    }
+
+    /// Splits this region in two: the first `at` bytes and the rest
+    ///
+    /// Returns `None` if `at` is greater than the size of this region
+    pub fn split_at(&self, at: u32) -> Option<(Region, Region)> {
+        if at <= self.size {
+            Some((
+                Region::new(self.offset, at),
+                Region::new(self.offset + at, self.size - at),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Restricts `storage` to this region
+    pub fn partition<'a, S>(&self, storage: &'a mut S) -> Partition<'a, S>
+    where
+        S: 'a,
+    {
+        Partition {
+            storage,
+            region: *self,
+        }
+    }
+
+    fn contains(&self, offset: u32, len: u32) -> bool {
+        offset
+            .checked_add(len)
+            .map(|end| end <= self.size)
+            .unwrap_or(false)
+    }
+
+    fn translate<E>(&self, offset: u32, len: usize) -> Result<u32, Error<E>> {
+        if len <= u32::MAX as usize && self.contains(offset, len as u32) {
+            Ok(self.offset + offset)
+        } else {
+            Err(Error::OutOfBounds)
+        }
+    }
+}
+
+/// Error returned by partitions
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Error<E> {
+    /// The operation would have accessed storage outside of the partition
+    OutOfBounds,
+    /// The underlying storage device returned an error
+    Storage(E),
+}
+
+impl<E> storage::Error for Error<E>
+where
+    E: storage::Error,
+{
+    fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::OutOfBounds => ErrorKind::OutOfBounds,
+            Error::Storage(ref e) => e.kind(),
+        }
+    }
+}
+
+impl<E> fmt::Display for Error<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::OutOfBounds => f.write_str("the access was out of the bounds of the partition"),
+            Error::Storage(ref e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> ::std::error::Error for Error<E> where E: fmt::Debug + fmt::Display {}
+
+/// A storage device restricted to a [`Region`](struct.Region.html)
+///
+/// Offsets passed to the storage traits implemented by a partition are relative to the start of
+/// its region.
+pub struct Partition<'a, S>
+where
+    S: 'a,
+{
+    storage: &'a mut S,
+    region: Region,
+}
+
+impl<'a, S> Partition<'a, S> {
+    /// Returns the region this partition gives access to
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Returns the partition of `size` bytes starting at `offset` *relative to the start of this
+    /// partition*, or `None` if it doesn't fit in this partition
+    pub fn sub(&mut self, offset: u32, size: u32) -> Option<Partition<'_, S>> {
+        let region = self.region.sub(offset, size)?;
+        Some(region.partition(&mut *self.storage))
+    }
+}
+
+impl<'a, S> ErrorType for Partition<'a, S>
+where
+    S: ErrorType,
+{
+    type Error = Error<S::Error>;
+}
+
+impl<'a, S> ReadNorFlash for Partition<'a, S>
+where
+    S: ReadNorFlash,
+{
+    const READ_SIZE: usize = S::READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let offset = self.region.translate(offset, bytes.len())?;
+        self.storage.read(offset, bytes).map_err(Error::Storage)
+    }
+
+    fn capacity(&self) -> usize {
+        self.region.size as usize
+    }
+}
+
+impl<'a, S> NorFlash for Partition<'a, S>
+where
+    S: NorFlash,
+{
+    const WRITE_SIZE: usize = S::WRITE_SIZE;
+
+    const ERASE_SIZE: usize = S::ERASE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        if from > to {
+            return Err(Error::OutOfBounds);
+        }
+        let len = to - from;
+        let from = self.region.translate(from, len as usize)?;
+        self.storage.erase(from, from + len).map_err(Error::Storage)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        let offset = self.region.translate(offset, bytes.len())?;
+        self.storage.write(offset, bytes).map_err(Error::Storage)
+    }
+}
+
+impl<'a, S> MultiwriteNorFlash for Partition<'a, S> where S: MultiwriteNorFlash {}
+
+impl<'a, S> Eeprom for Partition<'a, S>
+where
+    S: Eeprom,
+{
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        let offset = self.region.translate(offset, bytes.len())?;
+        self.storage.write(offset, bytes).map_err(Error::Storage)
+    }
+}
@@ -0,0 +1,230 @@
+//! Units of time and frequency
+//!
+//! The timing traits leave the unit of their `Time` associated type to the implementation, so a
+//! driver can't say "1 ms" portably unless the HALs agree on one. These newtypes are that
+//! agreement:
+//!
+//! - HALs SHOULD accept [`MicroSeconds`] and [`MilliSeconds`] as the `Time` of their timers
+//!   ([`CountDown`]), watchdogs and input captures ([`Capture`]), and [`Hertz`] as the `Time` of
+//!   their PWM peripherals ([`Pwm`]). As these traits take `T: Into<Self::Time>`, the simplest way
+//!   to do so is to use `MicroSeconds` or `Hertz` as `Time`: `MilliSeconds` converts into
+//!   `MicroSeconds`.
+//! - Drivers build the values with the const constructors, e.g. `MilliSeconds::from_s(2)`, or with
+//!   the [`U32Ext`] methods, e.g. `2.s()` or `400.khz()`. `U32Ext` is not part of the prelude, as
+//!   many HALs have a trait of that name with the same methods: import it explicitly.
+//!
+//! All the values are `u32`s, so a `MicroSeconds` is at most 4_294_967_295 µs, about 71.6 minutes.
+//! The constructors, the `U32Ext` methods and the conversion of `MilliSeconds` into
+//! `MicroSeconds` saturate at `u32::MAX` if the result doesn't fit;
+//! [`MicroSeconds::checked_from_ms`] reports the overflow instead. With the `"fugit"` feature,
+//! these types also convert into the `Duration` and `Rate` of the [`fugit`](../fugit/index.html)
+//! module.
+//!
+//! [`MicroSeconds`]: struct.MicroSeconds.html
+//! [`MilliSeconds`]: struct.MilliSeconds.html
+//! [`Hertz`]: struct.Hertz.html
+//! [`CountDown`]: ../timer/trait.CountDown.html
+//! [`Capture`]: ../trait.Capture.html
+//! [`Pwm`]: ../trait.Pwm.html
+//! [`U32Ext`]: trait.U32Ext.html
+//! [`MicroSeconds::checked_from_ms`]: struct.MicroSeconds.html#method.checked_from_ms
+//!
+//! # Examples
+//!
+//! A driver that blinks an LED with any timer
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//! #[macro_use(block)]
+//! extern crate nb;
+//!
+//! use hal::digital::OutputPin;
+//! use hal::time::{MicroSeconds, U32Ext};
+//! use hal::timer::CountDown;
+//!
+//! fn blink<L, T>(led: &mut L, timer: &mut T, times: u32)
+//! where
+//!     L: OutputPin,
+//!     T: CountDown<Time = MicroSeconds>,
+//! {
+//!     for _ in 0..times {
+//!         led.set_high();
+//!         timer.start(250.ms());
+//!         block!(timer.wait()).unwrap();
+//!         led.set_low();
+//!         timer.start(250.ms());
+//!         block!(timer.wait()).unwrap();
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let (mut led, mut timer): (Pc13, Timer2) = {
+//!         // ..
+//! #       (Pc13, Timer2(MicroSeconds(0)))
+//!     };
+//!
+//!     blink(&mut led, &mut timer, 3);
+//! #   assert_eq!(timer.0, MicroSeconds(250_000));
+//! }
+//!
+//! # extern crate void;
+//! # use void::Void;
+//! # struct Pc13;
+//! # impl OutputPin for Pc13 { fn set_low(&mut self) {} fn set_high(&mut self) {} }
+//! # struct Timer2(MicroSeconds);
+//! # impl CountDown for Timer2 {
+//! #     type Time = MicroSeconds;
+//! #     fn start<T>(&mut self, count: T) where T: Into<MicroSeconds> { self.0 = count.into() }
+//! #     fn wait(&mut self) -> nb::Result<(), Void> { Ok(()) }
+//! # }
+//! ```
+//!
+//! Values that don't fit saturate
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::time::{Hertz, MicroSeconds, MilliSeconds, U32Ext};
+//!
+//! fn main() {
+//!     assert_eq!(Hertz::from_mhz(5_000), Hertz(u32::MAX));
+//!     assert_eq!(5_000_000.khz(), Hertz(u32::MAX));
+//!     assert_eq!(MicroSeconds::from_s(5_000), MicroSeconds(u32::MAX));
+//!     assert_eq!(MilliSeconds::from_s(5_000_000), MilliSeconds(u32::MAX));
+//!     assert_eq!(MicroSeconds::from(5_000_000.ms()), MicroSeconds(u32::MAX));
+//!     assert_eq!(MicroSeconds::checked_from_ms(5_000_000), None);
+//!
+//!     // the largest values that fit
+//!     assert_eq!(Hertz::from_mhz(4_294), Hertz(4_294_000_000));
+//!     assert_eq!(MicroSeconds::from_ms(4_294_967), MicroSeconds(4_294_967_000));
+//! }
+//! ```
+
+/// Frequency, in hertz
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Hertz(pub u32);
+
+impl Hertz {
+    /// Returns a frequency of `khz` kilohertz
+    pub const fn from_khz(khz: u32) -> Self {
+        Hertz(khz.saturating_mul(1_000))
+    }
+
+    /// Returns a frequency of `mhz` megahertz
+    pub const fn from_mhz(mhz: u32) -> Self {
+        Hertz(mhz.saturating_mul(1_000_000))
+    }
+
+    /// Returns the period of one cycle, rounded down to the microsecond
+    ///
+    /// # Panics
+    ///
+    /// If the frequency is zero
+    pub fn period(self) -> MicroSeconds {
+        MicroSeconds(1_000_000 / self.0)
+    }
+}
+
+/// Duration, in microseconds
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct MicroSeconds(pub u32);
+
+impl MicroSeconds {
+    /// Returns a duration of `ms` milliseconds
+    pub const fn from_ms(ms: u32) -> Self {
+        MicroSeconds(ms.saturating_mul(1_000))
+    }
+
+    /// Returns a duration of `s` seconds
+    pub const fn from_s(s: u32) -> Self {
+        MicroSeconds(s.saturating_mul(1_000_000))
+    }
+
+    /// Returns a duration of `ms` milliseconds, or `None` if it doesn't fit, i.e. if `ms` is
+    /// greater than 4_294_967
+    pub fn checked_from_ms(ms: u32) -> Option<Self> {
+        ms.checked_mul(1_000).map(MicroSeconds)
+    }
+
+    /// Returns the frequency whose period is this duration, rounded down to the hertz
+    ///
+    /// # Panics
+    ///
+    /// If the duration is zero
+    pub fn frequency(self) -> Hertz {
+        Hertz(1_000_000 / self.0)
+    }
+}
+
+/// Saturates to 4_294_967_295 µs, about 71.6 minutes, above 4_294_967 ms; use
+/// [`MicroSeconds::checked_from_ms`](struct.MicroSeconds.html#method.checked_from_ms) to detect
+/// the overflow instead
+impl From<MilliSeconds> for MicroSeconds {
+    fn from(ms: MilliSeconds) -> Self {
+        MicroSeconds::from_ms(ms.0)
+    }
+}
+
+/// Duration, in milliseconds
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct MilliSeconds(pub u32);
+
+impl MilliSeconds {
+    /// Returns a duration of `s` seconds
+    pub const fn from_s(s: u32) -> Self {
+        MilliSeconds(s.saturating_mul(1_000))
+    }
+}
+
+/// Extension trait to build the units of this module from integer literals, e.g. `10.ms()`
+pub trait U32Ext {
+    /// Returns a frequency of `self` hertz
+    fn hz(self) -> Hertz;
+
+    /// Returns a frequency of `self` kilohertz
+    fn khz(self) -> Hertz;
+
+    /// Returns a frequency of `self` megahertz
+    fn mhz(self) -> Hertz;
+
+    /// Returns a duration of `self` microseconds
+    fn us(self) -> MicroSeconds;
+
+    /// Returns a duration of `self` milliseconds
+    fn ms(self) -> MilliSeconds;
+
+    /// Returns a duration of `self` seconds
+    fn s(self) -> MilliSeconds;
+}
+
+impl U32Ext for u32 {
+    fn hz(self) -> Hertz {
+        Hertz(self)
+    }
+
+    fn khz(self) -> Hertz {
+        Hertz::from_khz(self)
+    }
+
+    fn mhz(self) -> Hertz {
+        Hertz::from_mhz(self)
+    }
+
+    fn us(self) -> MicroSeconds {
+        MicroSeconds(self)
+    }
+
+    fn ms(self) -> MilliSeconds {
+        MilliSeconds(self)
+    }
+
+    fn s(self) -> MilliSeconds {
+        MilliSeconds::from_s(self)
+    }
+}
@@ -1,5 +1,8 @@
 //! Timers
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
 use nb;
 use void::Void;
 
@@ -9,6 +12,7 @@ use void::Void;
 ///
 /// - `self.start(count); block!(self.wait());` MUST block for AT LEAST the time specified by
 /// `count`.
+/// - Calling `start` while a count down is in progress MUST restart it with the new `count`.
 ///
 /// *Note* that the implementer doesn't necessarily have to be a *downcounting* timer; it could also
 /// be an *upcounting* timer as long as the above contract is upheld.
@@ -42,9 +46,7 @@ use void::Void;
 ///
 /// # extern crate void;
 /// # use void::Void;
-/// # struct Seconds(u32);
-/// # trait U32Ext { fn s(self) -> Seconds; }
-/// # impl U32Ext for u32 { fn s(self) -> Seconds { Seconds(self) } }
+/// # use hal::time::{MilliSeconds, U32Ext};
 /// # struct Led;
 /// # impl Led {
 /// #     pub fn off(&mut self) {}
@@ -52,13 +54,16 @@ use void::Void;
 /// # }
 /// # struct Timer6;
 /// # impl hal::timer::CountDown for Timer6 {
-/// #     type Time = Seconds;
-/// #     fn start<T>(&mut self, _: T) where T: Into<Seconds> {}
+/// #     type Time = MilliSeconds;
+/// #     fn start<T>(&mut self, _: T) where T: Into<MilliSeconds> {}
 /// #     fn wait(&mut self) -> ::nb::Result<(), Void> { Ok(()) }
 /// # }
 /// ```
 pub trait CountDown {
     /// The unit of time used by this timer
+    ///
+    /// It SHOULD be `time::MicroSeconds`, or a type that `time::MicroSeconds` and
+    /// `time::MilliSeconds` convert into, so that drivers can pass durations portably.
     type Time;
 
     /// Starts a new count down
@@ -71,11 +76,146 @@ pub trait CountDown {
     /// # Contract
     ///
     /// - If `Self: Periodic`, the timer will start a new count down right after the last one
-    /// finishes.
-    /// - Otherwise the behavior of calling `wait` after the last call returned `Ok` is UNSPECIFIED.
-    /// Implementers are suggested to panic on this scenario to signal a programmer error.
+    ///   finishes. If several count downs finished since the last call, `wait` MUST return `Ok`
+    ///   only once, i.e. missed periods are not queued.
+    /// - Otherwise, once a call returned `Ok`, the timer MUST stop and the following calls MUST
+    ///   keep returning `Ok`, without wrapping around, until the next `start`.
+    /// - The behavior of calling `wait` on a timer that has never been started, or that has been
+    ///   [canceled](trait.Cancel.html), is UNSPECIFIED. Implementers are suggested to panic on
+    ///   this scenario to signal a programmer error.
     fn wait(&mut self) -> nb::Result<(), Void>;
 }
 
+#[cfg(feature = "alloc")]
+impl<T> CountDown for Box<T>
+where
+    T: CountDown + ?Sized,
+{
+    type Time = T::Time;
+
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<Self::Time>,
+    {
+        (**self).start(count)
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        (**self).wait()
+    }
+}
+
+impl<T> CountDown for &mut T
+where
+    T: CountDown + ?Sized,
+{
+    type Time = T::Time;
+
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<Self::Time>,
+    {
+        (**self).start(count)
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        (**self).wait()
+    }
+}
+
 /// Marker trait that indicates that a timer is periodic
 pub trait Periodic {}
+
+#[cfg(feature = "alloc")]
+impl<T: Periodic + ?Sized> Periodic for Box<T> {}
+
+impl<T: Periodic + ?Sized> Periodic for &mut T {}
+
+/// A count down timer that can be canceled
+///
+/// *This trait is available if embedded-hal is built with the `"timer-unproven"` feature.*
+///
+/// # Examples
+///
+/// Aborting a timeout once the awaited data has arrived
+///
+/// ```
+/// extern crate embedded_hal as hal;
+/// #[macro_use(block)]
+/// extern crate nb;
+///
+/// use hal::timer::{Cancel, CountDown};
+///
+/// fn main() {
+///     let mut timer: Timer6 = {
+///         // ..
+/// #       Timer6 { running: false }
+///     };
+///
+///     timer.start(1_000_u32);
+///     // .. the data arrives before the timeout
+///     timer.cancel().unwrap();
+///
+///     // there's nothing left to cancel
+///     assert_eq!(timer.cancel(), Err(NotRunning));
+///
+///     timer.start(1_000_u32);
+///     block!(timer.wait()).unwrap();
+///     // a non-periodic count down can't be canceled once it has finished
+///     assert_eq!(timer.cancel(), Err(NotRunning));
+/// }
+///
+/// # extern crate void;
+/// # use void::Void;
+/// # #[derive(Debug, PartialEq)]
+/// # struct NotRunning;
+/// # struct Timer6 { running: bool }
+/// # impl CountDown for Timer6 {
+/// #     type Time = u32;
+/// #     fn start<T>(&mut self, _: T) where T: Into<u32> { self.running = true }
+/// #     fn wait(&mut self) -> nb::Result<(), Void> { self.running = false; Ok(()) }
+/// # }
+/// # impl Cancel for Timer6 {
+/// #     type Error = NotRunning;
+/// #     fn cancel(&mut self) -> Result<(), NotRunning> {
+/// #         if std::mem::replace(&mut self.running, false) { Ok(()) } else { Err(NotRunning) }
+/// #     }
+/// # }
+/// ```
+#[cfg(feature = "timer-unproven")]
+pub trait Cancel: CountDown {
+    /// Error returned when the count down can't be canceled
+    type Error;
+
+    /// Stops the count down
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the count down has never been started or has already been
+    /// canceled, and, unless `Self: Periodic`, if it has already finished.
+    fn cancel(&mut self) -> Result<(), Self::Error>;
+}
+
+#[cfg(all(feature = "alloc", feature = "timer-unproven"))]
+impl<T> Cancel for Box<T>
+where
+    T: Cancel + ?Sized,
+{
+    type Error = T::Error;
+
+    fn cancel(&mut self) -> Result<(), T::Error> {
+        (**self).cancel()
+    }
+}
+
+#[cfg(feature = "timer-unproven")]
+impl<T> Cancel for &mut T
+where
+    T: Cancel + ?Sized,
+{
+    type Error = T::Error;
+
+    fn cancel(&mut self) -> Result<(), T::Error> {
+        (**self).cancel()
+    }
+}
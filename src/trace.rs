@@ -0,0 +1,399 @@
+//! Tracing of bus transactions
+//!
+//! *This module is available if embedded-hal is built with the `"trace"` feature.*
+//!
+//! [`TracedSpi`] and [`TracedI2c`] wrap an SPI or I2C bus, implement the same blocking traits,
+//! and report each transaction, with its outcome, to a [`Sink`], which makes protocol debugging
+//! possible without a logic analyzer and without touching the driver. The sink can be:
+//!
+//! - a closure that takes an `&Event`
+//! - a [`FmtSink`], which prints the events to a `core::fmt::Write`r, e.g. a serial port
+//! - a [`DefmtSink`] (with the `"defmt"` feature), which logs them with `defmt`
+//!
+//! [`TracedSpi`]: struct.TracedSpi.html
+//! [`TracedI2c`]: struct.TracedI2c.html
+//! [`Sink`]: trait.Sink.html
+//! [`FmtSink`]: struct.FmtSink.html
+//! [`DefmtSink`]: struct.DefmtSink.html
+//!
+//! # Examples
+//!
+//! Printing the transactions of a driver
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::blocking::i2c::WriteRead;
+//! use hal::trace::{FmtSink, TracedI2c};
+//!
+//! fn main() {
+//!     let i2c: I2c1 = {
+//!         // ..
+//! #       I2c1
+//!     };
+//!
+//!     let mut i2c = TracedI2c::new(i2c, FmtSink::new(String::new()));
+//!     let mut id = [0];
+//!     i2c.write_read(0x76, &[0xd0], &mut id).unwrap();
+//!
+//!     let (_i2c, sink) = i2c.into_parts();
+//!     assert_eq!(sink.into_inner(), "i2c write_read 0x76 [d0]: [60]\n");
+//! }
+//!
+//! # struct I2c1;
+//! # impl hal::i2c::ErrorType for I2c1 { type Error = hal::i2c::ErrorKind; }
+//! # impl WriteRead for I2c1 {
+//! #     fn write_read(&mut self, _: u8, _: &[u8], id: &mut [u8]) -> Result<(), Self::Error> {
+//! #         id[0] = 0x60;
+//! #         Ok(())
+//! #     }
+//! # }
+//! ```
+
+use core::fmt;
+
+use blocking;
+use i2c::{self, Error as _};
+use spi::{self, Error as _};
+
+/// Transaction reported by a traced bus
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum Event<'a> {
+    /// A blocking SPI write of `words`
+    SpiWrite {
+        /// The words sent
+        words: &'a [u8],
+        /// The outcome of the write
+        result: Result<(), spi::ErrorKind>,
+    },
+    /// The start of a blocking SPI transfer of `words`
+    ///
+    /// As the words are transferred in place, it's followed by a `SpiTransferred` event with the
+    /// words received.
+    SpiTransfer {
+        /// The words about to be sent
+        words: &'a [u8],
+    },
+    /// The end of a blocking SPI transfer
+    SpiTransferred {
+        /// The words received, or the error
+        result: Result<&'a [u8], spi::ErrorKind>,
+    },
+    /// A blocking I2C read from `address`
+    I2cRead {
+        /// The address of the slave
+        address: u8,
+        /// The bytes read, or the error
+        result: Result<&'a [u8], i2c::ErrorKind>,
+    },
+    /// A blocking I2C write of `bytes` to `address`
+    I2cWrite {
+        /// The address of the slave
+        address: u8,
+        /// The bytes written
+        bytes: &'a [u8],
+        /// The outcome of the write
+        result: Result<(), i2c::ErrorKind>,
+    },
+    /// A blocking I2C write of `bytes` to `address`, followed by a read
+    I2cWriteRead {
+        /// The address of the slave
+        address: u8,
+        /// The bytes written
+        bytes: &'a [u8],
+        /// The bytes read, or the error
+        result: Result<&'a [u8], i2c::ErrorKind>,
+    },
+}
+
+/// One line per event, e.g. `i2c write 0x3c [00, af]: ok`
+impl<'a> fmt::Display for Event<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::SpiWrite { words, result } => {
+                write!(f, "spi write {:02x?}: ", words)?;
+                outcome(f, result)
+            }
+            Event::SpiTransfer { words } => write!(f, "spi transfer {:02x?}", words),
+            Event::SpiTransferred { result } => {
+                f.write_str("spi transferred: ")?;
+                outcome(f, result)
+            }
+            Event::I2cRead { address, result } => {
+                write!(f, "i2c read {:#04x}: ", address)?;
+                outcome(f, result)
+            }
+            Event::I2cWrite {
+                address,
+                bytes,
+                result,
+            } => {
+                write!(f, "i2c write {:#04x} {:02x?}: ", address, bytes)?;
+                outcome(f, result)
+            }
+            Event::I2cWriteRead {
+                address,
+                bytes,
+                result,
+            } => {
+                write!(f, "i2c write_read {:#04x} {:02x?}: ", address, bytes)?;
+                outcome(f, result)
+            }
+        }
+    }
+}
+
+fn outcome<K>(f: &mut fmt::Formatter, result: Result<impl Received, K>) -> fmt::Result
+where
+    K: fmt::Display,
+{
+    match result {
+        Ok(received) => received.fmt(f),
+        Err(kind) => write!(f, "error: {}", kind),
+    }
+}
+
+/// What an operation returns when it succeeds
+trait Received {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl Received for () {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ok")
+    }
+}
+
+impl Received for &[u8] {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x?}", self)
+    }
+}
+
+/// Destination of the events of a traced bus
+pub trait Sink {
+    /// Records `event`
+    fn record(&mut self, event: &Event);
+}
+
+impl<F> Sink for F
+where
+    F: FnMut(&Event),
+{
+    fn record(&mut self, event: &Event) {
+        self(event)
+    }
+}
+
+/// Sink that prints each event, on a line of its own, to a `core::fmt::Write`r
+///
+/// Errors of the writer are ignored, as tracing must not change the outcome of the transactions.
+#[derive(Debug)]
+pub struct FmtSink<W> {
+    writer: W,
+}
+
+impl<W> FmtSink<W>
+where
+    W: fmt::Write,
+{
+    /// Creates a sink that prints to `writer`
+    pub fn new(writer: W) -> Self {
+        FmtSink { writer }
+    }
+
+    /// Returns the writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> Sink for FmtSink<W>
+where
+    W: fmt::Write,
+{
+    fn record(&mut self, event: &Event) {
+        writeln!(self.writer, "{}", event).ok();
+    }
+}
+
+/// Sink that logs each event at the debug level with `defmt`
+///
+/// *This type is available if embedded-hal is built with the `"defmt"` feature.*
+#[cfg(feature = "defmt")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefmtSink;
+
+#[cfg(feature = "defmt")]
+impl Sink for DefmtSink {
+    fn record(&mut self, event: &Event) {
+        ::defmt::debug!("{}", event);
+    }
+}
+
+/// SPI bus that reports its transactions to a sink
+///
+/// It implements the blocking SPI traits, for 8-bit words, that the wrapped bus implements, with
+/// its error type.
+#[derive(Debug)]
+pub struct TracedSpi<T, L> {
+    inner: T,
+    sink: L,
+}
+
+impl<T, L> TracedSpi<T, L>
+where
+    L: Sink,
+{
+    /// Wraps `inner`, reporting its transactions to `sink`
+    pub fn new(inner: T, sink: L) -> Self {
+        TracedSpi { inner, sink }
+    }
+
+    /// Returns a mutable reference to the wrapped bus, whose transactions aren't traced
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns a mutable reference to the sink
+    pub fn sink_mut(&mut self) -> &mut L {
+        &mut self.sink
+    }
+
+    /// Returns the wrapped bus and the sink
+    pub fn into_parts(self) -> (T, L) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<T, L> spi::ErrorType for TracedSpi<T, L>
+where
+    T: spi::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T, L> blocking::spi::Transfer<u8> for TracedSpi<T, L>
+where
+    T: blocking::spi::Transfer<u8>,
+    L: Sink,
+{
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], T::Error> {
+        self.sink.record(&Event::SpiTransfer { words });
+        let result = self.inner.transfer(words);
+        self.sink.record(&Event::SpiTransferred {
+            result: result.as_ref().map(|received| *received).map_err(|e| e.kind()),
+        });
+        result
+    }
+}
+
+impl<T, L> blocking::spi::Write<u8> for TracedSpi<T, L>
+where
+    T: blocking::spi::Write<u8>,
+    L: Sink,
+{
+    fn write(&mut self, words: &[u8]) -> Result<(), T::Error> {
+        let result = self.inner.write(words);
+        self.sink.record(&Event::SpiWrite {
+            words,
+            result: result.as_ref().map(|_| ()).map_err(|e| e.kind()),
+        });
+        result
+    }
+}
+
+/// I2C bus that reports its transactions to a sink
+///
+/// It implements the blocking I2C traits that the wrapped bus implements, with its error type.
+#[derive(Debug)]
+pub struct TracedI2c<T, L> {
+    inner: T,
+    sink: L,
+}
+
+impl<T, L> TracedI2c<T, L>
+where
+    L: Sink,
+{
+    /// Wraps `inner`, reporting its transactions to `sink`
+    pub fn new(inner: T, sink: L) -> Self {
+        TracedI2c { inner, sink }
+    }
+
+    /// Returns a mutable reference to the wrapped bus, whose transactions aren't traced
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns a mutable reference to the sink
+    pub fn sink_mut(&mut self) -> &mut L {
+        &mut self.sink
+    }
+
+    /// Returns the wrapped bus and the sink
+    pub fn into_parts(self) -> (T, L) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<T, L> i2c::ErrorType for TracedI2c<T, L>
+where
+    T: i2c::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T, L> blocking::i2c::Read for TracedI2c<T, L>
+where
+    T: blocking::i2c::Read,
+    L: Sink,
+{
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), T::Error> {
+        let result = self.inner.read(address, buffer);
+        self.sink.record(&Event::I2cRead {
+            address,
+            result: match result {
+                Ok(()) => Ok(buffer),
+                Err(ref e) => Err(e.kind()),
+            },
+        });
+        result
+    }
+}
+
+impl<T, L> blocking::i2c::Write for TracedI2c<T, L>
+where
+    T: blocking::i2c::Write,
+    L: Sink,
+{
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), T::Error> {
+        let result = self.inner.write(address, bytes);
+        self.sink.record(&Event::I2cWrite {
+            address,
+            bytes,
+            result: result.as_ref().map(|_| ()).map_err(|e| e.kind()),
+        });
+        result
+    }
+}
+
+impl<T, L> blocking::i2c::WriteRead for TracedI2c<T, L>
+where
+    T: blocking::i2c::WriteRead,
+    L: Sink,
+{
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), T::Error> {
+        let result = self.inner.write_read(address, bytes, buffer);
+        self.sink.record(&Event::I2cWriteRead {
+            address,
+            bytes,
+            result: match result {
+                Ok(()) => Ok(buffer),
+                Err(ref e) => Err(e.kind()),
+            },
+        });
+        result
+    }
+}
@@ -0,0 +1,218 @@
+//! Building blocks for type-state configuration
+//!
+//! *This module is available if embedded-hal is built with the `"typestate"` feature.*
+//!
+//! Most HALs encode the configuration of their peripherals in the type system, e.g. a pin
+//! configured as a push-pull output is a `PA5<Output<PushPull>>`, so that a misconfigured
+//! peripheral is a compile time error. This module provides the marker types and the conversion
+//! traits of that pattern, so that HALs don't each define their own incompatible copies, and
+//! drivers can name the configuration they need:
+//!
+//! - pin modes: [`Input`], with [`Floating`], [`PullUp`] or [`PullDown`]; [`Output`], with
+//!   [`PushPull`] or [`OpenDrain`]; and [`Analog`]. [`IntoMode`] reconfigures a pin.
+//! - peripheral states: [`Enabled`] and [`Disabled`]. [`Enable`] and [`Disable`] switch between
+//!   them.
+//!
+//! [`Input`]: struct.Input.html
+//! [`Floating`]: struct.Floating.html
+//! [`PullUp`]: struct.PullUp.html
+//! [`PullDown`]: struct.PullDown.html
+//! [`Output`]: struct.Output.html
+//! [`PushPull`]: struct.PushPull.html
+//! [`OpenDrain`]: struct.OpenDrain.html
+//! [`Analog`]: struct.Analog.html
+//! [`IntoMode`]: trait.IntoMode.html
+//! [`Enabled`]: struct.Enabled.html
+//! [`Disabled`]: struct.Disabled.html
+//! [`Enable`]: trait.Enable.html
+//! [`Disable`]: trait.Disable.html
+//!
+//! # Examples
+//!
+//! A HAL pin, and a driver that configures it
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use std::marker::PhantomData;
+//!
+//! use hal::digital::OutputPin;
+//! use hal::typestate::{Floating, Input, IntoMode, Output, PinMode, PushPull};
+//!
+//! /// Pin PA5, in mode `MODE`
+//! pub struct PA5<MODE> {
+//!     _mode: PhantomData<MODE>,
+//! }
+//!
+//! impl<MODE, NEW> IntoMode<NEW> for PA5<MODE>
+//! where
+//!     NEW: PinMode,
+//! {
+//!     type Output = PA5<NEW>;
+//!
+//!     fn into_mode(self) -> PA5<NEW> {
+//!         // .. write the mode registers ..
+//!         PA5 { _mode: PhantomData }
+//!     }
+//! }
+//!
+//! impl OutputPin for PA5<Output<PushPull>> {
+//!     fn set_low(&mut self) {}
+//!     fn set_high(&mut self) {}
+//! }
+//!
+//! /// Driver of a LED
+//! pub struct Led<P> {
+//!     pin: P,
+//! }
+//!
+//! impl<P> Led<P>
+//! where
+//!     P: OutputPin,
+//! {
+//!     /// Takes a pin in any mode, and configures it as a push-pull output
+//!     pub fn new<Q>(pin: Q) -> Self
+//!     where
+//!         Q: IntoMode<Output<PushPull>, Output = P>,
+//!     {
+//!         let mut pin = pin.into_mode();
+//!         pin.set_low();
+//!         Led { pin }
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let pa5: PA5<Input<Floating>> = {
+//!         // ..
+//! #       PA5 { _mode: PhantomData }
+//!     };
+//!
+//!     let _led = Led::new(pa5);
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+/// Input mode, with pull resistors `MODE`
+#[derive(Debug)]
+pub struct Input<MODE> {
+    _mode: PhantomData<MODE>,
+}
+
+/// Floating input: no pull resistor
+#[derive(Debug)]
+pub struct Floating;
+
+/// Pulled up input
+#[derive(Debug)]
+pub struct PullUp;
+
+/// Pulled down input
+#[derive(Debug)]
+pub struct PullDown;
+
+/// Output mode, with driver `MODE`
+#[derive(Debug)]
+pub struct Output<MODE> {
+    _mode: PhantomData<MODE>,
+}
+
+/// Push-pull output: driven both high and low
+#[derive(Debug)]
+pub struct PushPull;
+
+/// Open-drain output: driven low, and left floating when high
+#[derive(Debug)]
+pub struct OpenDrain;
+
+/// Analog mode, e.g. for an ADC or DAC channel
+#[derive(Debug)]
+pub struct Analog;
+
+/// Pull resistors of an input
+///
+/// It's implemented for [`Floating`], [`PullUp`] and [`PullDown`].
+///
+/// [`Floating`]: struct.Floating.html
+/// [`PullUp`]: struct.PullUp.html
+/// [`PullDown`]: struct.PullDown.html
+pub trait InputMode {}
+
+impl InputMode for Floating {}
+impl InputMode for PullUp {}
+impl InputMode for PullDown {}
+
+/// Driver of an output
+///
+/// It's implemented for [`PushPull`] and [`OpenDrain`].
+///
+/// [`PushPull`]: struct.PushPull.html
+/// [`OpenDrain`]: struct.OpenDrain.html
+pub trait OutputMode {}
+
+impl OutputMode for PushPull {}
+impl OutputMode for OpenDrain {}
+
+/// Mode of a pin
+///
+/// It's implemented for [`Input`], [`Output`] and [`Analog`].
+///
+/// [`Input`]: struct.Input.html
+/// [`Output`]: struct.Output.html
+/// [`Analog`]: struct.Analog.html
+pub trait PinMode {}
+
+impl<MODE> PinMode for Input<MODE> where MODE: InputMode {}
+impl<MODE> PinMode for Output<MODE> where MODE: OutputMode {}
+impl PinMode for Analog {}
+
+/// Enabled peripheral
+#[derive(Debug)]
+pub struct Enabled;
+
+/// Disabled peripheral, e.g. with its clock gated
+#[derive(Debug)]
+pub struct Disabled;
+
+/// State of a peripheral
+///
+/// It's implemented for [`Enabled`] and [`Disabled`].
+///
+/// [`Enabled`]: struct.Enabled.html
+/// [`Disabled`]: struct.Disabled.html
+pub trait State {}
+
+impl State for Enabled {}
+impl State for Disabled {}
+
+/// Reconfiguration of a pin into mode `MODE`
+///
+/// See the [module documentation](index.html) for an example.
+pub trait IntoMode<MODE>
+where
+    MODE: PinMode,
+{
+    /// The pin, in mode `MODE`
+    type Output;
+
+    /// Configures the pin in mode `MODE`
+    fn into_mode(self) -> Self::Output;
+}
+
+/// Enabling of a peripheral
+pub trait Enable {
+    /// The peripheral, enabled
+    type Enabled;
+
+    /// Enables the peripheral, e.g. ungates its clock
+    fn enable(self) -> Self::Enabled;
+}
+
+/// Disabling of a peripheral
+pub trait Disable {
+    /// The peripheral, disabled
+    type Disabled;
+
+    /// Disables the peripheral, e.g. gates its clock
+    fn disable(self) -> Self::Disabled;
+}
@@ -0,0 +1,159 @@
+//! Watchdog timers
+//!
+//! *This module is available if embedded-hal is built with the `"watchdog"` feature.*
+//!
+//! A watchdog resets the device unless it's fed regularly. [`WatchdogEnable`] starts it with a
+//! timeout period, [`Watchdog`] feeds it and [`WatchdogDisable`] stops it, on the devices where it
+//! can be stopped once started.
+//!
+//! [`Watchdog`]: trait.Watchdog.html
+//! [`WatchdogEnable`]: trait.WatchdogEnable.html
+//! [`WatchdogDisable`]: trait.WatchdogDisable.html
+//!
+//! # Examples
+//!
+//! A main loop that only feeds the watchdog while all the tasks make progress
+//!
+//! ```
+//! extern crate embedded_hal as hal;
+//!
+//! use hal::watchdog::{Watchdog, WatchdogEnable};
+//!
+//! fn run<W>(watchdog: &mut W, tasks: &mut [&mut dyn FnMut() -> bool])
+//! where
+//!     W: Watchdog + WatchdogEnable<Time = u32>,
+//! {
+//!     // reset the device if a task hangs for more than 500 ms
+//!     watchdog.start(500_000_u32);
+//!
+//!     while tasks.iter_mut().all(|task| task()) {
+//!         watchdog.feed();
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut iwdg: Iwdg = {
+//!         // ..
+//! #       Iwdg { timeout: 0, feeds: 0 }
+//!     };
+//!
+//!     let mut polls = 0;
+//!     let mut poll_sensor = || {
+//!         polls += 1;
+//!         polls < 3
+//!     };
+//!     run(&mut iwdg, &mut [&mut poll_sensor]);
+//! #   assert_eq!((iwdg.timeout, iwdg.feeds), (500_000, 2));
+//! }
+//!
+//! # struct Iwdg { timeout: u32, feeds: u32 }
+//! # impl Watchdog for Iwdg { fn feed(&mut self) { self.feeds += 1 } }
+//! # impl WatchdogEnable for Iwdg {
+//! #     type Time = u32;
+//! #     fn start<T>(&mut self, period: T) where T: Into<u32> { self.timeout = period.into() }
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// Feeding a watchdog
+pub trait Watchdog {
+    /// Feeds the watchdog, restarting its timeout period
+    ///
+    /// It must be called before the period set with `WatchdogEnable::start` elapses, lest the
+    /// watchdog resets the device.
+    fn feed(&mut self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Watchdog for Box<T>
+where
+    T: Watchdog + ?Sized,
+{
+    fn feed(&mut self) {
+        (**self).feed()
+    }
+}
+
+impl<T> Watchdog for &mut T
+where
+    T: Watchdog + ?Sized,
+{
+    fn feed(&mut self) {
+        (**self).feed()
+    }
+}
+
+/// Enabling a watchdog
+pub trait WatchdogEnable {
+    /// The unit of time used by this watchdog
+    ///
+    /// It SHOULD be `time::MicroSeconds`, or a type that `time::MicroSeconds` and
+    /// `time::MilliSeconds` convert into, so that drivers can pass durations portably.
+    type Time;
+
+    /// Starts the watchdog with a timeout of `period`
+    ///
+    /// If the watchdog is already running, its timeout is changed to `period`, on the devices
+    /// that allow it.
+    fn start<T>(&mut self, period: T)
+    where
+        T: Into<Self::Time>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WatchdogEnable for Box<T>
+where
+    T: WatchdogEnable + ?Sized,
+{
+    type Time = T::Time;
+
+    fn start<P>(&mut self, period: P)
+    where
+        P: Into<Self::Time>,
+    {
+        (**self).start(period)
+    }
+}
+
+impl<T> WatchdogEnable for &mut T
+where
+    T: WatchdogEnable + ?Sized,
+{
+    type Time = T::Time;
+
+    fn start<P>(&mut self, period: P)
+    where
+        P: Into<Self::Time>,
+    {
+        (**self).start(period)
+    }
+}
+
+/// Disabling a watchdog
+///
+/// It's only implemented by the watchdogs that can be stopped once started.
+pub trait WatchdogDisable {
+    /// Stops the watchdog
+    fn disable(&mut self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WatchdogDisable for Box<T>
+where
+    T: WatchdogDisable + ?Sized,
+{
+    fn disable(&mut self) {
+        (**self).disable()
+    }
+}
+
+impl<T> WatchdogDisable for &mut T
+where
+    T: WatchdogDisable + ?Sized,
+{
+    fn disable(&mut self) {
+        (**self).disable()
+    }
+}